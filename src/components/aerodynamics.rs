@@ -0,0 +1,155 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// Applies aerodynamic drag and lift to a [dynamic rigid body](RigidBody::Dynamic) based on
+/// the shape of its [`Collider`].
+///
+/// Unlike [`LinearDamping`], the drag is quadratic with respect to the body's speed relative to the air,
+/// and it depends on the body's orientation through the area of the collider
+/// [projected](Collider::projected_area) onto the direction of the airflow. The properties
+/// of the air are configured using the [`Atmosphere`] resource.
+///
+/// The drag force is computed using the drag equation:
+///
+/// `F_drag = 0.5 * air_density * speed² * drag_coefficient * projected_area`
+///
+/// If the lift coefficient is non-zero, the body also acts like a thin wing with its surface normal
+/// along the local `lift_axis`. The lift is perpendicular to the airflow, and it depends on the
+/// angle of attack and the area of the collider projected onto the lift axis.
+///
+/// Only the collider on the same entity as the rigid body is taken into account.
+/// The forces are applied at the center of mass every substep, and the drag is limited so that it
+/// can stop the motion relative to the air but never reverse it.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// # #[cfg(all(feature = "3d", feature = "f32"))]
+/// fn setup(mut commands: Commands) {
+///     // A ball with the drag coefficient of a sphere
+///     commands.spawn((
+///         RigidBody::Dynamic,
+///         Collider::ball(0.1),
+///         Aerodynamics::new(0.47),
+///     ));
+///
+///     // A glider with lift generated along its local up axis
+///     commands.spawn((
+///         RigidBody::Dynamic,
+///         Collider::cuboid(1.0, 0.05, 4.0),
+///         Aerodynamics::new(0.1).with_lift(1.2, Vec3::Y),
+///     ));
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct Aerodynamics {
+    /// The drag coefficient of the body. The default is `1.0`.
+    pub drag_coefficient: Scalar,
+    /// The maximum lift coefficient of the body, reached at an angle of attack of 45 degrees.
+    /// The default is `0.0`, which means that no lift is generated.
+    pub lift_coefficient: Scalar,
+    /// The local surface normal of the lifting surface. The default is the local Y axis.
+    pub lift_axis: Vector,
+}
+
+impl Default for Aerodynamics {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+impl Aerodynamics {
+    /// Creates a new [`Aerodynamics`] component with the given drag coefficient and no lift.
+    pub fn new(drag_coefficient: Scalar) -> Self {
+        Self {
+            drag_coefficient,
+            lift_coefficient: 0.0,
+            lift_axis: Vector::Y,
+        }
+    }
+
+    /// Enables lift with the given lift coefficient along the given local surface normal.
+    pub fn with_lift(mut self, lift_coefficient: Scalar, lift_axis: Vector) -> Self {
+        self.lift_coefficient = lift_coefficient;
+        self.lift_axis = lift_axis;
+        self
+    }
+
+    /// Computes the world-space aerodynamic force for a body with the given `collider` and `rotation`
+    /// moving at `velocity` relative to the air with the given `air_density`.
+    ///
+    /// This is the sum of the [drag](Self::drag) and the [lift](Self::lift).
+    pub fn force(
+        &self,
+        collider: &Collider,
+        rotation: &Rotation,
+        velocity: Vector,
+        air_density: Scalar,
+    ) -> Vector {
+        self.drag(collider, rotation, velocity, air_density)
+            + self.lift(collider, rotation, velocity, air_density)
+    }
+
+    /// Computes the world-space drag force for a body with the given `collider` and `rotation`
+    /// moving at `velocity` relative to the air with the given `air_density`.
+    ///
+    /// The drag always opposes the `velocity`.
+    pub fn drag(
+        &self,
+        collider: &Collider,
+        rotation: &Rotation,
+        velocity: Vector,
+        air_density: Scalar,
+    ) -> Vector {
+        let speed_squared = velocity.length_squared();
+        if speed_squared <= Scalar::EPSILON {
+            return Vector::ZERO;
+        }
+        let direction = velocity / speed_squared.sqrt();
+        let dynamic_pressure = 0.5 * air_density * speed_squared;
+
+        let drag_area = collider.projected_area(rotation.inverse().rotate(direction));
+        -direction * dynamic_pressure * self.drag_coefficient * drag_area
+    }
+
+    /// Computes the world-space lift force for a body with the given `collider` and `rotation`
+    /// moving at `velocity` relative to the air with the given `air_density`.
+    ///
+    /// The lift is perpendicular to the `velocity`, and it is zero if the lift coefficient is zero.
+    pub fn lift(
+        &self,
+        collider: &Collider,
+        rotation: &Rotation,
+        velocity: Vector,
+        air_density: Scalar,
+    ) -> Vector {
+        let speed_squared = velocity.length_squared();
+        if self.lift_coefficient == 0.0 || speed_squared <= Scalar::EPSILON {
+            return Vector::ZERO;
+        }
+        let direction = velocity / speed_squared.sqrt();
+        let dynamic_pressure = 0.5 * air_density * speed_squared;
+
+        // Thin plate approximation: the lift coefficient is proportional to sin(2 * angle of attack)
+        let normal = rotation.rotate(self.lift_axis.normalize_or_zero());
+        let sin_attack = -direction.dot(normal);
+        let cos_attack = (1.0 - sin_attack * sin_attack).max(0.0).sqrt();
+        let lift_direction = (normal + direction * sin_attack).normalize_or_zero();
+        let lift_area = collider.projected_area(self.lift_axis);
+        lift_direction
+            * dynamic_pressure
+            * self.lift_coefficient
+            * 2.0
+            * sin_attack
+            * cos_attack
+            * lift_area
+    }
+}
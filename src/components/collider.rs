@@ -1,9 +1,12 @@
-use std::fmt;
+use std::{fmt, sync::Arc};
 
 use crate::{prelude::*, utils::make_isometry};
-#[cfg(all(feature = "3d", feature = "collider-from-mesh"))]
+#[cfg(feature = "collider-from-mesh")]
 use bevy::render::mesh::{Indices, VertexAttributeValues};
-#[cfg(all(feature = "3d", feature = "async-collider"))]
+#[cfg(any(
+    all(feature = "2d", feature = "collider-from-mesh"),
+    all(feature = "3d", feature = "async-collider")
+))]
 use bevy::utils::HashMap;
 use bevy::{
     ecs::entity::{EntityMapper, MapEntities},
@@ -294,6 +297,67 @@ impl Collider {
         ColliderMassProperties::new(self, density)
     }
 
+    /// Computes the area of the collider projected onto a plane perpendicular to the given local `direction`.
+    /// In 2D, this is the width of the collider perpendicular to the direction.
+    ///
+    /// The area is exact for balls, cuboids and capsules. For other shapes,
+    /// it is approximated using the collider's local bounding box.
+    pub fn projected_area(&self, direction: Vector) -> Scalar {
+        let direction = direction.normalize_or_zero();
+
+        // Projected area of a box with the given half extents
+        let box_area = |half_extents: Vector| {
+            let [x, y] = [half_extents.x, half_extents.y];
+            #[cfg(feature = "2d")]
+            {
+                2.0 * (y * direction.x.abs() + x * direction.y.abs())
+            }
+            #[cfg(feature = "3d")]
+            {
+                let z = half_extents.z;
+                4.0 * (y * z * direction.x.abs()
+                    + x * z * direction.y.abs()
+                    + x * y * direction.z.abs())
+            }
+        };
+
+        match self.shape_scaled().as_typed_shape() {
+            TypedShape::Ball(ball) => {
+                #[cfg(feature = "2d")]
+                {
+                    2.0 * ball.radius
+                }
+                #[cfg(feature = "3d")]
+                {
+                    PI * ball.radius.powi(2)
+                }
+            }
+            TypedShape::Cuboid(cuboid) => box_area(cuboid.half_extents.into()),
+            TypedShape::Capsule(capsule) => {
+                let segment: Vector = (capsule.segment.b - capsule.segment.a).into();
+                let length = segment.length();
+                // Sine of the angle between the capsule's axis and the direction
+                let sin = (1.0 - segment.normalize_or_zero().dot(direction).powi(2))
+                    .max(0.0)
+                    .sqrt();
+                #[cfg(feature = "2d")]
+                {
+                    2.0 * capsule.radius + length * sin
+                }
+                #[cfg(feature = "3d")]
+                {
+                    PI * capsule.radius.powi(2) + 2.0 * capsule.radius * length * sin
+                }
+            }
+            _ => box_area(
+                self.shape_scaled()
+                    .compute_local_aabb()
+                    .half_extents()
+                    .into(),
+            ),
+        }
+    }
+
     /// Creates a collider with a compound shape defined by a given vector of colliders with a position and a rotation.
     ///
     /// Especially for dynamic rigid bodies, compound shape colliders should be preferred over triangle meshes and polylines,
@@ -496,7 +560,17 @@ impl Collider {
         SharedShape::heightfield(heights, scale.into()).into()
     }
 
-    /// Creates a collider with a triangle mesh shape from a `Mesh`.
+    /// Creates a collider with a heightfield shape from a grayscale heightmap `Image`.
+    ///
+    /// Each pixel is a subdivision point of the heightfield. The columns of the image go along the `X` axis,
+    /// and the rows go along the `Z` axis so that the top of the image is at negative `Z`.
+    /// Black pixels are at `min_height` and white pixels are at `max_height`, and the heights are then
+    /// multiplied by `scale.y`. The `X` and `Z` components of `scale` are the size of the heightfield
+    /// on the `XZ` plane.
+    ///
+    /// For color images, the luminance is used. 16-bit grayscale formats are recommended for smooth terrain.
+    ///
+    /// Returns `None` if the image format isn't supported or if the image is smaller than 2x2 pixels.
     ///
     /// ## Example
     ///
@@ -504,18 +578,265 @@ impl Collider {
     /// use bevy::prelude::*;
     /// use bevy_xpbd_3d::prelude::*;
     ///
+    /// fn spawn_terrain(mut commands: Commands, images: Res<Assets<Image>>, terrain: Res<Terrain>) {
+    ///     if let Some(image) = images.get(&terrain.heightmap) {
+    ///         // A 500x500 meter terrain with heights between -10 and 40 meters
+    ///         let collider =
+    ///             Collider::heightfield_from_image(image, Vec3::new(500.0, 1.0, 500.0), -10.0, 40.0);
+    ///         commands.spawn((RigidBody::Static, collider.unwrap()));
+    ///     }
+    /// }
+    /// # #[derive(Resource)]
+    /// # struct Terrain { heightmap: Handle<Image> }
+    /// ```
+    #[cfg(all(feature = "3d", feature = "collider-from-image"))]
+    pub fn heightfield_from_image(
+        image: &Image,
+        scale: Vector,
+        min_height: Scalar,
+        max_height: Scalar,
+    ) -> Option<Self> {
+        let (width, height) = (image.width() as usize, image.height() as usize);
+        if width < 2 || height < 2 {
+            return None;
+        }
+
+        let luminance = image.clone().try_into_dynamic().ok()?.into_luma16();
+        let heights = nalgebra::DMatrix::from_fn(height, width, |row, column| {
+            let value = luminance.get_pixel(column as u32, row as u32).0[0] as Scalar / 65535.0;
+            min_height + value * (max_height - min_height)
+        });
+
+        Some(SharedShape::heightfield(heights, scale.into()).into())
+    }
+
+    /// Makes the given segment of a heightfield collider a hole, or fills the hole if `hole` is false.
+    /// Bodies pass through holes.
+    ///
+    /// Returns an error if the collider isn't a heightfield.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the segment is out of bounds.
+    #[cfg(feature = "2d")]
+    pub fn set_heightfield_hole(
+        &mut self,
+        segment: usize,
+        hole: bool,
+    ) -> Result<(), UnsupportedShape> {
+        let heightfield = self
+            .shape
+            .make_mut()
+            .as_heightfield_mut()
+            .ok_or(parry::query::Unsupported)?;
+        heightfield.set_segment_removed(segment, hole);
+        self.set_shape(self.shape.clone());
+        Ok(())
+    }
+
+    /// Makes the given cell of a heightfield collider a hole, or fills the hole if `hole` is false.
+    /// Bodies pass through holes.
+    ///
+    /// The cell is between the subdivision points `row..=row + 1` along the `Z` axis
+    /// and `column..=column + 1` along the `X` axis.
+    ///
+    /// Returns an error if the collider isn't a heightfield.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cell is out of bounds.
+    #[cfg(feature = "3d")]
+    pub fn set_heightfield_hole(
+        &mut self,
+        row: usize,
+        column: usize,
+        hole: bool,
+    ) -> Result<(), UnsupportedShape> {
+        use parry::shape::HeightFieldCellStatus;
+
+        let heightfield = self
+            .shape
+            .make_mut()
+            .as_heightfield_mut()
+            .ok_or(parry::query::Unsupported)?;
+        let mut status = heightfield.cell_status(row, column);
+        status.set(HeightFieldCellStatus::CELL_REMOVED, hole);
+        heightfield.set_cell_status(row, column, status);
+        self.set_shape(self.shape.clone());
+        Ok(())
+    }
+
+    /// Replaces the heights of a range of subdivision points of a heightfield collider,
+    /// starting from the point at index `start`. The holes of the heightfield are kept.
+    ///
+    /// Unlike creating a new collider, this doesn't require the source data of the whole heightfield,
+    /// so it can be used for deforming terrain at runtime.
+    ///
+    /// Returns an error if the collider isn't a heightfield.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    #[cfg(feature = "2d")]
+    pub fn patch_heightfield(
+        &mut self,
+        start: usize,
+        heights: &[Scalar],
+    ) -> Result<(), UnsupportedShape> {
+        use parry::shape::HeightField;
+
+        let heightfield = self
+            .shape
+            .as_heightfield()
+            .ok_or(parry::query::Unsupported)?;
+
+        let mut new_heights = heightfield.heights().clone();
+        new_heights
+            .rows_mut(start, heights.len())
+            .copy_from_slice(heights);
+
+        // Parry doesn't allow modifying the heights in place, so only the heights and holes are copied
+        let mut patched = HeightField::new(new_heights, *heightfield.scale());
+        for segment in 0..heightfield.num_cells() {
+            patched.set_segment_removed(segment, heightfield.is_segment_removed(segment));
+        }
+
+        self.set_shape(SharedShape::new(patched));
+        Ok(())
+    }
+
+    /// Replaces the heights of a rectangular region of subdivision points of a heightfield collider,
+    /// starting from the point at `start_row` along the `Z` axis and `start_column` along the `X` axis.
+    /// `heights[i][j]` is the new height of the point at row `start_row + i` and column `start_column + j`.
+    /// The holes of the heightfield are kept.
+    ///
+    /// Unlike creating a new collider, this doesn't require the source data of the whole heightfield,
+    /// so it can be used for deforming terrain at runtime.
+    ///
+    /// Returns an error if the collider isn't a heightfield.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the region is out of bounds.
+    #[cfg(feature = "3d")]
+    pub fn patch_heightfield(
+        &mut self,
+        start_row: usize,
+        start_column: usize,
+        heights: &[Vec<Scalar>],
+    ) -> Result<(), UnsupportedShape> {
+        use parry::shape::HeightField;
+
+        let heightfield = self
+            .shape
+            .as_heightfield()
+            .ok_or(parry::query::Unsupported)?;
+
+        let mut new_heights = heightfield.heights().clone();
+        for (i, row) in heights.iter().enumerate() {
+            for (j, height) in row.iter().enumerate() {
+                new_heights[(start_row + i, start_column + j)] = *height;
+            }
+        }
+
+        // Parry doesn't allow modifying the heights in place, so only the heights and holes are copied
+        let mut patched = HeightField::new(new_heights, *heightfield.scale());
+        *patched.cells_statuses_mut() = heightfield.cells_statuses().clone();
+
+        self.set_shape(SharedShape::new(patched));
+        Ok(())
+    }
+
+    /// Creates a collider with a user-defined [`CustomShape`].
+    pub fn custom(shape: impl CustomShape) -> Self {
+        SharedShape::new(CustomShapeWrapper::new(Arc::new(shape))).into()
+    }
+
+    /// Returns the [`CustomShape`] of the collider, or `None` if the collider doesn't have a custom shape of type `T`.
+    pub fn as_custom<T: CustomShape>(&self) -> Option<&T> {
+        self.shape
+            .as_shape::<CustomShapeWrapper>()
+            .and_then(|shape| shape.downcast_ref::<T>())
+    }
+
+    /// Creates a collider with a [`VoxelCollider`] shape.
+    #[cfg(feature = "3d")]
+    pub fn voxels(voxels: VoxelCollider) -> Self {
+        SharedShape::new(voxels).into()
+    }
+
+    /// Returns the [`VoxelCollider`] shape of the collider, or `None` if the collider isn't made of voxels.
+    #[cfg(feature = "3d")]
+    pub fn as_voxels(&self) -> Option<&VoxelCollider> {
+        self.shape.as_shape::<VoxelCollider>()
+    }
+
+    /// Makes the given voxel of a [`VoxelCollider`] solid or empty.
+    ///
+    /// Changing the voxel itself is a constant-time operation, but an edit can have other costs:
+    ///
+    /// - The shape is shared with clones of the collider, so if it's shared when it's edited,
+    ///   the voxel grid is cloned first. This is an `O(n)` copy of the voxels, but the bounding volume
+    ///   hierarchy is never copied.
+    /// - The edit marks the [`Collider`] as changed, so the mass properties of the collider and its body
+    ///   are recomputed from all solid voxels in the next physics step.
+    ///
+    /// The mass properties are only recomputed once per step, so many voxels can be changed in the same frame
+    /// without extra work.
+    ///
+    /// Returns an error if the collider isn't made of voxels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the voxel is outside of the grid.
+    #[cfg(feature = "3d")]
+    pub fn set_voxel(&mut self, voxel: UVec3, solid: bool) -> Result<(), UnsupportedShape> {
+        if self.as_voxels().is_none() {
+            return Err(parry::query::Unsupported);
+        }
+        // Both the unscaled and the scaled shape are edited in place instead of scaling the shape again
+        for shape in [&mut self.shape, &mut self.scaled_shape] {
+            if let Some(voxels) = shape.make_mut().as_shape_mut::<VoxelCollider>() {
+                voxels.set_solid(voxel, solid);
+            }
+        }
+        Ok(())
+    }
+
+    /// Creates a collider with a triangle mesh shape from a `Mesh`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    #[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+    #[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+    ///
     /// fn setup(mut commands: Commands, mut meshes: ResMut<Assets<Mesh>>) {
-    ///     let mesh = Mesh::from(shape::Cube { size: 1.0 });
+    #[cfg_attr(
+        feature = "2d",
+        doc = "    let mesh = Mesh::from(shape::Circle::new(0.5));"
+    )]
+    #[cfg_attr(
+        feature = "3d",
+        doc = "    let mesh = Mesh::from(shape::Cube { size: 1.0 });"
+    )]
     ///     commands.spawn((
     ///         Collider::trimesh_from_mesh(&mesh).unwrap(),
-    ///         PbrBundle {
-    ///             mesh: meshes.add(mesh),
+    #[cfg_attr(
+        feature = "2d",
+        doc = "        ColorMesh2dBundle {\n            mesh: meshes.add(mesh).into(),"
+    )]
+    #[cfg_attr(
+        feature = "3d",
+        doc = "        PbrBundle {\n            mesh: meshes.add(mesh),"
+    )]
     ///             ..default()
     ///         },
     ///     ));
     /// }
     /// ```
-    #[cfg(all(feature = "3d", feature = "collider-from-mesh"))]
+    #[cfg(feature = "collider-from-mesh")]
     pub fn trimesh_from_mesh(mesh: &Mesh) -> Option<Self> {
         extract_mesh_vertices_indices(mesh).map(|(vertices, indices)| {
             SharedShape::trimesh_with_flags(
@@ -534,26 +855,100 @@ impl Collider {
     ///
     /// ```
     /// use bevy::prelude::*;
-    /// use bevy_xpbd_3d::prelude::*;
+    #[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+    #[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
     ///
     /// fn setup(mut commands: Commands, mut meshes: ResMut<Assets<Mesh>>) {
-    ///     let mesh = Mesh::from(shape::Cube { size: 1.0 });
+    #[cfg_attr(
+        feature = "2d",
+        doc = "    let mesh = Mesh::from(shape::Circle::new(0.5));"
+    )]
+    #[cfg_attr(
+        feature = "3d",
+        doc = "    let mesh = Mesh::from(shape::Cube { size: 1.0 });"
+    )]
     ///     commands.spawn((
     ///         Collider::trimesh_from_mesh_with_config(&mesh, TriMeshFlags::all()).unwrap(),
-    ///         PbrBundle {
-    ///             mesh: meshes.add(mesh),
+    #[cfg_attr(
+        feature = "2d",
+        doc = "        ColorMesh2dBundle {\n            mesh: meshes.add(mesh).into(),"
+    )]
+    #[cfg_attr(
+        feature = "3d",
+        doc = "        PbrBundle {\n            mesh: meshes.add(mesh),"
+    )]
     ///             ..default()
     ///         },
     ///     ));
     /// }
     /// ```
-    #[cfg(all(feature = "3d", feature = "collider-from-mesh"))]
+    #[cfg(feature = "collider-from-mesh")]
     pub fn trimesh_from_mesh_with_config(mesh: &Mesh, flags: TriMeshFlags) -> Option<Self> {
         extract_mesh_vertices_indices(mesh).map(|(vertices, indices)| {
             SharedShape::trimesh_with_flags(vertices, indices, flags).into()
         })
     }
 
+    /// Creates a collider with a polyline shape from the outline of a `Mesh`.
+    ///
+    /// The `X` and `Y` coordinates of the vertices are used, and the outline consists of the edges
+    /// that only belong to a single triangle. Vertices at the same position are merged, so the outline
+    /// doesn't include seams between triangles that don't share vertices.
+    ///
+    /// Returns `None` if the mesh doesn't have triangle indices or positions.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_xpbd_2d::prelude::*;
+    ///
+    /// fn setup(mut commands: Commands, mut meshes: ResMut<Assets<Mesh>>) {
+    ///     let mesh = Mesh::from(shape::RegularPolygon::new(1.0, 6));
+    ///     commands.spawn((
+    ///         RigidBody::Static,
+    ///         Collider::polyline_from_mesh(&mesh).unwrap(),
+    ///         ColorMesh2dBundle {
+    ///             mesh: meshes.add(mesh).into(),
+    ///             ..default()
+    ///         },
+    ///     ));
+    /// }
+    /// ```
+    #[cfg(all(feature = "2d", feature = "collider-from-mesh"))]
+    pub fn polyline_from_mesh(mesh: &Mesh) -> Option<Self> {
+        let (vertices, indices) = extract_mesh_vertices_indices(mesh)?;
+        let outline = extract_mesh_outline(&vertices, &indices);
+        (!outline.is_empty()).then(|| SharedShape::polyline(vertices, Some(outline)).into())
+    }
+
+    /// Creates a collider with a convex polygon shape obtained from the convex hull of a `Mesh`.
+    ///
+    /// The `X` and `Y` coordinates of the vertices are used.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_xpbd_2d::prelude::*;
+    ///
+    /// fn setup(mut commands: Commands, mut meshes: ResMut<Assets<Mesh>>) {
+    ///     let mesh = Mesh::from(shape::Circle::new(0.5));
+    ///     commands.spawn((
+    ///         Collider::convex_hull_from_mesh(&mesh).unwrap(),
+    ///         ColorMesh2dBundle {
+    ///             mesh: meshes.add(mesh).into(),
+    ///             ..default()
+    ///         },
+    ///     ));
+    /// }
+    /// ```
+    #[cfg(all(feature = "2d", feature = "collider-from-mesh"))]
+    pub fn convex_hull_from_mesh(mesh: &Mesh) -> Option<Self> {
+        extract_mesh_vertices_indices(mesh)
+            .and_then(|(vertices, _)| SharedShape::convex_hull(&vertices).map(|shape| shape.into()))
+    }
+
     /// Creates a collider with a convex polygon shape obtained from the convex hull of a `Mesh`.
     ///
     /// ## Example
@@ -579,6 +974,34 @@ impl Collider {
             .and_then(|(vertices, _)| SharedShape::convex_hull(&vertices).map(|shape| shape.into()))
     }
 
+    /// Creates a compound shape obtained from the decomposition of the outline of a `Mesh`.
+    ///
+    /// The outline is computed like in [`Collider::polyline_from_mesh`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_xpbd_2d::prelude::*;
+    ///
+    /// fn setup(mut commands: Commands, mut meshes: ResMut<Assets<Mesh>>) {
+    ///     let mesh = Mesh::from(shape::Quad::new(Vec2::new(2.0, 1.0)));
+    ///     commands.spawn((
+    ///         Collider::convex_decomposition_from_mesh(&mesh).unwrap(),
+    ///         ColorMesh2dBundle {
+    ///             mesh: meshes.add(mesh).into(),
+    ///             ..default()
+    ///         },
+    ///     ));
+    /// }
+    /// ```
+    #[cfg(all(feature = "2d", feature = "collider-from-mesh"))]
+    pub fn convex_decomposition_from_mesh(mesh: &Mesh) -> Option<Self> {
+        let (vertices, indices) = extract_mesh_vertices_indices(mesh)?;
+        let outline = extract_mesh_outline(&vertices, &indices);
+        (!outline.is_empty()).then(|| SharedShape::convex_decomposition(&vertices, &outline).into())
+    }
+
     /// Creates a compound shape obtained from the decomposition of a `Mesh`.
     ///
     /// ## Example
@@ -605,6 +1028,44 @@ impl Collider {
         })
     }
 
+    /// Creates a compound shape obtained from the decomposition of the outline of a `Mesh`
+    /// with the given [`VHACDParameters`] passed to the decomposition algorithm.
+    ///
+    /// The outline is computed like in [`Collider::polyline_from_mesh`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_xpbd_2d::prelude::*;
+    ///
+    /// fn setup(mut commands: Commands, mut meshes: ResMut<Assets<Mesh>>) {
+    ///     let mesh = Mesh::from(shape::Quad::new(Vec2::new(2.0, 1.0)));
+    ///     let config = VHACDParameters {
+    ///         convex_hull_approximation: false,
+    ///         ..default()
+    ///     };
+    ///     commands.spawn((
+    ///         Collider::convex_decomposition_from_mesh_with_config(&mesh, &config).unwrap(),
+    ///         ColorMesh2dBundle {
+    ///             mesh: meshes.add(mesh).into(),
+    ///             ..default()
+    ///         },
+    ///     ));
+    /// }
+    /// ```
+    #[cfg(all(feature = "2d", feature = "collider-from-mesh"))]
+    pub fn convex_decomposition_from_mesh_with_config(
+        mesh: &Mesh,
+        parameters: &VHACDParameters,
+    ) -> Option<Self> {
+        let (vertices, indices) = extract_mesh_vertices_indices(mesh)?;
+        let outline = extract_mesh_outline(&vertices, &indices);
+        (!outline.is_empty()).then(|| {
+            SharedShape::convex_decomposition_with_params(&vertices, &outline, parameters).into()
+        })
+    }
+
     /// Creates a compound shape obtained from the decomposition of a `Mesh`
     /// with the given [`VHACDParameters`] passed to the decomposition algorithm.
     ///
@@ -640,25 +1101,27 @@ impl Collider {
     }
 }
 
-#[cfg(all(feature = "3d", feature = "collider-from-mesh"))]
-type VerticesIndices = (Vec<nalgebra::Point3<Scalar>>, Vec<[u32; 3]>);
+#[cfg(feature = "collider-from-mesh")]
+pub(crate) type VerticesIndices = (Vec<parry::math::Point<Scalar>>, Vec<[u32; 3]>);
 
-#[cfg(all(feature = "3d", feature = "collider-from-mesh"))]
-fn extract_mesh_vertices_indices(mesh: &Mesh) -> Option<VerticesIndices> {
+/// Extracts the vertex positions and triangle indices of a `Mesh`.
+/// In 2D, the `Z` coordinates of the vertices are ignored.
+#[cfg(feature = "collider-from-mesh")]
+pub(crate) fn extract_mesh_vertices_indices(mesh: &Mesh) -> Option<VerticesIndices> {
     let vertices = mesh.attribute(Mesh::ATTRIBUTE_POSITION)?;
     let indices = mesh.indices()?;
 
+    #[cfg(feature = "2d")]
+    let point =
+        |v: &[f32]| -> parry::math::Point<Scalar> { [v[0] as Scalar, v[1] as Scalar].into() };
+    #[cfg(feature = "3d")]
+    let point = |v: &[f32]| -> parry::math::Point<Scalar> {
+        [v[0] as Scalar, v[1] as Scalar, v[2] as Scalar].into()
+    };
+
     let vtx: Vec<_> = match vertices {
-        VertexAttributeValues::Float32(vtx) => Some(
-            vtx.chunks(3)
-                .map(|v| [v[0] as Scalar, v[1] as Scalar, v[2] as Scalar].into())
-                .collect(),
-        ),
-        VertexAttributeValues::Float32x3(vtx) => Some(
-            vtx.iter()
-                .map(|v| [v[0] as Scalar, v[1] as Scalar, v[2] as Scalar].into())
-                .collect(),
-        ),
+        VertexAttributeValues::Float32(vtx) => Some(vtx.chunks(3).map(point).collect()),
+        VertexAttributeValues::Float32x3(vtx) => Some(vtx.iter().map(|v| point(v)).collect()),
         _ => None,
     }?;
 
@@ -673,6 +1136,44 @@ fn extract_mesh_vertices_indices(mesh: &Mesh) -> Option<VerticesIndices> {
     Some((vtx, idx))
 }
 
+/// Computes the outline of a 2D triangle mesh as the edges that only belong to a single triangle.
+///
+/// Vertices at the same position are merged, and the edges keep the winding of their triangles.
+#[cfg(all(feature = "2d", feature = "collider-from-mesh"))]
+fn extract_mesh_outline(
+    vertices: &[parry::math::Point<Scalar>],
+    indices: &[[u32; 3]],
+) -> Vec<[u32; 2]> {
+    // Map each vertex to the first vertex at the same position
+    let mut unique = HashMap::default();
+    let canonical: Vec<u32> = vertices
+        .iter()
+        .enumerate()
+        .map(|(i, v)| {
+            *unique
+                .entry((v.x.to_bits(), v.y.to_bits()))
+                .or_insert(i as u32)
+        })
+        .collect();
+
+    // Count the triangles that each undirected edge belongs to, in a deterministic order
+    let mut edges: indexmap::IndexMap<(u32, u32), ([u32; 2], usize)> = default();
+    for triangle in indices {
+        let [a, b, c] = triangle.map(|i| canonical[i as usize]);
+        if a == b || b == c || c == a {
+            continue;
+        }
+        for [i, j] in [[a, b], [b, c], [c, a]] {
+            edges.entry((i.min(j), i.max(j))).or_insert(([i, j], 0)).1 += 1;
+        }
+    }
+
+    edges
+        .into_values()
+        .filter_map(|(edge, count)| (count == 1).then_some(edge))
+        .collect()
+}
+
 fn scale_shape(
     shape: &SharedShape,
     scale: Vector,
@@ -832,38 +1333,111 @@ fn scale_shape(
             }
             Ok(SharedShape::compound(scaled))
         }
+        #[cfg(feature = "3d")]
+        TypedShape::Custom(_) if shape.as_shape::<VoxelCollider>().is_some() => {
+            let voxels = shape.as_shape::<VoxelCollider>().unwrap();
+            Ok(SharedShape::new(voxels.scaled(scale)))
+        }
+        TypedShape::Custom(_) if shape.as_shape::<CustomShapeWrapper>().is_some() => {
+            let custom = shape.as_shape::<CustomShapeWrapper>().unwrap();
+            custom
+                .scaled(scale)
+                .map(SharedShape::new)
+                .ok_or(parry::query::Unsupported)
+        }
         _ => Err(parry::query::Unsupported),
     }
 }
 
-/// A component that will automatically generate a [`Collider`] based on the entity's `Mesh`.
-/// The type of the generated collider can be specified using [`ComputedCollider`].
+/// A component that will automatically generate a [`Collider`] based on the entity's `Mesh`
+/// once the mesh asset has been loaded. The type of the generated collider can be specified using [`ComputedCollider`].
+///
+/// In 2D, the mesh is read from the entity's `Mesh2dHandle`, and in 3D from its `Handle<Mesh>`.
+///
+/// The collider is computed on the `AsyncComputeTaskPool`, so expensive shapes like convex decompositions
+/// don't block the main schedule. From the moment the component is added until the collider has been inserted,
+/// including while the mesh is loading, the entity has the [`ColliderPending`] component, and a [`ColliderComputed`]
+/// event is sent once the computation has finished.
 ///
 /// ## Example
 ///
 /// ```
 /// use bevy::prelude::*;
-/// use bevy_xpbd_3d::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
 ///
 /// fn setup(mut commands: Commands, mut assets: ResMut<AssetServer>, mut meshes: Assets<Mesh>) {
-///     // Spawn a cube with a convex hull collider generated from the mesh
-///     commands.spawn((
-///         AsyncCollider(ComputedCollider::ConvexHull),
-///         PbrBundle {
-///             mesh: meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
-///             ..default()
-///         },
-///     ));
+#[cfg_attr(
+    feature = "2d",
+    doc = "    // Spawn a hexagon with a convex hull collider generated from the mesh
+    commands.spawn((
+        AsyncCollider(ComputedCollider::ConvexHull),
+        ColorMesh2dBundle {
+            mesh: meshes.add(Mesh::from(shape::RegularPolygon::new(1.0, 6))).into(),
+            ..default()
+        },
+    ));"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "    // Spawn a cube with a convex hull collider generated from the mesh
+    commands.spawn((
+        AsyncCollider(ComputedCollider::ConvexHull),
+        PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
+            ..default()
+        },
+    ));"
+)]
 /// }
 /// ```
-#[cfg(all(feature = "3d", feature = "async-collider"))]
+#[cfg(feature = "async-collider")]
 #[derive(Component, Clone, Debug, Default, Deref, DerefMut)]
 pub struct AsyncCollider(pub ComputedCollider);
 
+/// A marker component for entities whose [`Collider`] is being computed in the background
+/// from a mesh or an image, for example using [`AsyncCollider`].
+///
+/// The component is inserted as soon as the component describing the collider is added, so it also covers the time
+/// spent loading the assets. It's removed once the computation has finished and a [`ColliderComputed`] event has been sent.
+/// It can be used to show loading states or to wait for the colliders of a level before starting the game.
+#[cfg(feature = "async-collider")]
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ColliderPending;
+
+/// An event that is sent when the background computation of a [`Collider`] has finished
+/// and the [`ColliderPending`] component has been removed from the entity.
+#[cfg(feature = "async-collider")]
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ColliderComputed {
+    /// The entity that the collider was computed for.
+    pub entity: Entity,
+    /// True if the collider was generated and inserted. If false, no collider could be generated
+    /// from the mesh or image.
+    pub succeeded: bool,
+}
+
 /// A component that will automatically generate colliders for the meshes in a scene
 /// once the scene has been loaded. The type of the generated collider can be specified
 /// using [`ComputedCollider`].
 ///
+/// Like with [`AsyncCollider`], the colliders are computed in the background. The scene entity has
+/// the [`ColliderPending`] component until the scene has been spawned, and after that each mesh entity
+/// has it until its collider has been inserted.
+///
+/// ## Name patterns
+///
+/// Colliders can be configured for meshes by their exact name, or for all meshes matching a name pattern
+/// like `*_col`. In patterns, `*` matches any sequence of characters and `?` matches any single character.
+/// Patterns are matched against the name of the mesh and the name of its parent node, since tools like Blender
+/// export object names as node names.
+///
+/// Exact names take priority over patterns, and patterns are checked in the order they were added.
+/// Meshes that match neither use [`default_shape`](#structfield.default_shape).
+///
+/// Meshes that only exist for collision can be marked as collision-only and hidden or removed
+/// once their colliders have been built. See [`CollisionMeshHandling`].
+///
 /// ## Example
 ///
 /// ```
@@ -897,12 +1471,23 @@ pub struct AsyncCollider(pub ComputedCollider);
 ///
 ///     // Generate colliders for everything except specific meshes by name
 ///     commands.spawn((
-///         SceneBundle { scene, ..default() },
+///         SceneBundle { scene: scene.clone(), ..default() },
 ///         AsyncSceneCollider::new(Some(ComputedCollider::TriMeshWithFlags(
 ///             TriMeshFlags::MERGE_DUPLICATE_VERTICES
 ///         )))
 ///         .without_shape_with_name("Tree"),
 ///     ));
+///
+///     // Use naming conventions from the level editor
+///     commands.spawn((
+///         SceneBundle { scene, ..default() },
+///         AsyncSceneCollider::new(None)
+///             .with_shape_for_pattern("*_col", ComputedCollider::TriMesh)
+///             .with_collision_only_for_pattern("*_col")
+///             .with_shape_for_pattern("*_convex", ComputedCollider::ConvexHull)
+///             .with_sensor_for_pattern("*_trigger")
+///             .with_collision_mesh_handling(CollisionMeshHandling::Remove),
+///     ));
 /// }
 /// ```
 #[cfg(all(feature = "3d", feature = "async-collider"))]
@@ -916,6 +1501,12 @@ pub struct AsyncSceneCollider {
     /// For the meshes not found in this `HashMap`, [`default_shape`](#structfield.default_shape)
     /// and all collision layers will be used instead.
     pub meshes_by_name: HashMap<String, Option<AsyncSceneColliderData>>,
+    /// Specifies data like the collider type and [`CollisionLayers`] for meshes whose name
+    /// or parent node name matches a pattern. The first matching pattern is used.
+    /// Entries with a `None` value will be skipped.
+    pub meshes_by_pattern: Vec<(String, Option<AsyncSceneColliderData>)>,
+    /// Determines what happens to collision-only meshes once their colliders have been built.
+    pub collision_mesh_handling: CollisionMeshHandling,
 }
 
 #[cfg(all(feature = "3d", feature = "async-collider"))]
@@ -924,57 +1515,44 @@ impl AsyncSceneCollider {
     /// meshes set to the given `default_shape`.
     ///
     /// If the given collider type is `None`, all meshes except the ones in
-    /// [`meshes_by_name`](#structfield.meshes_by_name) will be skipped.
-    /// You can add named shapes using [`with_shape_for_name`](Self::with_shape_for_name).
+    /// [`meshes_by_name`](#structfield.meshes_by_name) and [`meshes_by_pattern`](#structfield.meshes_by_pattern)
+    /// will be skipped. You can add named shapes using [`with_shape_for_name`](Self::with_shape_for_name)
+    /// and [`with_shape_for_pattern`](Self::with_shape_for_pattern).
     pub fn new(default_shape: Option<ComputedCollider>) -> Self {
         Self {
             default_shape,
-            meshes_by_name: default(),
+            ..default()
         }
     }
 
     /// Specifies the collider type used for a mesh with the given `name`.
     pub fn with_shape_for_name(mut self, name: &str, shape: ComputedCollider) -> Self {
-        if let Some(Some(data)) = self.meshes_by_name.get_mut(name) {
-            data.shape = shape;
-        } else {
-            self.meshes_by_name.insert(
-                name.to_string(),
-                Some(AsyncSceneColliderData { shape, ..default() }),
-            );
-        }
+        self.data_for_name(name).shape = shape;
         self
     }
 
     /// Specifies the [`CollisionLayers`] used for a mesh with the given `name`.
     pub fn with_layers_for_name(mut self, name: &str, layers: CollisionLayers) -> Self {
-        if let Some(Some(data)) = self.meshes_by_name.get_mut(name) {
-            data.layers = layers;
-        } else {
-            self.meshes_by_name.insert(
-                name.to_string(),
-                Some(AsyncSceneColliderData {
-                    layers,
-                    ..default()
-                }),
-            );
-        }
+        self.data_for_name(name).layers = layers;
         self
     }
 
     /// Specifies the [`ColliderDensity`] used for a mesh with the given `name`.
     pub fn with_density_for_name(mut self, name: &str, density: Scalar) -> Self {
-        if let Some(Some(data)) = self.meshes_by_name.get_mut(name) {
-            data.density = density;
-        } else {
-            self.meshes_by_name.insert(
-                name.to_string(),
-                Some(AsyncSceneColliderData {
-                    density,
-                    ..default()
-                }),
-            );
-        }
+        self.data_for_name(name).density = density;
+        self
+    }
+
+    /// Makes the collider of a mesh with the given `name` a [`Sensor`].
+    pub fn with_sensor_for_name(mut self, name: &str) -> Self {
+        self.data_for_name(name).sensor = true;
+        self
+    }
+
+    /// Marks a mesh with the given `name` as collision-only, so that it is handled
+    /// according to [`collision_mesh_handling`](#structfield.collision_mesh_handling).
+    pub fn with_collision_only_for_name(mut self, name: &str) -> Self {
+        self.data_for_name(name).collision_only = true;
         self
     }
 
@@ -984,6 +1562,152 @@ impl AsyncSceneCollider {
         self.meshes_by_name.insert(name.to_string(), None);
         self
     }
+
+    /// Specifies the collider type used for meshes matching the given name `pattern`.
+    pub fn with_shape_for_pattern(mut self, pattern: &str, shape: ComputedCollider) -> Self {
+        self.data_for_pattern(pattern).shape = shape;
+        self
+    }
+
+    /// Specifies the [`CollisionLayers`] used for meshes matching the given name `pattern`.
+    pub fn with_layers_for_pattern(mut self, pattern: &str, layers: CollisionLayers) -> Self {
+        self.data_for_pattern(pattern).layers = layers;
+        self
+    }
+
+    /// Specifies the [`ColliderDensity`] used for meshes matching the given name `pattern`.
+    pub fn with_density_for_pattern(mut self, pattern: &str, density: Scalar) -> Self {
+        self.data_for_pattern(pattern).density = density;
+        self
+    }
+
+    /// Makes the colliders of meshes matching the given name `pattern` [`Sensor`]s.
+    pub fn with_sensor_for_pattern(mut self, pattern: &str) -> Self {
+        self.data_for_pattern(pattern).sensor = true;
+        self
+    }
+
+    /// Marks meshes matching the given name `pattern` as collision-only, so that they are handled
+    /// according to [`collision_mesh_handling`](#structfield.collision_mesh_handling).
+    pub fn with_collision_only_for_pattern(mut self, pattern: &str) -> Self {
+        self.data_for_pattern(pattern).collision_only = true;
+        self
+    }
+
+    /// Skips collider generation for meshes matching the given name `pattern`.
+    pub fn without_shape_with_pattern(mut self, pattern: &str) -> Self {
+        *self.pattern_entry(pattern) = None;
+        self
+    }
+
+    /// Sets what happens to collision-only meshes once their colliders have been built.
+    pub fn with_collision_mesh_handling(mut self, handling: CollisionMeshHandling) -> Self {
+        self.collision_mesh_handling = handling;
+        self
+    }
+
+    /// Returns the collider configuration for a mesh with the given name and parent node name,
+    /// or `None` if no collider should be generated for it.
+    pub fn data_for_mesh(
+        &self,
+        mesh_name: &str,
+        node_name: Option<&str>,
+    ) -> Option<AsyncSceneColliderData> {
+        if let Some(data) = self.meshes_by_name.get(mesh_name) {
+            return data.clone();
+        }
+
+        let matching_pattern = self.meshes_by_pattern.iter().find(|(pattern, _)| {
+            name_matches_pattern(mesh_name, pattern)
+                || node_name.is_some_and(|name| name_matches_pattern(name, pattern))
+        });
+        if let Some((_, data)) = matching_pattern {
+            return data.clone();
+        }
+
+        self.default_shape
+            .clone()
+            .map(|shape| AsyncSceneColliderData { shape, ..default() })
+    }
+
+    fn data_for_name(&mut self, name: &str) -> &mut AsyncSceneColliderData {
+        self.meshes_by_name
+            .entry(name.to_string())
+            .or_default()
+            .get_or_insert_with(default)
+    }
+
+    fn data_for_pattern(&mut self, pattern: &str) -> &mut AsyncSceneColliderData {
+        self.pattern_entry(pattern).get_or_insert_with(default)
+    }
+
+    fn pattern_entry(&mut self, pattern: &str) -> &mut Option<AsyncSceneColliderData> {
+        let index = self
+            .meshes_by_pattern
+            .iter()
+            .position(|(existing, _)| existing == pattern)
+            .unwrap_or_else(|| {
+                self.meshes_by_pattern.push((pattern.to_string(), None));
+                self.meshes_by_pattern.len() - 1
+            });
+        &mut self.meshes_by_pattern[index].1
+    }
+}
+
+/// Returns true if the `name` matches the `pattern`, where `*` matches any sequence
+/// of characters and `?` matches any single character.
+#[cfg(all(feature = "3d", feature = "async-collider"))]
+fn name_matches_pattern(name: &str, pattern: &str) -> bool {
+    let name: Vec<char> = name.chars().collect();
+    let pattern: Vec<char> = pattern.chars().collect();
+
+    let (mut name_index, mut pattern_index) = (0, 0);
+    // The pattern index after the last `*` and the name index it was matched at,
+    // used for backtracking when the rest of the pattern doesn't match
+    let mut last_star = None;
+
+    while name_index < name.len() {
+        match pattern.get(pattern_index) {
+            Some('*') => {
+                pattern_index += 1;
+                last_star = Some((pattern_index, name_index));
+            }
+            Some(&c) if c == '?' || c == name[name_index] => {
+                pattern_index += 1;
+                name_index += 1;
+            }
+            _ => {
+                // Let the last `*` match one more character
+                let Some((star_pattern_index, star_name_index)) = last_star else {
+                    return false;
+                };
+                pattern_index = star_pattern_index;
+                name_index = star_name_index + 1;
+                last_star = Some((star_pattern_index, name_index));
+            }
+        }
+    }
+
+    pattern[pattern_index..].iter().all(|&c| c == '*')
+}
+
+/// Determines what happens to the collision-only meshes of an [`AsyncSceneCollider`]
+/// once their colliders have been built.
+///
+/// Meshes can be marked as collision-only using methods like
+/// [`AsyncSceneCollider::with_collision_only_for_pattern`]. If a collider couldn't be generated
+/// for a mesh, the mesh is kept as it is.
+#[cfg(all(feature = "3d", feature = "async-collider"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CollisionMeshHandling {
+    /// The meshes are kept as they are.
+    #[default]
+    Keep,
+    /// The meshes are hidden by setting their [`Visibility`] to [`Visibility::Hidden`].
+    Hide,
+    /// The `Handle<Mesh>` components are removed, so the meshes aren't rendered
+    /// and the mesh assets can be unloaded.
+    Remove,
 }
 
 /// Configuration for a specific collider generated from a scene using [`AsyncSceneCollider`].
@@ -996,6 +1720,11 @@ pub struct AsyncSceneColliderData {
     pub layers: CollisionLayers,
     /// The [`ColliderDensity`] used for this collider.
     pub density: Scalar,
+    /// If true, the collider is a [`Sensor`].
+    pub sensor: bool,
+    /// If true, the mesh is only used for collision, and it is handled according to
+    /// [`AsyncSceneCollider::collision_mesh_handling`] once its collider has been built.
+    pub collision_only: bool,
 }
 
 #[cfg(all(feature = "3d", feature = "async-collider"))]
@@ -1005,6 +1734,8 @@ impl Default for AsyncSceneColliderData {
             shape: ComputedCollider::TriMesh,
             layers: CollisionLayers::default(),
             density: 1.0,
+            sensor: false,
+            collision_only: false,
         }
     }
 }
@@ -1014,11 +1745,15 @@ impl Default for AsyncSceneColliderData {
 /// Colliders can be created from meshes with the following components and methods:
 ///
 /// - [`AsyncCollider`] (requires `async-collider` features)
-/// - [`AsyncSceneCollider`] (requires `async-collider` features)
+#[cfg_attr(
+    feature = "3d",
+    doc = "- [`AsyncSceneCollider`] (requires `async-collider` features)"
+)]
 /// - [`Collider::trimesh_from_mesh`]
+#[cfg_attr(feature = "2d", doc = "- [`Collider::polyline_from_mesh`]")]
 /// - [`Collider::convex_hull_from_mesh`]
 /// - [`Collider::convex_decomposition_from_mesh`]
-#[cfg(all(feature = "3d", feature = "collider-from-mesh"))]
+#[cfg(feature = "collider-from-mesh")]
 #[derive(Component, Clone, Debug, Default, PartialEq)]
 pub enum ComputedCollider {
     /// A triangle mesh.
@@ -1026,6 +1761,9 @@ pub enum ComputedCollider {
     TriMesh,
     /// A triangle mesh with a custom configuration.
     TriMeshWithFlags(TriMeshFlags),
+    /// A polyline along the outline of the mesh.
+    #[cfg(feature = "2d")]
+    Polyline,
     /// A convex hull.
     ConvexHull,
     /// A compound shape obtained from a decomposition into convex parts using the specified
@@ -1033,6 +1771,46 @@ pub enum ComputedCollider {
     ConvexDecomposition(VHACDParameters),
 }
 
+#[cfg(feature = "collider-from-mesh")]
+impl ComputedCollider {
+    /// Creates a [`Collider`] of this type from the given `Mesh`.
+    pub(crate) fn compute(&self, mesh: &Mesh) -> Option<Collider> {
+        match self {
+            ComputedCollider::TriMesh => Collider::trimesh_from_mesh(mesh),
+            ComputedCollider::TriMeshWithFlags(flags) => {
+                Collider::trimesh_from_mesh_with_config(mesh, *flags)
+            }
+            #[cfg(feature = "2d")]
+            ComputedCollider::Polyline => Collider::polyline_from_mesh(mesh),
+            ComputedCollider::ConvexHull => Collider::convex_hull_from_mesh(mesh),
+            ComputedCollider::ConvexDecomposition(params) => {
+                Collider::convex_decomposition_from_mesh_with_config(mesh, params)
+            }
+        }
+    }
+
+    /// Creates a [`Collider`] of this type from the given `Mesh`, loading convex decompositions
+    /// from the [`ConvexDecompositionCache`] and storing new ones in it.
+    #[cfg(feature = "collider-asset")]
+    pub(crate) fn compute_cached(
+        &self,
+        mesh: &Mesh,
+        cache: Option<&ConvexDecompositionCache>,
+    ) -> Option<Collider> {
+        let (ComputedCollider::ConvexDecomposition(parameters), Some(cache)) = (self, cache) else {
+            return self.compute(mesh);
+        };
+        if let Some(collider) = cache.load(mesh, parameters) {
+            return Some(collider);
+        }
+        let collider = self.compute(mesh)?;
+        if let Err(error) = cache.store(mesh, parameters, &collider) {
+            log::error!("Unable to cache convex decomposition: {error}");
+        }
+        Some(collider)
+    }
+}
+
 /// A component that stores the `Entity` ID of the [`RigidBody`] that a [`Collider`] is attached to.
 ///
 /// If the collider is a child of a rigid body, this points to the body's `Entity` ID.
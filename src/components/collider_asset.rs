@@ -0,0 +1,173 @@
+//! [`ColliderAsset`] and its [`AssetLoader`].
+
+use std::fmt;
+
+use crate::prelude::*;
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+    utils::BoxedFuture,
+};
+
+/// A [`Collider`] stored as an asset, so that it can be loaded from a file and shared between many entities.
+///
+/// Collider assets are loaded from files with the `.collider.ron` extension in the [RON](https://github.com/ron-rs/ron)
+/// format, and from files with the `.collider.bin` extension in a compact binary format.
+/// Files can be written with [`ColliderAsset::to_ron`] and [`ColliderAsset::to_bytes`].
+///
+/// Adding a `Handle<ColliderAsset>` to an entity inserts the asset's [`Collider`] once the asset has been loaded.
+/// When the asset is modified, for example when the file is changed and hot reloading is enabled,
+/// the colliders of all entities with the handle are replaced.
+///
+/// The shape of the collider is shared between the asset and the entities using it, so expensive shapes
+/// like convex decompositions aren't copied.
+///
+/// Loading collider assets requires the `AssetPlugin` to be added before the [`PhysicsPlugins`].
+///
+/// ## Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn setup(mut commands: Commands, assets: Res<AssetServer>) {
+///     let rock: Handle<ColliderAsset> = assets.load("rock.collider.ron");
+///
+///     // The rocks share the same collider
+///     for i in 0..10 {
+///         commands.spawn((
+///             RigidBody::Dynamic,
+///             rock.clone(),
+///             TransformBundle::from_transform(Transform::from_xyz(i as f32 * 2.0, 0.0, 0.0)),
+///         ));
+///     }
+/// }
+/// ```
+#[derive(Asset, TypePath, Clone, Debug, Deref, DerefMut)]
+pub struct ColliderAsset(pub Collider);
+
+impl ColliderAsset {
+    /// Creates a new [`ColliderAsset`] from a [`Collider`].
+    pub fn new(collider: Collider) -> Self {
+        Self(collider)
+    }
+
+    /// Reads a collider asset from a string in the RON format.
+    pub fn from_ron(ron: &str) -> Result<Self, ColliderAssetError> {
+        Ok(Self(ron::from_str(ron)?))
+    }
+
+    /// Reads a collider asset from bytes in the binary format.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ColliderAssetError> {
+        Ok(Self(bincode::deserialize(bytes)?))
+    }
+
+    /// Writes the collider asset into a string in the RON format.
+    pub fn to_ron(&self) -> Result<String, ColliderAssetError> {
+        Ok(ron::ser::to_string_pretty(
+            &self.0,
+            ron::ser::PrettyConfig::default(),
+        )?)
+    }
+
+    /// Writes the collider asset into bytes in the binary format.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, ColliderAssetError> {
+        Ok(bincode::serialize(&self.0)?)
+    }
+}
+
+impl From<Collider> for ColliderAsset {
+    fn from(collider: Collider) -> Self {
+        Self(collider)
+    }
+}
+
+/// An error that can occur when reading or writing a [`ColliderAsset`].
+#[derive(Debug)]
+pub enum ColliderAssetError {
+    /// The file couldn't be read.
+    Io(std::io::Error),
+    /// The RON data couldn't be read.
+    RonRead(ron::error::SpannedError),
+    /// The RON data couldn't be written.
+    RonWrite(ron::Error),
+    /// The binary data couldn't be read or written.
+    Binary(bincode::Error),
+}
+
+impl fmt::Display for ColliderAssetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "could not read collider asset: {error}"),
+            Self::RonRead(error) => write!(f, "could not parse collider asset: {error}"),
+            Self::RonWrite(error) => write!(f, "could not write collider asset: {error}"),
+            Self::Binary(error) => write!(f, "could not encode collider asset: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for ColliderAssetError {}
+
+impl From<std::io::Error> for ColliderAssetError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<ron::error::SpannedError> for ColliderAssetError {
+    fn from(error: ron::error::SpannedError) -> Self {
+        Self::RonRead(error)
+    }
+}
+
+impl From<ron::Error> for ColliderAssetError {
+    fn from(error: ron::Error) -> Self {
+        Self::RonWrite(error)
+    }
+}
+
+impl From<bincode::Error> for ColliderAssetError {
+    fn from(error: bincode::Error) -> Self {
+        Self::Binary(error)
+    }
+}
+
+/// An [`AssetLoader`] for [`ColliderAsset`]s in `.collider.ron` and `.collider.bin` files.
+#[derive(Default)]
+pub struct ColliderAssetLoader;
+
+impl AssetLoader for ColliderAssetLoader {
+    type Asset = ColliderAsset;
+    type Settings = ();
+    type Error = ColliderAssetError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a Self::Settings,
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+
+            let is_ron = load_context
+                .path()
+                .extension()
+                .is_some_and(|extension| extension == "ron");
+
+            if is_ron {
+                let ron = std::str::from_utf8(&bytes)
+                    .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))?;
+                ColliderAsset::from_ron(ron)
+            } else {
+                ColliderAsset::from_bytes(&bytes)
+            }
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["collider.ron", "collider.bin"]
+    }
+}
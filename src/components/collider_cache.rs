@@ -0,0 +1,195 @@
+//! [`ConvexDecompositionCache`] resource.
+
+use std::{
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use crate::prelude::*;
+use bevy::prelude::*;
+use fxhash::FxHasher64;
+use parry::transformation::voxelization::FillMode;
+
+/// The version of the cache format. Changing it invalidates all cached decompositions.
+const CACHE_VERSION: u32 = 1;
+
+/// A resource that stores convex decompositions of meshes on disk, so that they only need to be computed once.
+///
+/// Computing a convex decomposition with [`Collider::convex_decomposition_from_mesh_with_config`] can take seconds
+/// for detailed meshes. The cache stores each decomposition in its directory as a binary [`ColliderAsset`] file,
+/// keyed by a hash of the mesh's vertices and indices and the [`VHACDParameters`]. If the mesh or the parameters
+/// change, the decomposition is computed again.
+///
+/// When the resource exists, [`AsyncCollider`] and
+#[cfg_attr(feature = "3d", doc = "[`AsyncSceneCollider`]")]
+#[cfg_attr(feature = "2d", doc = "`AsyncSceneCollider`")]
+/// colliders using [`ComputedCollider::ConvexDecomposition`] load the cached decompositions
+/// and store new ones in the cache.
+///
+/// Decompositions can also be baked ahead of time with [`ConvexDecompositionCache::bake`], for example in a tool
+/// that processes the meshes of a game before shipping it, so that they don't need to be computed at runtime at all.
+///
+/// ## Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, PhysicsPlugins::default()))
+///         .insert_resource(ConvexDecompositionCache::new("assets/colliders"))
+///         .run();
+/// }
+/// ```
+#[derive(Resource, Clone, Debug, PartialEq, Eq)]
+pub struct ConvexDecompositionCache {
+    directory: PathBuf,
+}
+
+impl ConvexDecompositionCache {
+    /// Creates a new [`ConvexDecompositionCache`] that stores decompositions in the given directory.
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+
+    /// Returns the directory where the decompositions are stored.
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Computes the key of the decomposition of a `Mesh` with the given [`VHACDParameters`].
+    ///
+    /// Returns `None` if the mesh doesn't have vertex positions or indices.
+    pub fn key(mesh: &Mesh, parameters: &VHACDParameters) -> Option<u64> {
+        let (vertices, indices) = extract_mesh_vertices_indices(mesh)?;
+
+        // Fxhash is used because its output stays the same across runs and platforms
+        let mut hasher = FxHasher64::default();
+        CACHE_VERSION.hash(&mut hasher);
+        (vertices.len() as u64).hash(&mut hasher);
+        for vertex in vertices {
+            for coordinate in vertex.iter() {
+                coordinate.to_bits().hash(&mut hasher);
+            }
+        }
+        (indices.len() as u64).hash(&mut hasher);
+        for index in indices.iter().flatten() {
+            index.hash(&mut hasher);
+        }
+        hash_parameters(parameters, &mut hasher);
+
+        Some(hasher.finish())
+    }
+
+    /// Returns the path of the file that stores the decomposition with the given key.
+    pub fn path(&self, key: u64) -> PathBuf {
+        self.directory.join(format!("{key:016x}.collider.bin"))
+    }
+
+    /// Loads the cached decomposition of a `Mesh` with the given [`VHACDParameters`].
+    ///
+    /// Returns `None` if the decomposition hasn't been cached or the cached file is invalid.
+    pub fn load(&self, mesh: &Mesh, parameters: &VHACDParameters) -> Option<Collider> {
+        let bytes = std::fs::read(self.path(Self::key(mesh, parameters)?)).ok()?;
+        ColliderAsset::from_bytes(&bytes).ok().map(|asset| asset.0)
+    }
+
+    /// Stores the decomposition of a `Mesh` with the given [`VHACDParameters`] in the cache.
+    ///
+    /// The file is written to a temporary file first and then renamed, so that a concurrent [`load`](Self::load)
+    /// or a crash during the write can't leave a partially written decomposition in the cache.
+    pub fn store(
+        &self,
+        mesh: &Mesh,
+        parameters: &VHACDParameters,
+        collider: &Collider,
+    ) -> Result<(), ColliderAssetError> {
+        let Some(key) = Self::key(mesh, parameters) else {
+            return Ok(());
+        };
+        let bytes = ColliderAsset::new(collider.clone()).to_bytes()?;
+        std::fs::create_dir_all(&self.directory)?;
+
+        // The temporary file is unique, so that tasks storing the same decomposition don't write to the same file
+        static TEMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+        let temp_path = self.directory.join(format!(
+            "{key:016x}.{}.{}.tmp",
+            std::process::id(),
+            TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let result = std::fs::write(&temp_path, bytes)
+            .and_then(|_| std::fs::rename(&temp_path, self.path(key)));
+        if result.is_err() {
+            let _ = std::fs::remove_file(&temp_path);
+        }
+        result?;
+        Ok(())
+    }
+
+    /// Loads the decomposition of a `Mesh` with the given [`VHACDParameters`] from the cache,
+    /// or computes it and stores it in the cache if it hasn't been cached yet.
+    ///
+    /// Returns `Ok(None)` if the decomposition can't be computed for the mesh.
+    pub fn bake(
+        &self,
+        mesh: &Mesh,
+        parameters: &VHACDParameters,
+    ) -> Result<Option<Collider>, ColliderAssetError> {
+        if let Some(collider) = self.load(mesh, parameters) {
+            return Ok(Some(collider));
+        }
+        let Some(collider) = Collider::convex_decomposition_from_mesh_with_config(mesh, parameters)
+        else {
+            return Ok(None);
+        };
+        self.store(mesh, parameters, &collider)?;
+        Ok(Some(collider))
+    }
+}
+
+/// Hashes each of the [`VHACDParameters`] explicitly, so that the key doesn't depend on their `Debug` output.
+fn hash_parameters(parameters: &VHACDParameters, hasher: &mut impl Hasher) {
+    // Destructured so that new parameters can't be left out of the key
+    let VHACDParameters {
+        concavity,
+        alpha,
+        beta,
+        resolution,
+        plane_downsampling,
+        convex_hull_downsampling,
+        fill_mode,
+        convex_hull_approximation,
+        max_convex_hulls,
+    } = parameters;
+
+    concavity.to_bits().hash(hasher);
+    alpha.to_bits().hash(hasher);
+    beta.to_bits().hash(hasher);
+    resolution.hash(hasher);
+    plane_downsampling.hash(hasher);
+    convex_hull_downsampling.hash(hasher);
+    match fill_mode {
+        FillMode::SurfaceOnly => 0u8.hash(hasher),
+        #[cfg(feature = "2d")]
+        FillMode::FloodFill {
+            detect_cavities,
+            detect_self_intersections,
+        } => {
+            1u8.hash(hasher);
+            detect_cavities.hash(hasher);
+            detect_self_intersections.hash(hasher);
+        }
+        #[cfg(feature = "3d")]
+        FillMode::FloodFill { detect_cavities } => {
+            1u8.hash(hasher);
+            detect_cavities.hash(hasher);
+        }
+    }
+    convex_hull_approximation.hash(hasher);
+    max_convex_hulls.hash(hasher);
+}
@@ -0,0 +1,690 @@
+//! [`CustomShape`] trait for user-defined collider shapes.
+
+use std::{any::Any, fmt::Debug, sync::Arc};
+
+use crate::prelude::*;
+use parry::{
+    bounding_volume::{Aabb, BoundingSphere},
+    mass_properties::MassProperties,
+    math::{Isometry, Point, Real},
+    na::Unit,
+    query::{
+        details::{
+            contact_manifolds_composite_shape_shape, contact_manifolds_heightfield_shape_shapes,
+            contact_manifolds_trimesh_shape_shapes, ContactManifoldsWorkspace,
+        },
+        ClosestPoints, Contact, ContactManifold, DefaultQueryDispatcher, NonlinearRigidMotion,
+        PersistentQueryDispatcher, PointProjection, PointQuery, QueryDispatcher,
+        QueryDispatcherChain, Ray, RayCast, RayIntersection, TrackedContact, Unsupported, TOI,
+    },
+    shape::{
+        FeatureId, PackedFeatureId, PolygonalFeature, Shape, ShapeType, SupportMap, TypedShape,
+    },
+};
+
+/// The identifier of custom shapes in `TypedShape::Custom`.
+const CUSTOM_SHAPE_ID: u32 = 0x6375_7374;
+
+/// A trait for user-defined collider shapes, like shapes described by signed distance functions
+/// or procedurally generated shapes.
+///
+/// A collider with a custom shape is created with [`Collider::custom`], and it works like the built-in shapes
+/// in the broad phase, the narrow phase, [spatial queries](spatial_query) and the debug renderer.
+///
+/// ## Contacts
+///
+/// If the shape is convex, it should implement [`support_point`](CustomShape::support_point).
+/// Contacts against all other shapes, shape casts and distance queries are then computed
+/// using the support mapping.
+///
+/// Otherwise, it should implement [`contact`](CustomShape::contact) to compute contacts against the shapes
+/// it needs to collide with. Shape casts and distance queries aren't supported for non-convex custom shapes.
+///
+/// To let boxes rest on custom shapes without jittering, the contact manifolds contain a point for each corner
+/// of the faces where the shapes touch. The corners of shapes with flat faces, like cuboids and convex hulls,
+/// are projected onto the custom shape with [`project_point`](CustomShape::project_point), and the corners
+/// of convex custom shapes are found with their support mapping. Otherwise, for example between a ball and
+/// a non-convex custom shape, the manifolds only contain the single deepest contact point.
+///
+/// Custom shapes can't currently be used as parts of compound colliders.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(
+    feature = "2d",
+    doc = "use bevy_xpbd_2d::{math::{Scalar, Vector}, parry::{bounding_volume::Aabb, mass_properties::MassProperties}, prelude::*};"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "use bevy_xpbd_3d::{math::{Scalar, Vector}, parry::{bounding_volume::Aabb, mass_properties::MassProperties}, prelude::*};"
+)]
+///
+/// /// A ball described by its signed distance function
+/// #[derive(Debug)]
+/// struct Orb {
+///     radius: Scalar,
+/// }
+///
+/// impl Orb {
+///     fn distance(&self, point: Vector) -> Scalar {
+///         point.length() - self.radius
+///     }
+/// }
+///
+/// impl CustomShape for Orb {
+///     fn compute_local_aabb(&self) -> ColliderAabb {
+///         let half_extents = Vector::splat(self.radius);
+///         ColliderAabb(Aabb::new((-half_extents).into(), half_extents.into()))
+///     }
+///
+///     fn mass_properties(&self, density: Scalar) -> MassProperties {
+///         MassProperties::from_ball(density, self.radius)
+///     }
+///
+///     fn project_point(&self, point: Vector, solid: bool) -> (Vector, bool) {
+///         let inside = self.distance(point) <= 0.0;
+///         if solid && inside {
+///             return (point, true);
+///         }
+///         (point.normalize_or_zero() * self.radius, inside)
+///     }
+///
+///     fn cast_ray(
+///         &self,
+///         origin: Vector,
+///         direction: Vector,
+///         max_time_of_impact: Scalar,
+///         _solid: bool,
+///     ) -> Option<(Scalar, Vector)> {
+///         // Sphere tracing
+///         let mut time_of_impact = 0.0;
+///         while time_of_impact <= max_time_of_impact {
+///             let point = origin + time_of_impact * direction;
+///             let distance = self.distance(point);
+///             if distance < 0.001 {
+///                 return Some((time_of_impact, point.normalize_or_zero()));
+///             }
+///             time_of_impact += distance / direction.length();
+///         }
+///         None
+///     }
+///
+///     fn support_point(&self, direction: Vector) -> Option<Vector> {
+///         Some(direction.normalize_or_zero() * self.radius)
+///     }
+/// }
+///
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((RigidBody::Static, Collider::custom(Orb { radius: 5.0 })));
+/// }
+/// ```
+pub trait CustomShape: Any + Debug + Send + Sync {
+    /// Computes the [`ColliderAabb`] of the shape in its local space.
+    fn compute_local_aabb(&self) -> ColliderAabb;
+
+    /// Computes the mass properties of the shape with the given density.
+    fn mass_properties(&self, density: Scalar) -> MassProperties;
+
+    /// Projects a `point` in the local space of the shape onto its surface.
+    ///
+    /// Returns the projected point and whether the point is inside the shape.
+    /// If `solid` is true and the point is inside the shape, the point itself should be returned.
+    fn project_point(&self, point: Vector, solid: bool) -> (Vector, bool);
+
+    /// Casts a ray in the local space of the shape.
+    ///
+    /// Returns the time of impact and the local surface normal at the hit point, or `None` if the ray
+    /// doesn't hit the shape before `max_time_of_impact`. If `solid` is true and the ray starts inside the shape,
+    /// the time of impact should be zero.
+    fn cast_ray(
+        &self,
+        origin: Vector,
+        direction: Vector,
+        max_time_of_impact: Scalar,
+        solid: bool,
+    ) -> Option<(Scalar, Vector)>;
+
+    /// Returns the point of the shape that is the furthest in the given local `direction`,
+    /// or `None` if the shape isn't convex.
+    ///
+    /// Convex shapes get contacts against all other shapes through this support mapping.
+    /// The shape is treated as convex if this returns `Some`, so it should do so for all directions.
+    fn support_point(&self, _direction: Vector) -> Option<Vector> {
+        None
+    }
+
+    /// Computes a contact between the shape and another shape in the local space of this shape,
+    /// given by its `position` and `rotation`. This is only used for shapes that aren't convex.
+    ///
+    /// `point1` and `normal1` of the returned [`ContactData`] should be in the local space of this shape,
+    /// and `point2` and `normal2` in the local space of the other shape.
+    /// Returns `None` if the shapes are separated by more than `prediction_distance`.
+    fn contact(
+        &self,
+        _other: &dyn Shape,
+        _position: Vector,
+        _rotation: Rotation,
+        _prediction_distance: Scalar,
+    ) -> Option<ContactData> {
+        None
+    }
+
+    /// Returns a copy of the shape scaled by the given factors, or `None` if the shape can't be scaled.
+    fn scaled(&self, _scale: Vector) -> Option<Box<dyn CustomShape>> {
+        None
+    }
+
+    /// Returns the line segments that the debug renderer draws for the shape in its local space.
+    ///
+    /// By default, the [`ColliderAabb`] of the shape is drawn.
+    fn debug_lines(&self) -> Vec<[Vector; 2]> {
+        let aabb = self.compute_local_aabb().0;
+        let vertices: Vec<Vector> = aabb.vertices().iter().map(|v| (*v).into()).collect();
+        #[cfg(feature = "2d")]
+        let edges = [[0, 1], [1, 3], [3, 2], [2, 0]];
+        #[cfg(feature = "3d")]
+        let edges = [
+            [0, 1],
+            [1, 2],
+            [2, 3],
+            [3, 0],
+            [4, 5],
+            [5, 6],
+            [6, 7],
+            [7, 4],
+            [0, 4],
+            [1, 5],
+            [2, 6],
+            [3, 7],
+        ];
+        edges
+            .iter()
+            .map(|[a, b]| [vertices[*a], vertices[*b]])
+            .collect()
+    }
+}
+
+/// Adapts a [`CustomShape`] into a parry shape so that it can be stored in a [`Collider`].
+#[derive(Clone, Debug)]
+pub(crate) struct CustomShapeWrapper {
+    shape: Arc<dyn CustomShape>,
+    convex: bool,
+}
+
+impl CustomShapeWrapper {
+    pub(crate) fn new(shape: Arc<dyn CustomShape>) -> Self {
+        let convex = shape.support_point(Vector::X).is_some();
+        Self { shape, convex }
+    }
+
+    /// Returns the user-defined shape.
+    pub(crate) fn shape(&self) -> &dyn CustomShape {
+        self.shape.as_ref()
+    }
+
+    /// Returns the user-defined shape if it is of type `T`.
+    pub(crate) fn downcast_ref<T: CustomShape>(&self) -> Option<&T> {
+        (self.shape.as_ref() as &dyn Any).downcast_ref::<T>()
+    }
+
+    pub(crate) fn scaled(&self, scale: Vector) -> Option<Self> {
+        self.shape
+            .scaled(scale)
+            .map(|shape| Self::new(Arc::from(shape)))
+    }
+
+    /// Computes a contact with another shape using [`CustomShape::contact`].
+    /// `pos12` is the position of the other shape in the local space of this shape.
+    fn contact(
+        &self,
+        other: &dyn Shape,
+        pos12: &Isometry<Real>,
+        prediction: Real,
+    ) -> Option<Contact> {
+        #[cfg(feature = "2d")]
+        let rotation = Rotation::from_radians(pos12.rotation.angle());
+        #[cfg(feature = "3d")]
+        let rotation = Rotation(pos12.rotation.into());
+
+        self.shape
+            .contact(other, pos12.translation.vector.into(), rotation, prediction)
+            .filter(|contact| contact.penetration >= -prediction)
+            .map(|contact| {
+                Contact::new(
+                    contact.point1.into(),
+                    contact.point2.into(),
+                    Unit::new_normalize(contact.normal1.into()),
+                    Unit::new_normalize(contact.normal2.into()),
+                    -contact.penetration,
+                )
+            })
+    }
+}
+
+impl Shape for CustomShapeWrapper {
+    fn clone_box(&self) -> Box<dyn Shape> {
+        Box::new(self.clone())
+    }
+
+    fn compute_local_aabb(&self) -> Aabb {
+        self.shape.compute_local_aabb().0
+    }
+
+    fn compute_local_bounding_sphere(&self) -> BoundingSphere {
+        self.compute_local_aabb().bounding_sphere()
+    }
+
+    fn mass_properties(&self, density: Real) -> MassProperties {
+        self.shape.mass_properties(density)
+    }
+
+    fn shape_type(&self) -> ShapeType {
+        ShapeType::Custom
+    }
+
+    fn as_typed_shape(&self) -> TypedShape<'_> {
+        TypedShape::Custom(CUSTOM_SHAPE_ID)
+    }
+
+    fn ccd_thickness(&self) -> Real {
+        self.compute_local_aabb().half_extents().min()
+    }
+
+    fn ccd_angular_thickness(&self) -> Real {
+        PI / 4.0
+    }
+
+    fn as_support_map(&self) -> Option<&dyn SupportMap> {
+        if self.convex {
+            Some(self as &dyn SupportMap)
+        } else {
+            None
+        }
+    }
+}
+
+impl SupportMap for CustomShapeWrapper {
+    fn local_support_point(&self, dir: &parry::math::Vector<Real>) -> Point<Real> {
+        self.shape
+            .support_point((*dir).into())
+            .unwrap_or_default()
+            .into()
+    }
+}
+
+impl RayCast for CustomShapeWrapper {
+    fn cast_local_ray(&self, ray: &Ray, max_toi: Real, solid: bool) -> Option<Real> {
+        self.shape
+            .cast_ray(ray.origin.into(), ray.dir.into(), max_toi, solid)
+            .map(|(toi, _)| toi)
+    }
+
+    fn cast_local_ray_and_get_normal(
+        &self,
+        ray: &Ray,
+        max_toi: Real,
+        solid: bool,
+    ) -> Option<RayIntersection> {
+        self.shape
+            .cast_ray(ray.origin.into(), ray.dir.into(), max_toi, solid)
+            .map(|(toi, normal)| RayIntersection::new(toi, normal.into(), FeatureId::Unknown))
+    }
+}
+
+impl PointQuery for CustomShapeWrapper {
+    fn project_local_point(&self, point: &Point<Real>, solid: bool) -> PointProjection {
+        let (projected, inside) = self.shape.project_point((*point).into(), solid);
+        PointProjection::new(inside, projected.into())
+    }
+
+    fn project_local_point_and_get_feature(
+        &self,
+        point: &Point<Real>,
+    ) -> (PointProjection, FeatureId) {
+        (self.project_local_point(point, false), FeatureId::Unknown)
+    }
+}
+
+/// The query dispatcher used for contacts and spatial queries.
+///
+/// Pairs with a [`CustomShape`] are handled by [`CustomShapeDispatcher`], and other pairs by parry's [`DefaultQueryDispatcher`].
+pub(crate) type ColliderQueryDispatcher =
+    QueryDispatcherChain<CustomShapeDispatcher, DefaultQueryDispatcher>;
+
+/// Returns the query dispatcher used for contacts and spatial queries.
+pub(crate) fn query_dispatcher() -> ColliderQueryDispatcher {
+    CustomShapeDispatcher.chain(DefaultQueryDispatcher)
+}
+
+/// A query dispatcher that computes contacts for pairs that include a [`CustomShape`].
+///
+/// Other pairs and queries are unsupported, so that they fall back to the next dispatcher in the chain.
+pub(crate) struct CustomShapeDispatcher;
+
+impl CustomShapeDispatcher {
+    /// Computes a contact for a pair that includes a custom shape.
+    ///
+    /// Non-convex custom shapes use [`CustomShape::contact`], and convex ones use the support mapping
+    /// through parry's [`DefaultQueryDispatcher`].
+    fn custom_contact(
+        &self,
+        pos12: &Isometry<Real>,
+        g1: &dyn Shape,
+        g2: &dyn Shape,
+        prediction: Real,
+    ) -> Result<Option<Contact>, Unsupported> {
+        let custom1 = g1.as_shape::<CustomShapeWrapper>();
+        let custom2 = g2.as_shape::<CustomShapeWrapper>();
+
+        if let Some(custom1) = custom1.filter(|custom| !custom.convex) {
+            Ok(custom1.contact(g2, pos12, prediction))
+        } else if let Some(custom2) = custom2.filter(|custom| !custom.convex) {
+            Ok(custom2
+                .contact(g1, &pos12.inverse(), prediction)
+                .map(Contact::flipped))
+        } else if custom1.is_some() || custom2.is_some() {
+            DefaultQueryDispatcher.contact(pos12, g1, g2, prediction)
+        } else {
+            Err(Unsupported)
+        }
+    }
+}
+
+fn is_custom_shape(shape: &dyn Shape) -> bool {
+    shape.as_shape::<CustomShapeWrapper>().is_some()
+}
+
+impl QueryDispatcher for CustomShapeDispatcher {
+    fn intersection_test(
+        &self,
+        pos12: &Isometry<Real>,
+        g1: &dyn Shape,
+        g2: &dyn Shape,
+    ) -> Result<bool, Unsupported> {
+        self.custom_contact(pos12, g1, g2, 0.0)
+            .map(|contact| contact.is_some_and(|contact| contact.dist <= 0.0))
+    }
+
+    fn distance(
+        &self,
+        _pos12: &Isometry<Real>,
+        _g1: &dyn Shape,
+        _g2: &dyn Shape,
+    ) -> Result<Real, Unsupported> {
+        Err(Unsupported)
+    }
+
+    fn contact(
+        &self,
+        pos12: &Isometry<Real>,
+        g1: &dyn Shape,
+        g2: &dyn Shape,
+        prediction: Real,
+    ) -> Result<Option<Contact>, Unsupported> {
+        self.custom_contact(pos12, g1, g2, prediction)
+    }
+
+    fn closest_points(
+        &self,
+        _pos12: &Isometry<Real>,
+        _g1: &dyn Shape,
+        _g2: &dyn Shape,
+        _max_dist: Real,
+    ) -> Result<ClosestPoints, Unsupported> {
+        Err(Unsupported)
+    }
+
+    fn time_of_impact(
+        &self,
+        _pos12: &Isometry<Real>,
+        _local_vel12: &parry::math::Vector<Real>,
+        _g1: &dyn Shape,
+        _g2: &dyn Shape,
+        _max_toi: Real,
+        _stop_at_penetration: bool,
+    ) -> Result<Option<TOI>, Unsupported> {
+        Err(Unsupported)
+    }
+
+    fn nonlinear_time_of_impact(
+        &self,
+        _motion1: &NonlinearRigidMotion,
+        _g1: &dyn Shape,
+        _motion2: &NonlinearRigidMotion,
+        _g2: &dyn Shape,
+        _start_time: Real,
+        _end_time: Real,
+        _stop_at_penetration: bool,
+    ) -> Result<Option<TOI>, Unsupported> {
+        Err(Unsupported)
+    }
+}
+
+impl PersistentQueryDispatcher for CustomShapeDispatcher {
+    fn contact_manifolds(
+        &self,
+        pos12: &Isometry<Real>,
+        g1: &dyn Shape,
+        g2: &dyn Shape,
+        prediction: Real,
+        manifolds: &mut Vec<ContactManifold<(), ()>>,
+        workspace: &mut Option<ContactManifoldsWorkspace>,
+    ) -> Result<(), Unsupported> {
+        if !is_custom_shape(g1) && !is_custom_shape(g2) {
+            return Err(Unsupported);
+        }
+
+        // Composite shapes are split into parts here instead of in the default dispatcher,
+        // so that the contacts between the parts and the custom shape are computed by this dispatcher.
+        match (g1.shape_type(), g2.shape_type()) {
+            (ShapeType::TriMesh, _) | (_, ShapeType::TriMesh) => {
+                contact_manifolds_trimesh_shape_shapes(
+                    self, pos12, g1, g2, prediction, manifolds, workspace,
+                );
+            }
+            (ShapeType::HeightField, _) | (_, ShapeType::HeightField) => {
+                contact_manifolds_heightfield_shape_shapes(
+                    self, pos12, g1, g2, prediction, manifolds, workspace,
+                );
+            }
+            _ => {
+                if let Some(composite1) = g1.as_composite_shape() {
+                    contact_manifolds_composite_shape_shape(
+                        self, pos12, composite1, g2, prediction, manifolds, workspace, false,
+                    );
+                } else if let Some(composite2) = g2.as_composite_shape() {
+                    contact_manifolds_composite_shape_shape(
+                        self,
+                        &pos12.inverse(),
+                        composite2,
+                        g1,
+                        prediction,
+                        manifolds,
+                        workspace,
+                        true,
+                    );
+                } else {
+                    if manifolds.is_empty() {
+                        manifolds.push(ContactManifold::new());
+                    }
+                    return self.contact_manifold_convex_convex(
+                        pos12,
+                        g1,
+                        g2,
+                        prediction,
+                        &mut manifolds[0],
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn contact_manifold_convex_convex(
+        &self,
+        pos12: &Isometry<Real>,
+        g1: &dyn Shape,
+        g2: &dyn Shape,
+        prediction: Real,
+        manifold: &mut ContactManifold<(), ()>,
+    ) -> Result<(), Unsupported> {
+        let contact = self.custom_contact(pos12, g1, g2, prediction)?;
+
+        manifold.clear();
+
+        let Some(contact) = contact.filter(|contact| contact.dist <= prediction) else {
+            return Ok(());
+        };
+        manifold.local_n1 = *contact.normal1;
+        manifold.local_n2 = *contact.normal2;
+
+        // Add contacts for the corners of the faces or edges where the shapes touch
+        let feature_contacts = if let Some(custom1) = g1.as_shape::<CustomShapeWrapper>() {
+            feature_contacts(
+                custom1,
+                g2,
+                pos12,
+                *contact.normal1,
+                *contact.normal2,
+                prediction,
+            )
+        } else if let Some(custom2) = g2.as_shape::<CustomShapeWrapper>() {
+            let pos21 = pos12.inverse();
+            feature_contacts(
+                custom2,
+                g1,
+                &pos21,
+                *contact.normal2,
+                *contact.normal1,
+                prediction,
+            )
+        } else {
+            vec![]
+        };
+        let flipped = !is_custom_shape(g1);
+        for (custom_point, other_point, other_feature, dist) in feature_contacts {
+            manifold.points.push(if flipped {
+                TrackedContact::new(
+                    other_point,
+                    custom_point,
+                    other_feature,
+                    PackedFeatureId::UNKNOWN,
+                    dist,
+                )
+            } else {
+                TrackedContact::new(
+                    custom_point,
+                    other_point,
+                    PackedFeatureId::UNKNOWN,
+                    other_feature,
+                    dist,
+                )
+            });
+        }
+
+        // The deepest point is needed if the face isn't flush against the custom shape
+        let deepest = manifold
+            .points
+            .iter()
+            .map(|point| point.dist)
+            .fold(Real::MAX, Real::min);
+        if contact.dist < deepest - FEATURE_CONTACT_TOLERANCE {
+            manifold.points.push(TrackedContact::new(
+                contact.point1,
+                contact.point2,
+                PackedFeatureId::UNKNOWN,
+                PackedFeatureId::UNKNOWN,
+                contact.dist,
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// How much deeper than the contacts at the corners of a face the deepest contact has to be
+/// for it to be added to the manifold. Corners closer to each other than this are merged.
+const FEATURE_CONTACT_TOLERANCE: Real = 1.0e-3;
+
+/// How far the support direction is tilted to find the corners of a convex custom shape.
+const SUPPORT_TILT: Real = 0.1;
+
+/// Computes contacts at the corners of the faces or edges where a custom shape and another shape touch.
+///
+/// The vertices of the other shape's closest face are projected onto the custom shape if the other shape
+/// has a polygonal feature map, like cuboids and convex hulls. The corners of convex custom shapes are found
+/// by tilting the support direction and are projected onto the other shape.
+///
+/// `pos` is the position of the other shape in the local space of the custom shape, and the normals point
+/// from each shape toward the other in their local spaces. The contacts are returned as the points
+/// on the custom shape and the other shape, the feature of the other shape and the distance.
+fn feature_contacts(
+    custom: &CustomShapeWrapper,
+    other: &dyn Shape,
+    pos: &Isometry<Real>,
+    custom_normal: parry::math::Vector<Real>,
+    other_normal: parry::math::Vector<Real>,
+    prediction: Real,
+) -> Vec<(Point<Real>, Point<Real>, PackedFeatureId, Real)> {
+    let mut contacts: Vec<(Point<Real>, Point<Real>, PackedFeatureId, Real)> = vec![];
+    let mut add_contact =
+        |custom_point: Point<Real>, other_point, feature, distance: Real, inside: bool| {
+            let dist = if inside { -distance } else { distance };
+            let duplicate = contacts
+                .iter()
+                .any(|(point, ..)| (point - custom_point).norm() < FEATURE_CONTACT_TOLERANCE);
+            if dist <= prediction && !duplicate {
+                contacts.push((custom_point, other_point, feature, dist));
+            }
+        };
+
+    if let Some((feature_map, border_radius)) = other.as_polygonal_feature_map() {
+        let mut feature = PolygonalFeature::default();
+        feature_map.local_support_feature(&Unit::new_normalize(other_normal), &mut feature);
+
+        for i in 0..feature.num_vertices {
+            let other_point = feature.vertices[i] + other_normal * border_radius;
+            let point: Vector = (pos * other_point).coords.into();
+            let (projected, inside) = custom.shape.project_point(point, false);
+            add_contact(
+                projected.into(),
+                other_point,
+                feature.vids[i],
+                point.distance(projected),
+                inside,
+            );
+        }
+    }
+
+    if custom.convex {
+        let normal: Vector = custom_normal.into();
+        #[cfg(feature = "2d")]
+        let tangents = [normal.perp(), -normal.perp()];
+        #[cfg(feature = "3d")]
+        let tangents = {
+            let (t1, t2) = normal.any_orthonormal_pair();
+            [t1, t2, -t1, -t2, t1 + t2, t1 - t2, t2 - t1, -t1 - t2]
+        };
+
+        for tangent in tangents {
+            let Some(point) = custom.shape.support_point(normal + SUPPORT_TILT * tangent) else {
+                continue;
+            };
+            let point: Point<Real> = point.into();
+            let local_point = pos.inverse_transform_point(&point);
+            let projection = other.project_local_point(&local_point, false);
+            add_contact(
+                point,
+                projection.point,
+                PackedFeatureId::UNKNOWN,
+                (local_point - projection.point).norm(),
+                projection.is_inside,
+            );
+        }
+    }
+
+    contacts
+}
@@ -0,0 +1,401 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// A gravity field that overrides or modifies the global [`Gravity`] for
+/// [dynamic rigid bodies](RigidBody::Dynamic) inside of it.
+///
+/// If the entity has a [`Collider`], the field only affects bodies whose [`Position`] is inside
+/// of the collider. Otherwise, the field is global and affects all bodies. The collider is typically
+/// a [`Sensor`] so that it doesn't block the bodies entering the field.
+///
+/// Gravity fields are evaluated in order of increasing [`priority`](Self::priority), starting from
+/// the global [`Gravity`]. Each field combines its own gravity with the gravity computed so far
+/// according to its [`GravityBlendMode`], so fields with a higher priority override the ones with
+/// a lower priority. The result is scaled by the body's [`GravityScale`].
+///
+/// The fields are evaluated for each body in the [`IntegratorPlugin`] every substep.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// # #[cfg(all(feature = "3d", feature = "f32"))]
+/// fn setup(mut commands: Commands) {
+///     // A planet with a radius of 10 that pulls bodies within 50 units towards its center
+///     commands
+///         .spawn((RigidBody::Static, Collider::ball(10.0)))
+///         .with_children(|children| {
+///             children.spawn((
+///                 Collider::ball(50.0),
+///                 Sensor,
+///                 GravityField::point(9.81, 10.0),
+///             ));
+///         });
+///
+///     // An anti-gravity room
+///     commands.spawn((
+///         RigidBody::Static,
+///         Collider::cuboid(5.0, 5.0, 5.0),
+///         Sensor,
+///         GravityField::directional(Vec3::ZERO).with_priority(1),
+///     ));
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct GravityField {
+    /// The type of gravity that the field applies.
+    pub kind: GravityFieldKind,
+    /// The priority of the field. Fields with a higher priority are evaluated later,
+    /// so they override fields with a lower priority. Defaults to `0`.
+    pub priority: i32,
+    /// How the field's gravity is combined with the gravity from the global [`Gravity`]
+    /// and fields with a lower priority.
+    pub blend_mode: GravityBlendMode,
+}
+
+impl Default for GravityField {
+    fn default() -> Self {
+        Self::directional(Gravity::default().0)
+    }
+}
+
+impl GravityField {
+    /// Creates a gravity field with uniform gravity in the given direction.
+    /// The `gravity` is in the local space of the field entity.
+    pub fn directional(gravity: Vector) -> Self {
+        Self {
+            kind: GravityFieldKind::Directional(gravity),
+            priority: 0,
+            blend_mode: GravityBlendMode::default(),
+        }
+    }
+
+    /// Creates a gravity field that pulls bodies towards the field entity's [`Position`]
+    /// like a planet with the given `surface_gravity` and `radius`.
+    pub fn point(surface_gravity: Scalar, radius: Scalar) -> Self {
+        Self {
+            kind: GravityFieldKind::Point {
+                surface_gravity,
+                radius,
+            },
+            priority: 0,
+            blend_mode: GravityBlendMode::default(),
+        }
+    }
+
+    /// Sets the priority of the field.
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Sets the [`GravityBlendMode`] of the field.
+    pub fn with_blend_mode(mut self, blend_mode: GravityBlendMode) -> Self {
+        self.blend_mode = blend_mode;
+        self
+    }
+
+    /// Computes the gravitational acceleration of the field at the given world-space `point`
+    /// for a field at `field_position` with the given `field_rotation`.
+    pub fn gravity_at(
+        &self,
+        field_position: Vector,
+        field_rotation: &Rotation,
+        point: Vector,
+    ) -> Vector {
+        match self.kind {
+            GravityFieldKind::Directional(gravity) => field_rotation.rotate(gravity),
+            GravityFieldKind::Point {
+                surface_gravity,
+                radius,
+            } => {
+                let offset = field_position - point;
+                let distance = offset.length();
+                if distance <= Scalar::EPSILON {
+                    return Vector::ZERO;
+                }
+                // Inverse-square law outside of the radius, linear inside of it
+                let magnitude = if distance >= radius {
+                    surface_gravity * (radius / distance).powi(2)
+                } else {
+                    surface_gravity * distance / radius.max(Scalar::EPSILON)
+                };
+                offset / distance * magnitude
+            }
+        }
+    }
+
+    /// Combines the gravity of this field at the given world-space `point` with the given `gravity`
+    /// according to the field's [`GravityBlendMode`].
+    pub fn blend(
+        &self,
+        gravity: Vector,
+        field_position: Vector,
+        field_rotation: &Rotation,
+        point: Vector,
+    ) -> Vector {
+        let field_gravity = self.gravity_at(field_position, field_rotation, point);
+        match self.blend_mode {
+            GravityBlendMode::Replace => field_gravity,
+            GravityBlendMode::Add => gravity + field_gravity,
+            GravityBlendMode::Lerp(weight) => gravity.lerp(field_gravity, weight.clamp(0.0, 1.0)),
+        }
+    }
+}
+
+/// The type of gravity applied by a [`GravityField`].
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum GravityFieldKind {
+    /// Uniform gravity in the local space of the field entity.
+    Directional(Vector),
+    /// Gravity towards the field entity's [`Position`].
+    ///
+    /// Outside of the `radius`, the gravity follows the inverse-square law. Inside of it,
+    /// the gravity decreases linearly towards the center, like inside of a uniform sphere.
+    Point {
+        /// The magnitude of the gravity at the given `radius`.
+        surface_gravity: Scalar,
+        /// The radius at which the gravity is equal to `surface_gravity`.
+        radius: Scalar,
+    },
+}
+
+/// Determines how a [`GravityField`] is combined with the global [`Gravity`]
+/// and the gravity of fields with a lower priority.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum GravityBlendMode {
+    /// The field's gravity replaces the existing gravity.
+    #[default]
+    Replace,
+    /// The field's gravity is added to the existing gravity.
+    Add,
+    /// The existing gravity is linearly interpolated towards the field's gravity
+    /// using the given weight between `0.0` and `1.0`.
+    Lerp(Scalar),
+}
+
+/// An area effector that applies forces to [dynamic rigid bodies](RigidBody::Dynamic) inside of it.
+///
+/// Like with [`GravityField`], if the entity has a [`Collider`], the effector only affects bodies
+/// whose [`Position`] is inside of the collider. Otherwise, it affects all bodies.
+///
+/// The forces of all effectors affecting a body are added together, and they are evaluated
+/// for each body in the [`IntegratorPlugin`] every substep.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// # #[cfg(all(feature = "3d", feature = "f32"))]
+/// fn setup(mut commands: Commands) {
+///     // A wind tunnel that pushes bodies along the X axis
+///     commands.spawn((
+///         RigidBody::Static,
+///         Collider::cuboid(20.0, 4.0, 4.0),
+///         Sensor,
+///         ForceEffector::wind(Vec3::X * 15.0, 0.5),
+///     ));
+///
+///     // A global explosion-like push away from the origin that fades out over 10 units
+///     commands.spawn((
+///         Position::default(),
+///         ForceEffector::radial(50.0).with_falloff(EffectorFalloff::Linear { radius: 10.0 }),
+///     ));
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct ForceEffector {
+    /// The type of force that the effector applies.
+    pub kind: ForceEffectorKind,
+    /// How the force changes based on the distance from the effector's [`Position`].
+    pub falloff: EffectorFalloff,
+    /// If true, the force is applied as an acceleration, affecting all bodies equally
+    /// regardless of their mass. Defaults to `false`.
+    pub ignore_mass: bool,
+}
+
+impl Default for ForceEffector {
+    fn default() -> Self {
+        Self::radial(0.0)
+    }
+}
+
+impl ForceEffector {
+    /// Creates an effector that pushes bodies away from the effector's [`Position`]
+    /// with the given `strength`. A negative strength pulls bodies towards the center.
+    pub fn radial(strength: Scalar) -> Self {
+        Self::new(ForceEffectorKind::Radial { strength })
+    }
+
+    /// Creates an effector that makes bodies swirl around the effector's [`Position`]
+    /// with the given `strength`. Positive values result in counterclockwise motion.
+    #[cfg(feature = "2d")]
+    pub fn vortex(strength: Scalar) -> Self {
+        Self::new(ForceEffectorKind::Vortex { strength })
+    }
+
+    /// Creates an effector that makes bodies swirl around the given local `axis` going through
+    /// the effector's [`Position`] with the given `strength`. Positive values result in
+    /// counterclockwise motion around the axis.
+    #[cfg(feature = "3d")]
+    pub fn vortex(axis: Vector, strength: Scalar) -> Self {
+        Self::new(ForceEffectorKind::Vortex { axis, strength })
+    }
+
+    /// Creates an effector that drags bodies towards the given local wind `velocity`.
+    /// The force is proportional to the `drag` and the difference between the wind velocity
+    /// and the body's velocity.
+    pub fn wind(velocity: Vector, drag: Scalar) -> Self {
+        Self::new(ForceEffectorKind::Wind { velocity, drag })
+    }
+
+    fn new(kind: ForceEffectorKind) -> Self {
+        Self {
+            kind,
+            falloff: EffectorFalloff::default(),
+            ignore_mass: false,
+        }
+    }
+
+    /// Sets the [`EffectorFalloff`] of the effector.
+    pub fn with_falloff(mut self, falloff: EffectorFalloff) -> Self {
+        self.falloff = falloff;
+        self
+    }
+
+    /// Determines if the force should be applied as an acceleration that ignores the mass of the bodies.
+    pub fn with_ignore_mass(mut self, ignore_mass: bool) -> Self {
+        self.ignore_mass = ignore_mass;
+        self
+    }
+
+    /// Computes the force applied by the effector at `effector_position` with the given
+    /// `effector_rotation` to a body at the world-space `point` moving with the given `velocity`.
+    ///
+    /// If [`ignore_mass`](Self::ignore_mass) is true, the returned value is an acceleration.
+    pub fn force_at(
+        &self,
+        effector_position: Vector,
+        effector_rotation: &Rotation,
+        point: Vector,
+        velocity: Vector,
+    ) -> Vector {
+        let offset = point - effector_position;
+        match self.kind {
+            ForceEffectorKind::Radial { strength } => {
+                offset.normalize_or_zero() * strength * self.falloff.factor(offset.length())
+            }
+            #[cfg(feature = "2d")]
+            ForceEffectorKind::Vortex { strength } => {
+                offset.perp().normalize_or_zero() * strength * self.falloff.factor(offset.length())
+            }
+            #[cfg(feature = "3d")]
+            ForceEffectorKind::Vortex { axis, strength } => {
+                let axis = effector_rotation.rotate(axis).normalize_or_zero();
+                // Offset perpendicular to the axis
+                let radial = offset - axis * offset.dot(axis);
+                axis.cross(radial).normalize_or_zero()
+                    * strength
+                    * self.falloff.factor(radial.length())
+            }
+            ForceEffectorKind::Wind {
+                velocity: wind_velocity,
+                drag,
+            } => {
+                (effector_rotation.rotate(wind_velocity) - velocity)
+                    * drag
+                    * self.falloff.factor(offset.length())
+            }
+        }
+    }
+}
+
+/// The type of force applied by a [`ForceEffector`].
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum ForceEffectorKind {
+    /// Pushes bodies away from the effector's [`Position`].
+    /// A negative strength pulls bodies towards it instead.
+    Radial {
+        /// The magnitude of the force.
+        strength: Scalar,
+    },
+    /// Applies a force tangential to the circle around the effector's [`Position`].
+    #[cfg(feature = "2d")]
+    Vortex {
+        /// The magnitude of the force. Positive values result in counterclockwise motion.
+        strength: Scalar,
+    },
+    /// Applies a force tangential to the circle around an axis going through the effector's [`Position`].
+    #[cfg(feature = "3d")]
+    Vortex {
+        /// The axis of the vortex in the local space of the effector.
+        axis: Vector,
+        /// The magnitude of the force. Positive values result in counterclockwise motion
+        /// around the axis.
+        strength: Scalar,
+    },
+    /// Drags bodies towards a wind velocity.
+    Wind {
+        /// The velocity of the wind in the local space of the effector.
+        velocity: Vector,
+        /// The drag coefficient that determines how strongly bodies are dragged along by the wind.
+        drag: Scalar,
+    },
+}
+
+/// Determines how the force of a [`ForceEffector`] changes based on the distance
+/// from the effector's [`Position`].
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum EffectorFalloff {
+    /// The force is the same regardless of the distance.
+    #[default]
+    Constant,
+    /// The force decreases linearly and reaches zero at the given `radius`.
+    Linear {
+        /// The distance at which the force reaches zero.
+        radius: Scalar,
+    },
+    /// The force decreases according to the inverse-square law.
+    InverseSquare {
+        /// The minimum distance used for computing the falloff,
+        /// which prevents the force from growing infinitely close to the center.
+        min_distance: Scalar,
+    },
+}
+
+impl EffectorFalloff {
+    /// Returns the factor that the force is multiplied by at the given `distance`.
+    pub fn factor(&self, distance: Scalar) -> Scalar {
+        match *self {
+            Self::Constant => 1.0,
+            Self::Linear { radius } => {
+                if radius <= 0.0 {
+                    0.0
+                } else {
+                    (1.0 - distance / radius).max(0.0)
+                }
+            }
+            Self::InverseSquare { min_distance } => {
+                1.0 / distance.max(min_distance).max(Scalar::EPSILON).powi(2)
+            }
+        }
+    }
+}
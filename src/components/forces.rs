@@ -19,8 +19,8 @@ impl FloatZero for Scalar {
 
 /// An external force applied continuously to a dynamic [rigid body](RigidBody).
 ///
-/// The force is stored in world space. If you want to apply forces in local space, you need to
-/// use the body's rotation to [transform the local force into world space](#local-forces).
+/// By default, the force is in world space. It can also be specified
+/// in [the local space of the body](#local-forces).
 ///
 /// By default, the force persists across frames. You can clear the force manually using
 /// [`clear`](Self::clear) or set `persistent` to false.
@@ -60,10 +60,9 @@ impl FloatZero for Scalar {
 ///
 /// ## Local forces
 ///
-/// The force stored in `ExternalForce` is in world space.
-///
-/// If you want to apply a force in some direction relative to the body's frame of reference,
-/// you need to rotate the force using the body's `Transform` or [`Rotation`].
+/// If `local` is true, the force and the torque caused by it are in the local space of the body,
+/// and they are rotated into world space using the body's current [`Rotation`] every substep.
+/// This is useful for things like thrusters that should stay aligned with the body as it rotates.
 ///
 /// ```
 /// use bevy::prelude::*;
@@ -75,18 +74,19 @@ impl FloatZero for Scalar {
 /// # #[cfg(all(feature = "3d", feature = "f32"))]
 /// fn setup(mut commands: Commands) {
 ///     // Spawn a rotated body and apply a force in the local up direction.
-///     let transform = Transform::from_rotation(Quat::from_rotation_z(0.2));
 ///     commands.spawn((
 ///         RigidBody::Dynamic,
-///         ExternalForce::new(transform.rotation * Vec3::Y),
-///         transform,
+///         ExternalForce::new(Vec3::Y).with_local_frame(true),
+///         Transform::from_rotation(Quat::from_rotation_z(0.2)),
 ///     ));
+///
+///     // Apply a local force at an offset from the center of mass, which also causes torque
+///     // around the body's local Z axis.
+///     let mut force = ExternalForce::default().with_local_frame(true);
+///     force.apply_force_at_point(Vec3::Y, Vec3::X, Vec3::ZERO);
+///     commands.spawn((RigidBody::Dynamic, force));
 /// }
 /// ```
-///
-/// Note that the actual force stored in `ExternalForce` is still in world space.
-/// If you want to apply a force in the same local direction every frame,
-/// consider setting `persistent` to `false` and running [`apply_force`](Self::apply_force) in a system.
 #[derive(Reflect, Clone, Copy, Component, Debug, PartialEq, From)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 #[reflect(Component)]
@@ -98,6 +98,9 @@ pub struct ExternalForce {
     /// If you clear the force manually, use the [`clear`](Self::clear) method. This will clear the force and
     /// the torque that is applied when the force is not applied at the center of mass.
     pub persistent: bool,
+    /// True if the force and the torque caused by it are in the [local space of the body](#local-forces),
+    /// and false if they are in world space. Defaults to false.
+    pub local: bool,
     /// The torque caused by forces applied at certain points using [`apply_force_at_point`](Self::apply_force_at_point).
     torque: Torque,
 }
@@ -121,6 +124,7 @@ impl Default for ExternalForce {
         Self {
             force: Vector::ZERO,
             persistent: true,
+            local: false,
             torque: Torque::ZERO,
         }
     }
@@ -131,27 +135,30 @@ impl ExternalForce {
     pub const ZERO: Self = Self {
         force: Vector::ZERO,
         persistent: true,
+        local: false,
         torque: Torque::ZERO,
     };
 
     /// Creates a new [`ExternalForce`] component with a given world-space `force`.
+    ///
+    /// The force can be specified in local space using [`with_local_frame`](Self::with_local_frame).
     pub fn new(force: Vector) -> Self {
         Self { force, ..default() }
     }
 
-    /// Sets the world-space force. Note that the torque caused by any forces will not be reset.
+    /// Sets the force. Note that the torque caused by any forces will not be reset.
     pub fn set_force(&mut self, force: Vector) -> &mut Self {
         **self = force;
         self
     }
 
-    /// Adds the given world-space `force` to the force that will be applied.
+    /// Adds the given `force` to the force that will be applied.
     pub fn apply_force(&mut self, force: Vector) -> &mut Self {
         **self += force;
         self
     }
 
-    /// Applies the given world-space `force` at a local `point`, which will also cause torque to be applied.
+    /// Applies the given `force` at a local `point`, which will also cause torque to be applied.
     pub fn apply_force_at_point(
         &mut self,
         force: Vector,
@@ -170,7 +177,7 @@ impl ExternalForce {
         self
     }
 
-    /// Returns the force in world space.
+    /// Returns the force. It is in local space if [`local`](Self::local) is true, and in world space otherwise.
     pub fn force(&self) -> Vector {
         self.force
     }
@@ -188,6 +195,13 @@ impl ExternalForce {
         self
     }
 
+    /// Determines if the force and the torque caused by it are in the [local space of the body](#local-forces)
+    /// or in world space.
+    pub fn with_local_frame(mut self, is_local: bool) -> Self {
+        self.local = is_local;
+        self
+    }
+
     /// Sets the force and the potential torque caused by the force to zero.
     pub fn clear(&mut self) {
         self.force = Vector::ZERO;
@@ -306,8 +320,8 @@ impl ExternalTorque {
 
 /// An external impulse applied instantly to a dynamic [rigid body](RigidBody).
 ///
-/// The impulse is stored in world space. If you want to apply impulses in local space, you need to
-/// use the body's rotation to [transform the local impulse into world space](#local-impulses).
+/// By default, the impulse is in world space. It can also be specified
+/// in [the local space of the body](#local-impulses).
 ///
 /// By default, the impulse is cleared every frame. You can set `persistent` to true in order to persist
 /// the impulse across frames.
@@ -347,10 +361,8 @@ impl ExternalTorque {
 ///
 /// ## Local impulses
 ///
-/// The impulse stored in `ExternalImpulse` is in world space.
-///
-/// If you want to apply an impulse in some direction relative to the body's frame of reference,
-/// you need to rotate the impulse using the body's `Transform` or [`Rotation`].
+/// If `local` is true, the impulse and the angular impulse caused by it are in the local space
+/// of the body, and they are rotated into world space using the body's [`Rotation`] when the impulse is applied.
 ///
 /// ```
 /// use bevy::prelude::*;
@@ -362,11 +374,10 @@ impl ExternalTorque {
 /// # #[cfg(all(feature = "3d", feature = "f32"))]
 /// fn setup(mut commands: Commands) {
 ///     // Spawn a rotated body and apply an impulse in the local up direction.
-///     let transform = Transform::from_rotation(Quat::from_rotation_z(0.2));
 ///     commands.spawn((
 ///         RigidBody::Dynamic,
-///         ExternalImpulse::new(transform.rotation * Vec3::Y),
-///         transform,
+///         ExternalImpulse::new(Vec3::Y).with_local_frame(true),
+///         Transform::from_rotation(Quat::from_rotation_z(0.2)),
 ///     ));
 /// }
 /// ```
@@ -381,6 +392,9 @@ pub struct ExternalImpulse {
     /// If you clear the impulse manually, use the [`clear`](Self::clear) method. This will clear the impulse and
     /// the angular impulse that is applied when the impulse is not applied at the center of mass.
     pub persistent: bool,
+    /// True if the impulse and the angular impulse caused by it are in the [local space of the body](#local-impulses),
+    /// and false if they are in world space. Defaults to false.
+    pub local: bool,
     /// The angular impulse caused by impulses applied at certain points using [`apply_impulse_at_point`](Self::apply_impulse_at_point).
     angular_impulse: Torque,
 }
@@ -404,6 +418,7 @@ impl Default for ExternalImpulse {
         Self {
             impulse: Vector::ZERO,
             persistent: false,
+            local: false,
             angular_impulse: Torque::ZERO,
         }
     }
@@ -414,10 +429,13 @@ impl ExternalImpulse {
     pub const ZERO: Self = Self {
         impulse: Vector::ZERO,
         persistent: false,
+        local: false,
         angular_impulse: Torque::ZERO,
     };
 
     /// Creates a new [`ExternalImpulse`] component with a given world-space `impulse`.
+    ///
+    /// The impulse can be specified in local space using [`with_local_frame`](Self::with_local_frame).
     pub fn new(impulse: Vector) -> Self {
         Self {
             impulse,
@@ -425,19 +443,19 @@ impl ExternalImpulse {
         }
     }
 
-    /// Sets the world-space impulse. Note that the angular impulse caused by any impulses will not be reset.
+    /// Sets the impulse. Note that the angular impulse caused by any impulses will not be reset.
     pub fn set_impulse(&mut self, impulse: Vector) -> &mut Self {
         **self = impulse;
         self
     }
 
-    /// Adds the given world-space `impulse` to the impulse that will be applied.
+    /// Adds the given `impulse` to the impulse that will be applied.
     pub fn apply_impulse(&mut self, impulse: Vector) -> &mut Self {
         **self += impulse;
         self
     }
 
-    /// Applies the given world-space `impulse` at a local `point`, which will also cause an angular impulse to be applied.
+    /// Applies the given `impulse` at a local `point`, which will also cause an angular impulse to be applied.
     pub fn apply_impulse_at_point(
         &mut self,
         impulse: Vector,
@@ -456,7 +474,7 @@ impl ExternalImpulse {
         self
     }
 
-    /// Returns the impulse in world space.
+    /// Returns the impulse. It is in local space if [`local`](Self::local) is true, and in world space otherwise.
     pub fn impulse(&self) -> Vector {
         self.impulse
     }
@@ -474,6 +492,13 @@ impl ExternalImpulse {
         self
     }
 
+    /// Determines if the impulse and the angular impulse caused by it are in the
+    /// [local space of the body](#local-impulses) or in world space.
+    pub fn with_local_frame(mut self, is_local: bool) -> Self {
+        self.local = is_local;
+        self
+    }
+
     /// Sets the impulse and the potential angular impulse caused by the impulse to zero.
     pub fn clear(&mut self) {
         self.impulse = Vector::ZERO;
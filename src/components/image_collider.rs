@@ -0,0 +1,409 @@
+//! Colliders traced from the alpha channel of images.
+
+use crate::prelude::*;
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+
+impl Collider {
+    /// Creates a collider with a polyline shape along the outline of the opaque region of an `Image`.
+    ///
+    /// The outline is traced with marching squares through the pixels whose alpha is above the `threshold`,
+    /// which is in the range `[0, 1]`. The outline is then simplified so that it doesn't deviate from
+    /// the traced outline by more than `simplify_tolerance` pixels. A tolerance of around one pixel removes
+    /// most of the stair-stepping of pixel art.
+    ///
+    /// The collider is centered on the image and one pixel corresponds to one unit, like for a `Sprite`
+    /// without a custom size. The collider can be scaled with the entity's `Transform`.
+    ///
+    /// Returns `None` if the image format isn't supported or if the image has no opaque region.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_xpbd_2d::prelude::*;
+    ///
+    /// fn spawn_sprite(mut commands: Commands, images: Res<Assets<Image>>, sprites: Res<Sprites>) {
+    ///     if let Some(image) = images.get(&sprites.rock) {
+    ///         commands.spawn((
+    ///             RigidBody::Static,
+    ///             Collider::from_image_alpha(image, 0.5, 1.0).unwrap(),
+    ///             SpriteBundle {
+    ///                 texture: sprites.rock.clone(),
+    ///                 ..default()
+    ///             },
+    ///         ));
+    ///     }
+    /// }
+    /// # #[derive(Resource)]
+    /// # struct Sprites { rock: Handle<Image> }
+    /// ```
+    pub fn from_image_alpha(
+        image: &Image,
+        threshold: Scalar,
+        simplify_tolerance: Scalar,
+    ) -> Option<Self> {
+        let (vertices, indices) = image_alpha_polylines(image, threshold, simplify_tolerance)?;
+        Some(Collider::polyline(vertices, Some(indices)))
+    }
+
+    /// Creates a collider with a convex polygon shape obtained from the convex hull of the opaque region
+    /// of an `Image`. The outline is traced like in [`Collider::from_image_alpha`].
+    ///
+    /// Returns `None` if the image format isn't supported or if the image has no opaque region.
+    pub fn convex_hull_from_image_alpha(image: &Image, threshold: Scalar) -> Option<Self> {
+        let (vertices, _) = image_alpha_polylines(image, threshold, 0.0)?;
+        Collider::convex_hull(vertices)
+    }
+
+    /// Creates a compound shape obtained from the decomposition of the opaque region of an `Image`
+    /// into convex parts. The outline is traced like in [`Collider::from_image_alpha`].
+    ///
+    /// Returns `None` if the image format isn't supported or if the image has no opaque region.
+    pub fn convex_decomposition_from_image_alpha(
+        image: &Image,
+        threshold: Scalar,
+        simplify_tolerance: Scalar,
+    ) -> Option<Self> {
+        let (vertices, indices) = image_alpha_polylines(image, threshold, simplify_tolerance)?;
+        Some(Collider::convex_decomposition(vertices, indices))
+    }
+
+    /// Creates a compound shape obtained from the decomposition of the opaque region of an `Image`
+    /// into convex parts with the given [`VHACDParameters`] passed to the decomposition algorithm.
+    /// The outline is traced like in [`Collider::from_image_alpha`].
+    ///
+    /// Returns `None` if the image format isn't supported or if the image has no opaque region.
+    pub fn convex_decomposition_from_image_alpha_with_config(
+        image: &Image,
+        threshold: Scalar,
+        simplify_tolerance: Scalar,
+        parameters: &VHACDParameters,
+    ) -> Option<Self> {
+        let (vertices, indices) = image_alpha_polylines(image, threshold, simplify_tolerance)?;
+        Some(Collider::convex_decomposition_with_config(
+            vertices, indices, parameters,
+        ))
+    }
+}
+
+/// Determines how a [`Collider`] is generated from the alpha channel of an `Image`.
+///
+/// Colliders can be created from images with the following components and methods:
+///
+/// - [`AsyncImageCollider`] (requires `async-collider` feature)
+/// - [`Collider::from_image_alpha`]
+/// - [`Collider::convex_hull_from_image_alpha`]
+/// - [`Collider::convex_decomposition_from_image_alpha`]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum ComputedImageCollider {
+    /// A polyline along the outline of the opaque region.
+    #[default]
+    Polyline,
+    /// The convex hull of the opaque region.
+    ConvexHull,
+    /// A compound shape obtained from a decomposition of the opaque region into convex parts
+    /// using the specified [`VHACDParameters`].
+    ConvexDecomposition(VHACDParameters),
+}
+
+#[cfg(feature = "async-collider")]
+impl ComputedImageCollider {
+    /// Creates a [`Collider`] of this type from the given `Image`.
+    pub(crate) fn compute(
+        &self,
+        image: &Image,
+        threshold: Scalar,
+        simplify_tolerance: Scalar,
+    ) -> Option<Collider> {
+        match self {
+            Self::Polyline => Collider::from_image_alpha(image, threshold, simplify_tolerance),
+            Self::ConvexHull => Collider::convex_hull_from_image_alpha(image, threshold),
+            Self::ConvexDecomposition(params) => {
+                Collider::convex_decomposition_from_image_alpha_with_config(
+                    image,
+                    threshold,
+                    simplify_tolerance,
+                    params,
+                )
+            }
+        }
+    }
+}
+
+/// A component that will automatically generate a [`Collider`] from the alpha channel of the entity's
+/// `Handle<Image>` once the image has been loaded. The type of the generated collider can be specified
+/// using [`ComputedImageCollider`].
+///
+/// This lets sprites be changed without touching the code that creates their colliders.
+///
+/// Like with [`AsyncCollider`], the collider is computed in the background, and the entity has
+/// the [`ColliderPending`] component until the collider has been inserted.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_xpbd_2d::prelude::*;
+///
+/// fn setup(mut commands: Commands, assets: Res<AssetServer>) {
+///     commands.spawn((
+///         RigidBody::Dynamic,
+///         AsyncImageCollider::new(ComputedImageCollider::ConvexDecomposition(default())),
+///         SpriteBundle {
+///             texture: assets.load("crate.png"),
+///             ..default()
+///         },
+///     ));
+/// }
+/// ```
+#[cfg(feature = "async-collider")]
+#[derive(Component, Clone, Debug, PartialEq)]
+pub struct AsyncImageCollider {
+    /// The type of the generated collider.
+    pub shape: ComputedImageCollider,
+    /// The alpha above which pixels are considered opaque, in the range `[0, 1]`.
+    pub alpha_threshold: Scalar,
+    /// How far in pixels the simplified outline can deviate from the traced outline.
+    pub simplify_tolerance: Scalar,
+}
+
+#[cfg(feature = "async-collider")]
+impl Default for AsyncImageCollider {
+    fn default() -> Self {
+        Self {
+            shape: ComputedImageCollider::default(),
+            alpha_threshold: 0.5,
+            simplify_tolerance: 1.0,
+        }
+    }
+}
+
+#[cfg(feature = "async-collider")]
+impl AsyncImageCollider {
+    /// Creates a new [`AsyncImageCollider`] with the given collider type,
+    /// an alpha threshold of `0.5` and a simplification tolerance of one pixel.
+    pub fn new(shape: ComputedImageCollider) -> Self {
+        Self { shape, ..default() }
+    }
+
+    /// Sets the alpha above which pixels are considered opaque, in the range `[0, 1]`.
+    pub fn with_alpha_threshold(mut self, alpha_threshold: Scalar) -> Self {
+        self.alpha_threshold = alpha_threshold;
+        self
+    }
+
+    /// Sets how far in pixels the simplified outline can deviate from the traced outline.
+    pub fn with_simplify_tolerance(mut self, simplify_tolerance: Scalar) -> Self {
+        self.simplify_tolerance = simplify_tolerance;
+        self
+    }
+}
+
+/// An edge of the sample grid, identified by its first sample and whether it is horizontal.
+type GridEdge = (i32, i32, bool);
+
+/// Traces the outlines of the opaque region of an image and returns them as closed polylines
+/// in a shared vertex buffer with segment indices.
+fn image_alpha_polylines(
+    image: &Image,
+    threshold: Scalar,
+    simplify_tolerance: Scalar,
+) -> Option<(Vec<Vector>, Vec<[u32; 2]>)> {
+    let (width, height) = (image.width(), image.height());
+    let alpha = image.clone().try_into_dynamic().ok()?.to_rgba8();
+
+    // The alpha of the pixel at the given coordinates, zero outside of the image
+    let sample = |x: i32, y: i32| -> Scalar {
+        if x < 0 || y < 0 || x >= width as i32 || y >= height as i32 {
+            0.0
+        } else {
+            alpha.get_pixel(x as u32, y as u32).0[3] as Scalar / 255.0
+        }
+    };
+
+    let outlines = marching_squares(width as i32, height as i32, threshold, sample);
+
+    // Center the outlines on the image and flip them so that `Y` points up
+    let offset = Vector::new(width as Scalar, height as Scalar) / 2.0;
+    let mut vertices = vec![];
+    let mut indices = vec![];
+    for outline in outlines {
+        let outline = simplify_closed(&outline, simplify_tolerance);
+        if outline.len() < 3 {
+            continue;
+        }
+        let start = vertices.len() as u32;
+        let count = outline.len() as u32;
+        vertices.extend(
+            outline
+                .into_iter()
+                .map(|p| Vector::new(p.x - offset.x, offset.y - p.y)),
+        );
+        indices.extend((0..count).map(|i| [start + i, start + (i + 1) % count]));
+    }
+
+    (!indices.is_empty()).then_some((vertices, indices))
+}
+
+/// Traces the closed contours where the samples cross the `threshold` using marching squares.
+///
+/// Samples are located at the pixel centers, and the grid is padded with empty samples so that
+/// all contours are closed. The returned points are in pixel coordinates.
+fn marching_squares(
+    width: i32,
+    height: i32,
+    threshold: Scalar,
+    sample: impl Fn(i32, i32) -> Scalar,
+) -> Vec<Vec<Vector>> {
+    // The point where the contour crosses an edge of the sample grid
+    let crossing = |(x, y, horizontal): GridEdge| -> Vector {
+        let (x2, y2) = if horizontal { (x + 1, y) } else { (x, y + 1) };
+        let (a1, a2) = (sample(x, y), sample(x2, y2));
+        let t = if a1 == a2 {
+            0.5
+        } else {
+            ((threshold - a1) / (a2 - a1)).clamp(0.0, 1.0)
+        };
+        let p1 = Vector::new(x as Scalar, y as Scalar);
+        let p2 = Vector::new(x2 as Scalar, y2 as Scalar);
+        p1.lerp(p2, t) + Vector::splat(0.5)
+    };
+
+    // Connect the crossings within each cell of four samples
+    let mut neighbors: HashMap<GridEdge, Vec<GridEdge>> = HashMap::default();
+    for y in -1..height {
+        for x in -1..width {
+            let solid = |x, y| sample(x, y) > threshold;
+            let (top_left, top_right) = (solid(x, y), solid(x + 1, y));
+            let (bottom_left, bottom_right) = (solid(x, y + 1), solid(x + 1, y + 1));
+
+            let top = (x, y, true);
+            let bottom = (x, y + 1, true);
+            let left = (x, y, false);
+            let right = (x + 1, y, false);
+
+            let segments: &[(GridEdge, GridEdge)] = match (
+                top_left != top_right,
+                top_right != bottom_right,
+                bottom_left != bottom_right,
+                top_left != bottom_left,
+            ) {
+                (false, false, false, false) => &[],
+                // Saddles keep the diagonal solid corners separate
+                (true, true, true, true) if top_left => &[(left, top), (right, bottom)],
+                (true, true, true, true) => &[(top, right), (bottom, left)],
+                (true, true, false, false) => &[(top, right)],
+                (true, false, true, false) => &[(top, bottom)],
+                (true, false, false, true) => &[(top, left)],
+                (false, true, true, false) => &[(right, bottom)],
+                (false, true, false, true) => &[(right, left)],
+                (false, false, true, true) => &[(bottom, left)],
+                _ => unreachable!("a cell always has an even number of crossings"),
+            };
+
+            for &(a, b) in segments {
+                neighbors.entry(a).or_default().push(b);
+                neighbors.entry(b).or_default().push(a);
+            }
+        }
+    }
+
+    // Walk the contours. Every crossing is shared by exactly two segments, so the contours are closed.
+    let mut edges: Vec<GridEdge> = neighbors.keys().copied().collect();
+    edges.sort_unstable();
+
+    let mut visited = HashSet::default();
+    let mut contours = vec![];
+    for start in edges {
+        if !visited.insert(start) {
+            continue;
+        }
+        let mut contour = vec![crossing(start)];
+        let mut current = start;
+        while let Some(&next) = neighbors[&current]
+            .iter()
+            .find(|edge| !visited.contains(*edge))
+        {
+            visited.insert(next);
+            contour.push(crossing(next));
+            current = next;
+        }
+        contours.push(contour);
+    }
+
+    contours
+}
+
+/// Simplifies a closed polyline with the Ramer-Douglas-Peucker algorithm.
+fn simplify_closed(points: &[Vector], tolerance: Scalar) -> Vec<Vector> {
+    if tolerance <= 0.0 || points.len() < 4 {
+        return points.to_vec();
+    }
+
+    // Split the loop at the point that is farthest from the first point
+    let farthest = (1..points.len())
+        .max_by(|&a, &b| {
+            let distance_a = points[a].distance_squared(points[0]);
+            let distance_b = points[b].distance_squared(points[0]);
+            distance_a.total_cmp(&distance_b)
+        })
+        .unwrap_or(0);
+
+    let mut first = points[..=farthest].to_vec();
+    let mut second = points[farthest..].to_vec();
+    second.push(points[0]);
+
+    first = simplify_open(&first, tolerance);
+    second = simplify_open(&second, tolerance);
+
+    // The halves share their end points
+    first.pop();
+    second.pop();
+    first.extend(second);
+    first
+}
+
+/// Simplifies an open polyline with the Ramer-Douglas-Peucker algorithm, keeping its end points.
+fn simplify_open(points: &[Vector], tolerance: Scalar) -> Vec<Vector> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+
+    let (start, end) = (points[0], points[points.len() - 1]);
+    let direction = (end - start).normalize_or_zero();
+
+    let (index, distance) = points[1..points.len() - 1]
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let offset = *p - start;
+            let distance = if direction == Vector::ZERO {
+                offset.length()
+            } else {
+                offset.perp_dot(direction).abs()
+            };
+            (i + 1, distance)
+        })
+        .fold(
+            (0, 0.0),
+            |max, current| {
+                if current.1 > max.1 {
+                    current
+                } else {
+                    max
+                }
+            },
+        );
+
+    if distance <= tolerance {
+        return vec![start, end];
+    }
+
+    let mut simplified = simplify_open(&points[..=index], tolerance);
+    simplified.pop();
+    simplified.extend(simplify_open(&points[index..], tolerance));
+    simplified
+}
@@ -1,19 +1,45 @@
 //! Components used for rigid bodies, colliders and mass properties.
 
+mod aerodynamics;
 mod collider;
+#[cfg(feature = "collider-asset")]
+mod collider_asset;
+#[cfg(all(feature = "collider-from-mesh", feature = "collider-asset"))]
+mod collider_cache;
+mod custom_shape;
+mod fields;
 mod forces;
+#[cfg(all(feature = "2d", feature = "collider-from-image"))]
+mod image_collider;
 mod layers;
 mod locked_axes;
 mod mass_properties;
 mod rotation;
+#[cfg(feature = "2d")]
+mod tile_grid;
+#[cfg(feature = "3d")]
+mod voxels;
 mod world_queries;
 
+pub use aerodynamics::*;
 pub use collider::*;
+#[cfg(feature = "collider-asset")]
+pub use collider_asset::*;
+#[cfg(all(feature = "collider-from-mesh", feature = "collider-asset"))]
+pub use collider_cache::*;
+pub use custom_shape::*;
+pub use fields::*;
 pub use forces::*;
+#[cfg(all(feature = "2d", feature = "collider-from-image"))]
+pub use image_collider::*;
 pub use layers::*;
 pub use locked_axes::*;
 pub use mass_properties::*;
 pub use rotation::*;
+#[cfg(feature = "2d")]
+pub use tile_grid::*;
+#[cfg(feature = "3d")]
+pub use voxels::*;
 pub use world_queries::*;
 
 use crate::prelude::*;
@@ -471,6 +497,31 @@ pub(crate) struct PreSolveAngularVelocity(pub Vector);
 #[reflect(Component)]
 pub struct GravityScale(pub Scalar);
 
+/// Overrides the global [`Gravity`] for a specific [rigid body](RigidBody).
+///
+/// The override is still affected by [`GravityScale`] and any [gravity fields](GravityField)
+/// that contain the body, which use the override instead of the global gravity as their starting point.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::{math::Vector, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::{math::Vector, prelude::*};")]
+///
+/// // Spawn a body that falls upwards regardless of the global gravity
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((
+///         RigidBody::Dynamic,
+///         GravityOverride(Vector::Y * 9.81),
+///     ));
+/// }
+/// ```
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq, Default, Deref, DerefMut, From)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct GravityOverride(pub Vector);
+
 /// Determines how coefficients are combined for [`Restitution`] and [`Friction`].
 /// The default is `Average`.
 ///
@@ -0,0 +1,466 @@
+//! [`TileGridCollider`] component.
+
+use crate::prelude::*;
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+use std::collections::BTreeMap;
+
+/// A component that generates optimized colliders for a grid of tiles, like a tilemap.
+///
+/// Spawning one collider per tile results in thousands of colliders, and bodies sliding along the ground
+/// can get caught on the seams between neighbouring tiles. Instead, the solid tiles are merged into
+/// as few shapes as possible, either as rectangles or as outline polylines depending on the [`TileGridMode`].
+///
+/// The grid is divided into square chunks of [`chunk_size`](Self::chunk_size) tiles, `16` by default. Each chunk
+/// is spawned as a child entity of the grid with a [`TileGridChunk`] component and a compound [`Collider`],
+/// so the grid entity should typically be a [static](RigidBody::Static) rigid body. When tiles are edited,
+/// only the colliders of the chunks that changed are rebuilt. Tiles are not merged across chunks,
+/// so larger chunks result in fewer seams but make rebuilding slower.
+///
+/// Tiles can have their own [`CollisionLayers`] and [`Friction`] using [`GridTile`]. Tiles with different
+/// properties are never merged, and each set of properties in a chunk gets its own child entity.
+///
+/// The center of the tile `(x, y)` is at `(x, y) * tile_size` in the local space of the grid entity,
+/// so the tile `(0, 0)` is centered on the grid entity and the grid extends along the positive axes.
+///
+/// If you only need a single collider for the whole grid, use [`TileGridCollider::collider`]
+/// instead of adding the component.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_xpbd_2d::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     let level = [
+///         "#........#",
+///         "#...###..#",
+///         "##########",
+///     ];
+///
+///     let mut grid = TileGridCollider::new(10, 3, Vec2::splat(16.0));
+///     for (row, line) in level.iter().rev().enumerate() {
+///         for (column, tile) in line.chars().enumerate() {
+///             if tile == '#' {
+///                 grid.set_solid(column as u32, row as u32);
+///             }
+///         }
+///     }
+///
+///     commands.spawn((RigidBody::Static, grid, SpatialBundle::default()));
+/// }
+///
+/// // Tiles can be edited at runtime, and only the affected chunk is rebuilt
+/// fn dig(mut grids: Query<&mut TileGridCollider>) {
+///     for mut grid in &mut grids {
+///         grid.set_tile(5, 1, None);
+///     }
+/// }
+/// ```
+#[derive(Component, Clone, Debug, PartialEq)]
+pub struct TileGridCollider {
+    width: u32,
+    height: u32,
+    tiles: Vec<Option<GridTile>>,
+    tile_size: Vector,
+    mode: TileGridMode,
+    chunk_size: u32,
+    /// The chunks that need to be rebuilt.
+    dirty_chunks: HashSet<UVec2>,
+    /// True if the whole grid needs to be rebuilt.
+    rebuild_all: bool,
+}
+
+/// The properties of a solid tile in a [`TileGridCollider`].
+///
+/// If a property is `None`, the chunk entity doesn't get the corresponding component,
+/// and the default value is used.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GridTile {
+    /// The collision layers of the tile.
+    pub layers: Option<CollisionLayers>,
+    /// The friction of the tile.
+    pub friction: Option<Friction>,
+}
+
+impl GridTile {
+    /// Creates a solid tile with default properties.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the collision layers of the tile.
+    pub fn with_layers(self, layers: CollisionLayers) -> Self {
+        Self {
+            layers: Some(layers),
+            ..self
+        }
+    }
+
+    /// Sets the friction of the tile.
+    pub fn with_friction(self, friction: Friction) -> Self {
+        Self {
+            friction: Some(friction),
+            ..self
+        }
+    }
+}
+
+/// Determines the shapes generated by a [`TileGridCollider`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TileGridMode {
+    /// Solid tiles are merged into as few rectangles as possible.
+    /// Rectangles have volume, so bodies can't tunnel into the ground as easily.
+    #[default]
+    Rectangles,
+    /// Only the outlines of solid regions are generated as polylines, where collinear edges are merged.
+    /// This removes all seams along flat surfaces, but bodies that end up inside the outline
+    /// aren't pushed out.
+    Outlines,
+}
+
+/// A component for the child entities of a [`TileGridCollider`] that hold the colliders of a chunk.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TileGridChunk {
+    /// The coordinates of the chunk, in chunks.
+    pub chunk: UVec2,
+}
+
+impl TileGridCollider {
+    /// Creates an empty grid with the given number of tiles along each axis and the size of a tile.
+    pub fn new(width: u32, height: u32, tile_size: Vector) -> Self {
+        Self {
+            width,
+            height,
+            tiles: vec![None; (width * height) as usize],
+            tile_size,
+            mode: TileGridMode::default(),
+            chunk_size: 16,
+            dirty_chunks: HashSet::default(),
+            rebuild_all: true,
+        }
+    }
+
+    /// Creates a grid with the given number of tiles along each axis and the size of a tile.
+    /// The tiles are given in rows starting from `y = 0`, and `true` means that the tile is solid.
+    ///
+    /// Missing tiles are empty, and extra tiles are ignored.
+    pub fn from_solid(
+        width: u32,
+        height: u32,
+        tile_size: Vector,
+        solid: impl IntoIterator<Item = bool>,
+    ) -> Self {
+        let mut grid = Self::new(width, height, tile_size);
+        for (tile, solid) in grid.tiles.iter_mut().zip(solid) {
+            *tile = solid.then(GridTile::default);
+        }
+        grid
+    }
+
+    /// Sets the type of the generated colliders.
+    pub fn with_mode(self, mode: TileGridMode) -> Self {
+        Self { mode, ..self }
+    }
+
+    /// Sets the width and height of the chunks in tiles. Defaults to `16`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    pub fn with_chunk_size(self, chunk_size: u32) -> Self {
+        assert!(chunk_size > 0, "chunk size must be greater than zero");
+        Self { chunk_size, ..self }
+    }
+
+    /// Returns the size of a single tile.
+    pub fn tile_size(&self) -> Vector {
+        self.tile_size
+    }
+
+    /// Sets the size of a single tile. This rebuilds the whole grid.
+    pub fn set_tile_size(&mut self, tile_size: Vector) {
+        self.tile_size = tile_size;
+        self.rebuild_all = true;
+    }
+
+    /// Returns the type of the generated colliders.
+    pub fn mode(&self) -> TileGridMode {
+        self.mode
+    }
+
+    /// Sets the type of the generated colliders. This rebuilds the whole grid.
+    pub fn set_mode(&mut self, mode: TileGridMode) {
+        self.mode = mode;
+        self.rebuild_all = true;
+    }
+
+    /// Returns the width and height of the chunks in tiles.
+    pub fn chunk_size(&self) -> u32 {
+        self.chunk_size
+    }
+
+    /// Returns the number of tiles along the `X` axis.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the number of tiles along the `Y` axis.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the tile at the given coordinates, or `None` if the tile is empty or outside of the grid.
+    pub fn tile(&self, x: u32, y: u32) -> Option<&GridTile> {
+        self.index(x, y).and_then(|i| self.tiles[i].as_ref())
+    }
+
+    /// Returns true if the tile at the given coordinates is solid.
+    pub fn is_solid(&self, x: u32, y: u32) -> bool {
+        self.tile(x, y).is_some()
+    }
+
+    /// Sets the tile at the given coordinates. `None` makes the tile empty.
+    ///
+    /// Only the chunk containing the tile is rebuilt. With [`TileGridMode::Outlines`], the outlines
+    /// of neighboring chunks also depend on the tile, so editing a tile on the border of a chunk
+    /// rebuilds the chunks next to it as well.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the coordinates are outside of the grid.
+    pub fn set_tile(&mut self, x: u32, y: u32, tile: Option<GridTile>) {
+        let index = self.index(x, y).unwrap_or_else(|| {
+            panic!(
+                "tile ({x}, {y}) is outside of the {}x{} grid",
+                self.width, self.height
+            )
+        });
+        if self.tiles[index] != tile {
+            self.tiles[index] = tile;
+            self.dirty_chunks.insert(UVec2::new(x, y) / self.chunk_size);
+
+            // Outlines only have edges next to empty tiles, including tiles in other chunks
+            if self.mode == TileGridMode::Outlines {
+                let neighbors = [
+                    (x.checked_sub(1), Some(y)),
+                    (x.checked_add(1), Some(y)),
+                    (Some(x), y.checked_sub(1)),
+                    (Some(x), y.checked_add(1)),
+                ];
+                for (x, y) in neighbors {
+                    if let (Some(x), Some(y)) = (x, y) {
+                        if x < self.width && y < self.height {
+                            self.dirty_chunks.insert(UVec2::new(x, y) / self.chunk_size);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Makes the tile at the given coordinates solid with default properties.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the coordinates are outside of the grid.
+    pub fn set_solid(&mut self, x: u32, y: u32) {
+        self.set_tile(x, y, Some(GridTile::default()));
+    }
+
+    /// Creates a single collider for the whole grid, ignoring the properties of the tiles.
+    /// Returns `None` if the grid has no solid tiles.
+    pub fn collider(&self) -> Option<Collider> {
+        self.build(UVec2::ZERO, UVec2::new(self.width, self.height), |_| true)
+    }
+
+    /// Creates the colliders of a chunk, one for each set of tile properties in the chunk.
+    pub fn chunk_colliders(&self, chunk: UVec2) -> Vec<(GridTile, Collider)> {
+        let min = chunk * self.chunk_size;
+        let max = (min + self.chunk_size).min(UVec2::new(self.width, self.height));
+
+        // Find the distinct tile properties in the chunk
+        let mut properties: Vec<GridTile> = vec![];
+        for y in min.y..max.y {
+            for x in min.x..max.x {
+                if let Some(tile) = self.tile(x, y) {
+                    if !properties.contains(tile) {
+                        properties.push(*tile);
+                    }
+                }
+            }
+        }
+
+        properties
+            .into_iter()
+            .filter_map(|properties| {
+                self.build(min, max, |tile| *tile == properties)
+                    .map(|collider| (properties, collider))
+            })
+            .collect()
+    }
+
+    /// Returns the number of chunks along each axis.
+    pub fn chunk_count(&self) -> UVec2 {
+        (UVec2::new(self.width, self.height) + self.chunk_size - 1) / self.chunk_size
+    }
+
+    /// Returns the chunks that need to be rebuilt and marks them as clean.
+    /// If the whole grid needs to be rebuilt, returns `None`.
+    pub(crate) fn take_dirty_chunks(&mut self) -> Option<HashSet<UVec2>> {
+        if std::mem::take(&mut self.rebuild_all) {
+            self.dirty_chunks.clear();
+            return None;
+        }
+        Some(std::mem::take(&mut self.dirty_chunks))
+    }
+
+    fn index(&self, x: u32, y: u32) -> Option<usize> {
+        (x < self.width && y < self.height).then_some((y * self.width + x) as usize)
+    }
+
+    /// Builds a collider for the tiles within `min..max` that match the filter.
+    fn build(
+        &self,
+        min: UVec2,
+        max: UVec2,
+        filter: impl Fn(&GridTile) -> bool,
+    ) -> Option<Collider> {
+        let solid = |x: u32, y: u32| self.tile(x, y).is_some_and(&filter);
+        match self.mode {
+            TileGridMode::Rectangles => self.build_rectangles(min, max, solid),
+            TileGridMode::Outlines => self.build_outlines(min, max, solid),
+        }
+    }
+
+    /// Greedily merges the solid tiles within `min..max` into rectangles,
+    /// first along rows and then along columns.
+    fn build_rectangles(
+        &self,
+        min: UVec2,
+        max: UVec2,
+        solid: impl Fn(u32, u32) -> bool,
+    ) -> Option<Collider> {
+        let size = max - min;
+        let mut covered = vec![false; (size.x * size.y) as usize];
+        let index = |x: u32, y: u32| ((y - min.y) * size.x + x - min.x) as usize;
+        let free = |covered: &[bool], x: u32, y: u32| solid(x, y) && !covered[index(x, y)];
+
+        let mut shapes = vec![];
+        for y in min.y..max.y {
+            for x in min.x..max.x {
+                if !free(&covered, x, y) {
+                    continue;
+                }
+
+                let mut end_x = x + 1;
+                while end_x < max.x && free(&covered, end_x, y) {
+                    end_x += 1;
+                }
+                let mut end_y = y + 1;
+                while end_y < max.y && (x..end_x).all(|x| free(&covered, x, end_y)) {
+                    end_y += 1;
+                }
+                for covered_y in y..end_y {
+                    for covered_x in x..end_x {
+                        covered[index(covered_x, covered_y)] = true;
+                    }
+                }
+
+                let tiles = Vector::new((end_x - x) as Scalar, (end_y - y) as Scalar);
+                let center =
+                    (Vector::new(x as Scalar, y as Scalar) + (tiles - 1.0) / 2.0) * self.tile_size;
+                let extents = tiles * self.tile_size;
+                shapes.push((
+                    Position(center),
+                    Rotation::default(),
+                    Collider::cuboid(extents.x, extents.y),
+                ));
+            }
+        }
+
+        (!shapes.is_empty()).then(|| Collider::compound(shapes))
+    }
+
+    /// Collects the edges between the solid tiles within `min..max` and empty tiles,
+    /// merges collinear edges, and joins them into a polyline.
+    fn build_outlines(
+        &self,
+        min: UVec2,
+        max: UVec2,
+        solid: impl Fn(u32, u32) -> bool,
+    ) -> Option<Collider> {
+        // Tiles outside of the grid are empty. Edges between tiles of different types aren't reachable,
+        // so only edges next to empty tiles are kept.
+        let empty = |x: i64, y: i64| {
+            x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 || {
+                !self.is_solid(x as u32, y as u32)
+            }
+        };
+
+        // Unit edges keyed by the grid line they lie on, stored as the coordinate along the line
+        let mut horizontal: BTreeMap<i64, Vec<i64>> = BTreeMap::new();
+        let mut vertical: BTreeMap<i64, Vec<i64>> = BTreeMap::new();
+        for y in min.y..max.y {
+            for x in min.x..max.x {
+                if !solid(x, y) {
+                    continue;
+                }
+                let (x, y) = (x as i64, y as i64);
+                if empty(x, y - 1) {
+                    horizontal.entry(y).or_default().push(x);
+                }
+                if empty(x, y + 1) {
+                    horizontal.entry(y + 1).or_default().push(x);
+                }
+                if empty(x - 1, y) {
+                    vertical.entry(x).or_default().push(y);
+                }
+                if empty(x + 1, y) {
+                    vertical.entry(x + 1).or_default().push(y);
+                }
+            }
+        }
+
+        // Grid line intersections are shared by the segments that meet at them
+        let mut vertices = vec![];
+        let mut vertex_indices: HashMap<(i64, i64), u32> = HashMap::default();
+        let mut vertex = |x: i64, y: i64| {
+            *vertex_indices.entry((x, y)).or_insert_with(|| {
+                vertices.push((Vector::new(x as Scalar, y as Scalar) - 0.5) * self.tile_size);
+                vertices.len() as u32 - 1
+            })
+        };
+
+        let mut indices = vec![];
+        for (horizontal, lines) in [(true, horizontal), (false, vertical)] {
+            for (line, mut edges) in lines {
+                for (start, end) in merge_runs(&mut edges) {
+                    if horizontal {
+                        indices.push([vertex(start, line), vertex(end, line)]);
+                    } else {
+                        indices.push([vertex(line, start), vertex(line, end)]);
+                    }
+                }
+            }
+        }
+
+        (!indices.is_empty()).then(|| Collider::polyline(vertices, Some(indices)))
+    }
+}
+
+/// Sorts unit edges given by their start coordinates and merges consecutive edges into runs.
+fn merge_runs(edges: &mut [i64]) -> Vec<(i64, i64)> {
+    edges.sort_unstable();
+    let mut runs: Vec<(i64, i64)> = vec![];
+    for &start in edges.iter() {
+        match runs.last_mut() {
+            Some((_, end)) if *end == start => *end = start + 1,
+            _ => runs.push((start, start + 1)),
+        }
+    }
+    runs
+}
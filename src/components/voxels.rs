@@ -0,0 +1,383 @@
+//! [`VoxelCollider`] shape.
+
+use std::sync::Arc;
+
+use crate::prelude::*;
+use bevy::prelude::*;
+use parry::{
+    bounding_volume::{Aabb, BoundingSphere},
+    mass_properties::MassProperties,
+    math::{Isometry, Point, Real},
+    partitioning::{Qbvh, QbvhDataGenerator},
+    query::{
+        details::{
+            PointCompositeShapeProjBestFirstVisitor, RayCompositeShapeToiAndNormalBestFirstVisitor,
+            RayCompositeShapeToiBestFirstVisitor,
+        },
+        PointProjection, PointQuery, Ray, RayCast, RayIntersection,
+    },
+    shape::{
+        Cuboid, FeatureId, Shape, ShapeType, SimdCompositeShape, TypedShape,
+        TypedSimdCompositeShape,
+    },
+    utils::DefaultStorage,
+};
+
+/// The identifier of [`VoxelCollider`] in `TypedShape::Custom`.
+const VOXEL_SHAPE_ID: u32 = 0x766f_7865;
+
+/// A collider shape made of a dense 3D grid of solid or empty cubic cells called voxels,
+/// like a chunk of a block world.
+///
+/// Unlike a triangle mesh of the chunk, the voxels don't need to be meshed, and setting a single voxel
+/// is a constant-time operation. Each solid voxel is a cuboid, and contacts on faces that are shared with
+/// a neighbouring solid voxel are ignored, so bodies can slide along flat surfaces without catching
+/// on the edges between voxels.
+///
+/// The voxel `(x, y, z)` spans from `(x, y, z) * voxel_size` to `(x + 1, y + 1, z + 1) * voxel_size`
+/// in the local space of the collider, so the minimum corner of the grid is at the collider's origin.
+///
+/// The shape is used through [`Collider::voxels`], and it supports contacts, [spatial queries](spatial_query)
+/// and the functions in [`contact_query`] like the built-in shapes.
+/// The voxels of an existing collider can be changed with [`Collider::set_voxel`],
+/// which also documents the costs of an edit.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     // A 16x16x16 chunk of 1x1x1 voxels where the bottom half is solid
+///     let size = UVec3::splat(16);
+///     let solid = (0..size.x * size.y * size.z).map(|i| {
+///         let y = i / size.x % size.y;
+///         y < 8
+///     });
+///     commands.spawn((
+///         RigidBody::Static,
+///         Collider::voxels(VoxelCollider::from_solid(size, Vec3::ONE, solid)),
+///     ));
+/// }
+///
+/// // Dig a hole by making a single voxel empty
+/// fn dig(mut chunks: Query<&mut Collider>) {
+///     for mut collider in &mut chunks {
+///         let _ = collider.set_voxel(UVec3::new(8, 7, 8), false);
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct VoxelCollider {
+    size: UVec3,
+    voxel_size: Vector,
+    solid: Vec<bool>,
+    /// The shape of a single voxel.
+    cuboid: Cuboid,
+    /// A bounding volume hierarchy of all voxels, both solid and empty, so that it never needs to be rebuilt
+    /// when voxels change. It is shared between clones.
+    qbvh: Arc<Qbvh<u32>>,
+}
+
+impl VoxelCollider {
+    /// Creates an empty voxel grid with the given number of voxels along each axis and the size of a voxel.
+    pub fn new(size: UVec3, voxel_size: Vector) -> Self {
+        Self::from_solid(size, voxel_size, [])
+    }
+
+    /// Creates a voxel grid with the given number of voxels along each axis and the size of a voxel.
+    /// `true` means that the voxel is solid.
+    ///
+    /// The voxels are ordered along the `X` axis first, then the `Y` axis, and finally the `Z` axis,
+    /// so the voxel `(x, y, z)` is at index `x + y * size.x + z * size.x * size.y`.
+    /// Missing voxels are empty, and extra voxels are ignored.
+    pub fn from_solid(
+        size: UVec3,
+        voxel_size: Vector,
+        solid: impl IntoIterator<Item = bool>,
+    ) -> Self {
+        let count = (size.x * size.y * size.z) as usize;
+        let mut voxels = Self {
+            size,
+            voxel_size,
+            solid: solid
+                .into_iter()
+                .chain(std::iter::repeat(false))
+                .take(count)
+                .collect(),
+            cuboid: Cuboid::new((voxel_size / 2.0).into()),
+            qbvh: Arc::new(Qbvh::new()),
+        };
+        voxels.rebuild_qbvh();
+        voxels
+    }
+
+    /// Returns the number of voxels along each axis.
+    pub fn size(&self) -> UVec3 {
+        self.size
+    }
+
+    /// Returns the size of a single voxel.
+    pub fn voxel_size(&self) -> Vector {
+        self.voxel_size
+    }
+
+    /// Returns true if the given voxel is solid. Voxels outside of the grid are empty.
+    pub fn is_solid(&self, voxel: UVec3) -> bool {
+        self.index(voxel).is_some_and(|i| self.solid[i])
+    }
+
+    /// Makes the given voxel solid or empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the voxel is outside of the grid.
+    pub fn set_solid(&mut self, voxel: UVec3, solid: bool) {
+        let index = self.index(voxel).unwrap_or_else(|| {
+            panic!(
+                "voxel {voxel} is outside of the {}x{}x{} grid",
+                self.size.x, self.size.y, self.size.z
+            )
+        });
+        self.solid[index] = solid;
+    }
+
+    /// Returns the voxel that contains the given point in the local space of the collider,
+    /// or `None` if the point is outside of the grid.
+    pub fn voxel_at_point(&self, point: Vector) -> Option<UVec3> {
+        let voxel = (point / self.voxel_size).floor();
+        if voxel.cmplt(Vector::ZERO).any() {
+            return None;
+        }
+        let voxel = UVec3::new(voxel.x as u32, voxel.y as u32, voxel.z as u32);
+        voxel.cmplt(self.size).all().then_some(voxel)
+    }
+
+    /// Returns the center of the given voxel in the local space of the collider.
+    pub fn voxel_center(&self, voxel: UVec3) -> Vector {
+        (Vector::new(voxel.x as Scalar, voxel.y as Scalar, voxel.z as Scalar) + 0.5)
+            * self.voxel_size
+    }
+
+    /// Returns an iterator over the solid voxels.
+    pub fn solid_voxels(&self) -> impl Iterator<Item = UVec3> + '_ {
+        self.solid
+            .iter()
+            .enumerate()
+            .filter(|(_, solid)| **solid)
+            .map(|(i, _)| self.voxel(i as u32))
+    }
+
+    /// Returns a copy of the voxel grid with the voxel size multiplied by `scale`.
+    pub(crate) fn scaled(&self, scale: Vector) -> Self {
+        Self {
+            voxel_size: self.voxel_size * scale,
+            cuboid: Cuboid::new((self.voxel_size * scale / 2.0).into()),
+            qbvh: Arc::new(self.qbvh.as_ref().clone().scaled(&scale.into())),
+            ..self.clone()
+        }
+    }
+
+    /// Returns true if a contact normal on the given voxel points toward a neighbouring solid voxel,
+    /// which means that the contact is on an internal face.
+    pub(crate) fn is_internal_normal(&self, voxel_index: u32, normal: Vector) -> bool {
+        let voxel = self.voxel(voxel_index).as_ivec3();
+        let abs = normal.abs();
+        let axis = if abs.x >= abs.y && abs.x >= abs.z {
+            0
+        } else if abs.y >= abs.z {
+            1
+        } else {
+            2
+        };
+        // Only contacts on faces are internal, not ones on edges or vertices
+        if normal[axis].abs() < 0.999 {
+            return false;
+        }
+        let mut neighbor = voxel;
+        neighbor[axis] += normal[axis].signum() as i32;
+        neighbor.cmpge(IVec3::ZERO).all() && self.is_solid(neighbor.as_uvec3())
+    }
+
+    fn index(&self, voxel: UVec3) -> Option<usize> {
+        voxel.cmplt(self.size).all().then_some(
+            (voxel.x + voxel.y * self.size.x + voxel.z * self.size.x * self.size.y) as usize,
+        )
+    }
+
+    fn voxel(&self, index: u32) -> UVec3 {
+        UVec3::new(
+            index % self.size.x,
+            index / self.size.x % self.size.y,
+            index / (self.size.x * self.size.y),
+        )
+    }
+
+    fn voxel_isometry(&self, index: u32) -> Isometry<Real> {
+        let center = self.voxel_center(self.voxel(index));
+        Isometry::translation(center.x, center.y, center.z)
+    }
+
+    fn rebuild_qbvh(&mut self) {
+        struct DataGenerator<'a>(&'a VoxelCollider);
+
+        impl<'a> QbvhDataGenerator<u32> for DataGenerator<'a> {
+            fn size_hint(&self) -> usize {
+                self.0.solid.len()
+            }
+
+            #[inline(always)]
+            fn for_each(&mut self, mut f: impl FnMut(u32, Aabb)) {
+                for i in 0..self.0.solid.len() as u32 {
+                    f(i, self.0.cuboid.aabb(&self.0.voxel_isometry(i)));
+                }
+            }
+        }
+
+        let mut qbvh = Qbvh::new();
+        qbvh.clear_and_rebuild(DataGenerator(self), 0.0);
+        self.qbvh = Arc::new(qbvh);
+    }
+}
+
+impl From<VoxelCollider> for Collider {
+    fn from(voxels: VoxelCollider) -> Self {
+        Collider::voxels(voxels)
+    }
+}
+
+impl Shape for VoxelCollider {
+    fn clone_box(&self) -> Box<dyn Shape> {
+        Box::new(self.clone())
+    }
+
+    fn compute_local_aabb(&self) -> Aabb {
+        *self.qbvh.root_aabb()
+    }
+
+    fn compute_local_bounding_sphere(&self) -> BoundingSphere {
+        self.compute_local_aabb().bounding_sphere()
+    }
+
+    fn mass_properties(&self, density: Real) -> MassProperties {
+        let voxel = MassProperties::from_cuboid(density, self.cuboid.half_extents);
+        self.solid
+            .iter()
+            .enumerate()
+            .filter(|(_, solid)| **solid)
+            .map(|(i, _)| voxel.transform_by(&self.voxel_isometry(i as u32)))
+            .sum()
+    }
+
+    fn shape_type(&self) -> ShapeType {
+        ShapeType::Custom
+    }
+
+    fn as_typed_shape(&self) -> TypedShape<'_> {
+        TypedShape::Custom(VOXEL_SHAPE_ID)
+    }
+
+    fn ccd_thickness(&self) -> Real {
+        self.cuboid.half_extents.min()
+    }
+
+    fn ccd_angular_thickness(&self) -> Real {
+        PI / 2.0
+    }
+
+    fn as_composite_shape(&self) -> Option<&dyn SimdCompositeShape> {
+        Some(self as &dyn SimdCompositeShape)
+    }
+}
+
+impl SimdCompositeShape for VoxelCollider {
+    fn map_part_at(&self, shape_id: u32, f: &mut dyn FnMut(Option<&Isometry<Real>>, &dyn Shape)) {
+        // Empty voxels aren't parts of the shape
+        if self.solid[shape_id as usize] {
+            f(Some(&self.voxel_isometry(shape_id)), &self.cuboid);
+        }
+    }
+
+    fn qbvh(&self) -> &Qbvh<u32> {
+        &self.qbvh
+    }
+}
+
+impl TypedSimdCompositeShape for VoxelCollider {
+    type PartShape = Cuboid;
+    type PartId = u32;
+    type QbvhStorage = DefaultStorage;
+
+    fn map_typed_part_at(
+        &self,
+        shape_id: u32,
+        mut f: impl FnMut(Option<&Isometry<Real>>, &Self::PartShape),
+    ) {
+        if self.solid[shape_id as usize] {
+            f(Some(&self.voxel_isometry(shape_id)), &self.cuboid);
+        }
+    }
+
+    fn map_untyped_part_at(
+        &self,
+        shape_id: u32,
+        mut f: impl FnMut(Option<&Isometry<Real>>, &dyn Shape),
+    ) {
+        if self.solid[shape_id as usize] {
+            f(Some(&self.voxel_isometry(shape_id)), &self.cuboid);
+        }
+    }
+
+    fn typed_qbvh(&self) -> &Qbvh<u32> {
+        &self.qbvh
+    }
+}
+
+impl RayCast for VoxelCollider {
+    fn cast_local_ray(&self, ray: &Ray, max_toi: Real, solid: bool) -> Option<Real> {
+        let mut visitor = RayCompositeShapeToiBestFirstVisitor::new(self, ray, max_toi, solid);
+        self.qbvh
+            .traverse_best_first(&mut visitor)
+            .map(|(_, (_, toi))| toi)
+    }
+
+    fn cast_local_ray_and_get_normal(
+        &self,
+        ray: &Ray,
+        max_toi: Real,
+        solid: bool,
+    ) -> Option<RayIntersection> {
+        let mut visitor =
+            RayCompositeShapeToiAndNormalBestFirstVisitor::new(self, ray, max_toi, solid);
+        self.qbvh
+            .traverse_best_first(&mut visitor)
+            .map(|(_, (_, intersection))| intersection)
+    }
+}
+
+impl PointQuery for VoxelCollider {
+    fn project_local_point(&self, point: &Point<Real>, solid: bool) -> PointProjection {
+        if solid && self.contains_local_point(point) {
+            return PointProjection::new(true, *point);
+        }
+        let mut visitor = PointCompositeShapeProjBestFirstVisitor::new(self, point, solid);
+        self.qbvh.traverse_best_first(&mut visitor).map_or_else(
+            // Without solid voxels, there is no point to project onto
+            || PointProjection::new(false, Point::new(Real::MAX, Real::MAX, Real::MAX)),
+            |(_, (projection, _))| projection,
+        )
+    }
+
+    fn project_local_point_and_get_feature(
+        &self,
+        point: &Point<Real>,
+    ) -> (PointProjection, FeatureId) {
+        (self.project_local_point(point, false), FeatureId::Unknown)
+    }
+
+    fn contains_local_point(&self, point: &Point<Real>) -> bool {
+        self.voxel_at_point(Vector::from(*point))
+            .is_some_and(|voxel| self.is_solid(voxel))
+    }
+}
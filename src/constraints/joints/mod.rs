@@ -348,3 +348,57 @@ impl AngleLimit {
         None
     }
 }
+
+/// A motor that drives the relative rotation of two bodies toward a target rotation.
+///
+/// The motor is solved as a soft angular constraint. Its stiffness is controlled by the `compliance`,
+/// and the torque it can apply is limited by the `max_torque`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct RotationMotor {
+    /// The target rotation of the second body relative to the first body.
+    pub target_rotation: Rotation,
+    /// The motor's compliance, the inverse of stiffness, has the unit radians / Newton meter.
+    /// A compliance of zero drives the bodies to the target as fast as the `max_torque` allows.
+    pub compliance: Scalar,
+    /// The maximum torque that the motor can apply.
+    pub max_torque: Scalar,
+}
+
+impl RotationMotor {
+    /// Creates a new [`RotationMotor`] with the given target rotation, zero compliance and no torque limit.
+    pub fn new(target_rotation: Rotation) -> Self {
+        Self {
+            target_rotation,
+            compliance: 0.0,
+            max_torque: Scalar::INFINITY,
+        }
+    }
+
+    /// Sets the compliance of the motor.
+    pub fn with_compliance(self, compliance: Scalar) -> Self {
+        Self { compliance, ..self }
+    }
+
+    /// Sets the maximum torque that the motor can apply.
+    pub fn with_max_torque(self, max_torque: Scalar) -> Self {
+        Self { max_torque, ..self }
+    }
+
+    /// Returns the angular correction that rotates the second body to the target rotation relative to the first body.
+    #[cfg(feature = "2d")]
+    fn compute_correction(&self, rot1: &Rotation, rot2: &Rotation) -> Vector3 {
+        (*rot2 - rot1.mul(self.target_rotation)).as_radians() * Vector3::Z
+    }
+
+    /// Returns the angular correction that rotates the second body to the target rotation relative to the first body.
+    #[cfg(feature = "3d")]
+    fn compute_correction(&self, rot1: &Rotation, rot2: &Rotation) -> Vector3 {
+        let mut difference = rot2.0 * (rot1.0 * self.target_rotation.0).inverse();
+        // Take the shortest path
+        if difference.w < 0.0 {
+            difference = -difference;
+        }
+        2.0 * difference.xyz()
+    }
+}
@@ -32,12 +32,16 @@ pub struct SphericalJoint {
     pub damping_linear: Scalar,
     /// Angular damping applied by the joint.
     pub damping_angular: Scalar,
+    /// A motor that drives the relative rotation of the bodies toward a target rotation.
+    pub motor: Option<RotationMotor>,
     /// Lagrange multiplier for the positional correction.
     pub position_lagrange: Scalar,
     /// Lagrange multiplier for the angular correction caused by the swing limits.
     pub swing_lagrange: Scalar,
     /// Lagrange multiplier for the angular correction caused by the twist limits.
     pub twist_lagrange: Scalar,
+    /// Lagrange multiplier for the angular correction caused by the motor.
+    pub motor_lagrange: Scalar,
     /// The joint's compliance, the inverse of stiffness, has the unit meters / Newton.
     pub compliance: Scalar,
     /// The force exerted by the joint.
@@ -46,6 +50,8 @@ pub struct SphericalJoint {
     pub swing_torque: Torque,
     /// The torque exerted by the joint when limiting the relative rotation of the bodies around the `twist_axis`.
     pub twist_torque: Torque,
+    /// The torque exerted by the motor.
+    pub motor_torque: Torque,
 }
 
 impl XpbdConstraint<2> for SphericalJoint {
@@ -57,6 +63,7 @@ impl XpbdConstraint<2> for SphericalJoint {
         self.position_lagrange = 0.0;
         self.swing_lagrange = 0.0;
         self.twist_lagrange = 0.0;
+        self.motor_lagrange = 0.0;
     }
 
     fn solve(&mut self, bodies: [&mut RigidBodyQueryItem; 2], dt: Scalar) {
@@ -76,6 +83,9 @@ impl XpbdConstraint<2> for SphericalJoint {
         );
         self.position_lagrange = lagrange;
 
+        // Drive the bodies toward the target rotation
+        self.motor_torque = self.apply_motor(body1, body2, dt);
+
         // Apply swing limits
         self.swing_torque = self.apply_swing_limits(body1, body2, dt);
 
@@ -97,9 +107,11 @@ impl Joint for SphericalJoint {
             twist_limit: None,
             damping_linear: 1.0,
             damping_angular: 1.0,
+            motor: None,
             position_lagrange: 0.0,
             swing_lagrange: 0.0,
             twist_lagrange: 0.0,
+            motor_lagrange: 0.0,
             compliance: 0.0,
             force: Vector::ZERO,
             #[cfg(feature = "2d")]
@@ -110,6 +122,10 @@ impl Joint for SphericalJoint {
             twist_torque: 0.0,
             #[cfg(feature = "3d")]
             twist_torque: Vector::ZERO,
+            #[cfg(feature = "2d")]
+            motor_torque: 0.0,
+            #[cfg(feature = "3d")]
+            motor_torque: Vector::ZERO,
         }
     }
 
@@ -180,6 +196,52 @@ impl SphericalJoint {
         }
     }
 
+    /// Sets a [`RotationMotor`] that drives the relative rotation of the bodies toward a target rotation.
+    pub fn with_motor(self, motor: RotationMotor) -> Self {
+        Self {
+            motor: Some(motor),
+            ..self
+        }
+    }
+
+    /// Applies an angular correction that drives the relative rotation of the bodies toward the target
+    /// rotation of the [motor](SphericalJoint::motor).
+    fn apply_motor(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        dt: Scalar,
+    ) -> Torque {
+        let Some(motor) = self.motor else {
+            return Torque::ZERO;
+        };
+
+        let mut dq = motor.compute_correction(&body1.rotation, &body2.rotation);
+        let angle = dq.length();
+
+        if angle <= Scalar::EPSILON {
+            return Torque::ZERO;
+        }
+
+        // Limit the correction so that the torque doesn't exceed the maximum torque.
+        // The Lagrange multiplier update is `angle / (w1 + w2 + compliance / dt^2)`,
+        // and the torque is the multiplier divided by `dt^2`.
+        if motor.max_torque.is_finite() {
+            let axis = dq / angle;
+            let w1 = AngularConstraint::compute_generalized_inverse_mass(self, body1, axis);
+            let w2 = AngularConstraint::compute_generalized_inverse_mass(self, body2, axis);
+            let max_angle = motor.max_torque * ((w1 + w2) * dt.powi(2) + motor.compliance);
+            if angle > max_angle {
+                dq *= max_angle / angle;
+            }
+        }
+
+        let mut lagrange = self.motor_lagrange;
+        let torque = self.align_orientation(body1, body2, dq, &mut lagrange, motor.compliance, dt);
+        self.motor_lagrange = lagrange;
+        torque
+    }
+
     /// Applies angle limits to limit the relative rotation of the bodies around the `swing_axis`.
     fn apply_swing_limits(
         &mut self,
@@ -21,6 +21,7 @@
 //!     - [`SphericalJoint`]
 //!     - [`RevoluteJoint`]
 //!     - [`PrismaticJoint`]
+//! - [`PoseTarget`]
 //!
 //! More constraint types will be added in future releases. If you need more constraints now, consider
 //! [creating your own constraints](#custom-constraints).
@@ -202,6 +203,7 @@
 
 pub mod joints;
 pub mod penetration;
+pub mod pose_target;
 
 mod angular_constraint;
 mod position_constraint;
@@ -209,6 +211,7 @@ mod position_constraint;
 pub use angular_constraint::AngularConstraint;
 pub use joints::*;
 pub use penetration::*;
+pub use pose_target::*;
 pub use position_constraint::PositionConstraint;
 
 use crate::prelude::*;
@@ -258,23 +261,39 @@ pub trait XpbdConstraint<const ENTITY_COUNT: usize>: MapEntities {
         compliance: Scalar,
         dt: Scalar,
     ) -> Scalar {
-        // Compute the sum of all inverse masses multiplied by the squared lengths of the corresponding gradients.
-        let w_sum = inverse_masses
-            .iter()
-            .enumerate()
-            .fold(0.0, |acc, (i, w)| acc + *w * gradients[i].length_squared());
+        compute_lagrange_update(lagrange, c, gradients, inverse_masses, compliance, dt)
+    }
 
-        // Avoid division by zero
-        if w_sum <= Scalar::EPSILON {
-            return 0.0;
-        }
+    /// Sets the constraint's [Lagrange multipliers](constraints#lagrange-multipliers) to 0.
+    fn clear_lagrange_multipliers(&mut self);
+}
 
-        // tilde_a = a/h^2
-        let tilde_compliance = compliance / dt.powi(2);
+/// Computes how much a [Lagrange multiplier](constraints#lagrange-multipliers) changes when projecting
+/// a constraint for all participating particles.
+///
+/// This is the computation behind [`XpbdConstraint::compute_lagrange_update`], and it can also be used
+/// for constraints that aren't between rigid bodies, like the particle constraints of deformable bodies.
+pub fn compute_lagrange_update(
+    lagrange: Scalar,
+    c: Scalar,
+    gradients: &[Vector],
+    inverse_masses: &[Scalar],
+    compliance: Scalar,
+    dt: Scalar,
+) -> Scalar {
+    // Compute the sum of all inverse masses multiplied by the squared lengths of the corresponding gradients.
+    let w_sum = inverse_masses
+        .iter()
+        .enumerate()
+        .fold(0.0, |acc, (i, w)| acc + *w * gradients[i].length_squared());
 
-        (-c - tilde_compliance * lagrange) / (w_sum + tilde_compliance)
+    // Avoid division by zero
+    if w_sum <= Scalar::EPSILON {
+        return 0.0;
     }
 
-    /// Sets the constraint's [Lagrange multipliers](constraints#lagrange-multipliers) to 0.
-    fn clear_lagrange_multipliers(&mut self);
+    // tilde_a = a/h^2
+    let tilde_compliance = compliance / dt.powi(2);
+
+    (-c - tilde_compliance * lagrange) / (w_sum + tilde_compliance)
 }
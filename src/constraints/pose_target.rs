@@ -0,0 +1,345 @@
+//! [`PoseTarget`] component.
+
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// A soft constraint that drives a [dynamic](RigidBody::Dynamic) rigid body toward a target position
+/// and/or rotation in world space, like a spring and damper attached to a world frame.
+///
+/// Unlike hand-tuned forces, the target is solved as a compliant XPBD constraint during each substep, so the drive
+/// stays stable at high stiffness and low frame rates. This is useful for things like hovering drones,
+/// objects held by a player, and smoothing the motion of networked proxies.
+///
+/// The component is added to the body itself. The `position` is the target of the body's [`Position`],
+/// and the `rotation` is the target of the body's [`Rotation`]. If either of them is `None`,
+/// the body can move freely along that degree of freedom.
+///
+/// ## Stiffness and damping
+///
+/// The stiffness has the unit Newtons / meter for the position and Newton meters / radian for the rotation,
+/// and the damping has the unit Newton seconds / meter and Newton meter seconds / radian. The damping slows down
+/// the motion of the body, so a body with only damping acts like it's moving through a thick fluid.
+///
+/// The stiffness should be finite. For a rigid attachment to the world, use a very high stiffness or a [joint](joints).
+///
+/// The force and torque that the drive can apply can be limited with `max_force` and `max_torque`.
+/// The force and torque that were applied during the last substep are stored in `force` and `torque`.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// # #[cfg(all(feature = "3d", feature = "f32"))]
+/// fn setup(mut commands: Commands) {
+///     // A drone that hovers at a height of 5 meters and stays upright
+///     commands.spawn((
+///         RigidBody::Dynamic,
+///         Collider::cuboid(1.0, 0.2, 1.0),
+///         PoseTarget::new()
+///             .with_position(Vec3::Y * 5.0)
+///             .with_rotation(Rotation::default())
+///             .with_linear_drive(500.0, 50.0)
+///             .with_angular_drive(100.0, 10.0)
+///             .with_max_force(200.0),
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct PoseTarget {
+    /// The target position of the body.
+    pub position: Option<Vector>,
+    /// The target rotation of the body.
+    pub rotation: Option<Rotation>,
+    /// The stiffness of the drive toward the target position.
+    pub linear_stiffness: Scalar,
+    /// The damping of the linear motion of the body.
+    pub linear_damping: Scalar,
+    /// The stiffness of the drive toward the target rotation.
+    pub angular_stiffness: Scalar,
+    /// The damping of the angular motion of the body.
+    pub angular_damping: Scalar,
+    /// The maximum force that the drive can apply.
+    pub max_force: Scalar,
+    /// The maximum torque that the drive can apply.
+    pub max_torque: Scalar,
+    /// The force exerted by the drive during the last substep.
+    pub force: Vector,
+    /// The torque exerted by the drive during the last substep.
+    pub torque: Torque,
+}
+
+impl Default for PoseTarget {
+    fn default() -> Self {
+        Self {
+            position: None,
+            rotation: None,
+            linear_stiffness: 100.0,
+            linear_damping: 10.0,
+            angular_stiffness: 100.0,
+            angular_damping: 10.0,
+            max_force: Scalar::INFINITY,
+            max_torque: Scalar::INFINITY,
+            force: Vector::ZERO,
+            #[cfg(feature = "2d")]
+            torque: 0.0,
+            #[cfg(feature = "3d")]
+            torque: Vector::ZERO,
+        }
+    }
+}
+
+impl PoseTarget {
+    /// Creates a new [`PoseTarget`] without a target position or rotation.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the target position of the body.
+    pub fn with_position(self, position: Vector) -> Self {
+        Self {
+            position: Some(position),
+            ..self
+        }
+    }
+
+    /// Sets the target rotation of the body.
+    pub fn with_rotation(self, rotation: Rotation) -> Self {
+        Self {
+            rotation: Some(rotation),
+            ..self
+        }
+    }
+
+    /// Sets the stiffness and damping of the drive toward the target position.
+    pub fn with_linear_drive(self, stiffness: Scalar, damping: Scalar) -> Self {
+        Self {
+            linear_stiffness: stiffness,
+            linear_damping: damping,
+            ..self
+        }
+    }
+
+    /// Sets the stiffness and damping of the drive toward the target rotation.
+    pub fn with_angular_drive(self, stiffness: Scalar, damping: Scalar) -> Self {
+        Self {
+            angular_stiffness: stiffness,
+            angular_damping: damping,
+            ..self
+        }
+    }
+
+    /// Sets the maximum force that the drive can apply.
+    pub fn with_max_force(self, max_force: Scalar) -> Self {
+        Self { max_force, ..self }
+    }
+
+    /// Sets the maximum torque that the drive can apply.
+    pub fn with_max_torque(self, max_torque: Scalar) -> Self {
+        Self { max_torque, ..self }
+    }
+
+    /// Applies positional and angular corrections that drive the body toward the target pose.
+    pub(crate) fn solve(&mut self, body: &mut RigidBodyQueryItem, dt: Scalar) {
+        self.force = self.drive_position(body, dt);
+        self.torque = self.drive_rotation(body, dt);
+    }
+
+    /// Applies a positional correction toward the target position and returns the force exerted by it.
+    fn drive_position(&self, body: &mut RigidBodyQueryItem, dt: Scalar) -> Vector {
+        let Some(target) = self.position else {
+            return Vector::ZERO;
+        };
+
+        let current_position = body.current_position();
+        let offset = target - current_position;
+        let distance = offset.length();
+
+        // The direction of the correction. If the body is at the target, only damping is applied.
+        let displacement = current_position - body.previous_position.0;
+        let direction = if distance > Scalar::EPSILON {
+            offset / distance
+        } else {
+            displacement.normalize_or_zero()
+        };
+
+        let inv_mass = body.effective_inv_mass();
+
+        let correction = compute_drive(
+            direction,
+            distance,
+            displacement,
+            |direction| (direction * direction).dot(inv_mass),
+            self.linear_stiffness,
+            self.linear_damping,
+            self.max_force,
+            dt,
+        );
+
+        if correction.length() <= Scalar::EPSILON {
+            return Vector::ZERO;
+        }
+
+        body.accumulated_translation.0 += correction * inv_mass;
+
+        correction / dt.powi(2)
+    }
+
+    /// Applies an angular correction toward the target rotation and returns the torque exerted by it.
+    #[cfg(feature = "2d")]
+    fn drive_rotation(&self, body: &mut RigidBodyQueryItem, dt: Scalar) -> Torque {
+        let Some(target) = self.rotation else {
+            return 0.0;
+        };
+
+        let angle = (target - *body.rotation).as_radians();
+        let angular_displacement = (*body.rotation - body.previous_rotation.0).as_radians();
+
+        // The direction of the correction. If the body is at the target, only damping is applied.
+        let direction = if angle.abs() > Scalar::EPSILON {
+            angle.signum()
+        } else {
+            angular_displacement.signum()
+        };
+
+        let inv_inertia = body.effective_world_inv_inertia();
+
+        let magnitude = compute_correction(
+            angle.abs(),
+            direction * angular_displacement,
+            inv_inertia,
+            self.angular_stiffness,
+            self.angular_damping,
+            self.max_torque,
+            dt,
+        );
+
+        if magnitude.abs() <= Scalar::EPSILON {
+            return 0.0;
+        }
+
+        *body.rotation += Rotation::from_radians(inv_inertia * magnitude * direction);
+
+        magnitude * direction / dt.powi(2)
+    }
+
+    /// Applies an angular correction toward the target rotation and returns the torque exerted by it.
+    #[cfg(feature = "3d")]
+    fn drive_rotation(&self, body: &mut RigidBodyQueryItem, dt: Scalar) -> Torque {
+        let Some(target) = self.rotation else {
+            return Vector::ZERO;
+        };
+
+        let mut difference = target.0 * body.rotation.inverse().0;
+        // Take the shortest path
+        if difference.w < 0.0 {
+            difference = -difference;
+        }
+        let offset = 2.0 * difference.xyz();
+        let angle = offset.length();
+        let angular_displacement =
+            2.0 * (body.rotation.0 * body.previous_rotation.inverse().0).xyz();
+
+        // The direction of the correction. If the body is at the target, only damping is applied.
+        let direction = if angle > Scalar::EPSILON {
+            offset / angle
+        } else {
+            angular_displacement.normalize_or_zero()
+        };
+
+        let inv_inertia = body.effective_world_inv_inertia();
+
+        let correction = compute_drive(
+            direction,
+            angle,
+            angular_displacement,
+            |direction| direction.dot(inv_inertia * direction),
+            self.angular_stiffness,
+            self.angular_damping,
+            self.max_torque,
+            dt,
+        );
+
+        if correction.length() <= Scalar::EPSILON {
+            return Vector::ZERO;
+        }
+
+        let delta = inv_inertia * correction;
+        let rotation = body.rotation.0;
+        *body.rotation += Rotation(Quaternion::from_vec4(0.5 * delta.extend(0.0)) * rotation);
+
+        correction / dt.powi(2)
+    }
+}
+
+/// Computes the correction of a drive toward a target at the distance `error` along `direction`.
+///
+/// The stiffness only pulls the body along `direction`, but the damping acts on the whole `displacement`,
+/// so motion perpendicular to the direction of the target is slowed down as well. `inverse_mass` returns
+/// the generalized inverse mass along a direction. The result is clamped so that the force doesn't exceed `max_force`.
+#[allow(clippy::too_many_arguments)]
+fn compute_drive(
+    direction: Vector,
+    error: Scalar,
+    displacement: Vector,
+    inverse_mass: impl Fn(Vector) -> Scalar,
+    stiffness: Scalar,
+    damping: Scalar,
+    max_force: Scalar,
+    dt: Scalar,
+) -> Vector {
+    let along = compute_correction(
+        error,
+        direction.dot(displacement),
+        inverse_mass(direction),
+        stiffness,
+        damping,
+        max_force,
+        dt,
+    );
+
+    // The stiffness doesn't act on the perpendicular motion, so it's only damped
+    let perpendicular = displacement - direction * direction.dot(displacement);
+    let perpendicular_direction = perpendicular.normalize_or_zero();
+    let across = compute_correction(
+        0.0,
+        perpendicular.length(),
+        inverse_mass(perpendicular_direction),
+        0.0,
+        damping,
+        max_force,
+        dt,
+    );
+
+    (along * direction + across * perpendicular_direction).clamp_length_max(max_force * dt.powi(2))
+}
+
+/// Computes the magnitude of a soft correction along a direction using XPBD with damping.
+///
+/// `error` is the distance to the target along the direction, `displacement` is how much the body
+/// has moved along the direction during the substep, and `w` is the generalized inverse mass.
+/// The result is the Lagrange multiplier update, clamped so that the force doesn't exceed `max_force`.
+fn compute_correction(
+    error: Scalar,
+    displacement: Scalar,
+    w: Scalar,
+    stiffness: Scalar,
+    damping: Scalar,
+    max_force: Scalar,
+    dt: Scalar,
+) -> Scalar {
+    if w <= Scalar::EPSILON {
+        return 0.0;
+    }
+
+    // The standard XPBD update with compliance `1 / stiffness` and damping,
+    // multiplied by `stiffness * dt^2` so that a stiffness of zero can be used for pure damping
+    let numerator = stiffness * dt.powi(2) * error - damping * dt * displacement;
+    let denominator = (stiffness * dt.powi(2) + damping * dt) * w + 1.0;
+    let max_magnitude = max_force * dt.powi(2);
+
+    (numerator / denominator).clamp(-max_magnitude, max_magnitude)
+}
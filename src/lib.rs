@@ -51,19 +51,43 @@
 //! | `3d`                   | Enables 3D physics. Incompatible with `2d`.                                                                                      | Yes (`bevy_xpbd_3d`)    |
 //! | `f32`                  | Enables `f32` precision for physics. Incompatible with `f64`.                                                                    | Yes                     |
 //! | `f64`                  | Enables `f64` precision for physics. Incompatible with `f32`.                                                                    | No                      |
+#![cfg_attr(
+    feature = "2d",
+    doc = "| `collider-from-mesh`   | Allows you to create [`Collider`]s from `Mesh`es.                                                                                | No                      |"
+)]
 #![cfg_attr(
     feature = "3d",
     doc = "| `collider-from-mesh`   | Allows you to create [`Collider`]s from `Mesh`es.                                                                                | Yes                     |"
 )]
+#![cfg_attr(
+    feature = "2d",
+    doc = "| `collider-from-image`  | Allows you to create [`Collider`]s from the alpha channel of `Image`s.                                                           | No                      |"
+)]
 #![cfg_attr(
     feature = "3d",
-    doc = "| `async-collider`       | Allows you to generate [`Collider`]s from mesh handles and scenes.                                                               | Yes                     |"
+    doc = "| `collider-from-image`  | Allows you to create heightfield [`Collider`]s from heightmap `Image`s.                                                          | No                      |"
+)]
+#![cfg_attr(
+    feature = "2d",
+    doc = "| `async-collider`       | Generates [`Collider`]s from mesh and image handles in the background.                                                           | No                      |"
+)]
+#![cfg_attr(
+    feature = "3d",
+    doc = "| `async-collider`       | Generates [`Collider`]s from mesh handles and scenes in the background.                                                          | Yes                     |"
 )]
 //! | `debug-plugin`         | Enables physics debug rendering using the [`PhysicsDebugPlugin`]. The plugin must be added separately.                           | Yes                     |
+//! | `deformable`           | Enables particle-based deformable bodies like cloth and fluids. The `DeformablePlugin` must be added separately.                 | No                      |
+//! | `particle-render`      | Renders physics particles using GPU instancing. The `PhysicsParticleRenderPlugin` must be added separately.                      | No                      |
+//! | `fracture`             | Allows breaking convex bodies into fragments at runtime. The `FracturePlugin` must be added separately.                          | No                      |
+#![cfg_attr(
+    feature = "3d",
+    doc = "| `ragdoll`              | Allows building ragdolls from skinned meshes. The `RagdollPlugin` must be added separately.                                      | No                      |"
+)]
 //! | `enhanced-determinism` | Enables increased determinism.                                                                                                   | No                      |
 //! | `parallel`             | Enables some extra multithreading, which improves performance for larger simulations but can add some overhead for smaller ones. | Yes                     |
 //! | `simd`                 | Enables [SIMD] optimizations.                                                                                                    | No                      |
 //! | `serialize`            | Enables support for serialization and deserialization using Serde.                                                               | No                      |
+//! | `collider-asset`       | Allows loading [`Collider`]s from RON and binary files as `ColliderAsset`s. Enables `serialize`.                                 | No                      |
 //!
 //! [SIMD]: https://en.wikipedia.org/wiki/Single_instruction,_multiple_data
 //!
@@ -116,12 +140,19 @@
 //! - [Movement](RigidBody#movement)
 //!     - [Linear](LinearVelocity) and [angular](AngularVelocity) velocity
 //!     - [Forces](ExternalForce), [torque](ExternalTorque), and [linear](ExternalImpulse) and [angular](ExternalAngularImpulse) impulses
-//! - [Gravity] and [gravity scale](GravityScale)
+//!     - [Custom force laws evaluated every substep](ForceGenerator)
+//! - [Gravity], [gravity scale](GravityScale) and [gravity override](GravityOverride)
+//!     - [Gravity fields](GravityField) and [force effectors](ForceEffector)
 //! - [Mass properties](RigidBody#mass-properties)
 //! - [Linear](LinearDamping) and [angular](AngularDamping) velocity damping
+//! - [Aerodynamic drag and lift](Aerodynamics)
 //! - [Lock translational and rotational axes](LockedAxes)
 //! - [Dominance]
 //! - [Automatic deactivation with sleeping](Sleeping)
+#![cfg_attr(
+    feature = "fracture",
+    doc = "- [Breaking bodies into fragments at runtime](Fracturable)"
+)]
 //!
 //! ### Collision detection
 //!
@@ -131,6 +162,20 @@
 //!     - [Friction] and [restitution](Restitution) (bounciness)
 //!     - [Collision layers](CollisionLayers)
 //!     - [Sensors](Sensor)
+//!     - [Custom shapes](CustomShape)
+#![cfg_attr(
+    feature = "2d",
+    doc = "    - Creating colliders from meshes with [`AsyncCollider`]"
+)]
+#![cfg_attr(
+    feature = "2d",
+    doc = "    - Creating colliders from sprites with [`AsyncImageCollider`]"
+)]
+#![cfg_attr(
+    feature = "2d",
+    doc = "    - [Merged colliders for tilemaps](TileGridCollider)"
+)]
+#![cfg_attr(feature = "3d", doc = "    - [Voxel colliders](VoxelCollider)")]
 #![cfg_attr(
     feature = "3d",
     doc = "    - Creating colliders from meshes with [`AsyncCollider`] and [`AsyncSceneCollider`]"
@@ -149,8 +194,24 @@
 //!     - [Prismatic joint](PrismaticJoint)
 //!     - [Revolute joint](RevoluteJoint)
 //!     - [Spherical joint](SphericalJoint)
+//! - [Rotation motors](RotationMotor)
+//! - [Driving bodies toward a target pose](PoseTarget)
+#![cfg_attr(
+    all(feature = "3d", feature = "ragdoll"),
+    doc = "- [Ragdolls built from skinned meshes](Ragdoll)"
+)]
+//!
+//! Motors for the other joints and articulations are not supported yet, but they will be implemented in a future release.
+//!
+//! ### Deformable bodies
 //!
-//! Joint motors and articulations are not supported yet, but they will be implemented in a future release.
+//! - [Particle-based deformable bodies](deformable)
+//!     - [Ropes](Rope)
+//!     - [Fluids](FluidParticles)
+//!     - [Lightweight physics particles](PhysicsParticles)
+#![cfg_attr(feature = "2d", doc = "    - [Soft blobs](SoftBlob)")]
+#![cfg_attr(feature = "3d", doc = "    - [Cloth](Cloth)")]
+#![cfg_attr(feature = "3d", doc = "    - [Tetrahedral soft bodies](SoftBody)")]
 //!
 //! ### Spatial queries
 //!
@@ -160,6 +221,7 @@
 //!     - [Point projection](spatial_query#point-projection)
 //!     - [Intersection tests](spatial_query#intersection-tests)
 //! - [Spatial query filters](SpatialQueryFilter)
+//! - [Radial explosion impulses](Explosion)
 //! - [The `SpatialQuery` system parameter](SpatialQuery)
 //!
 //! ### Configuration
@@ -493,6 +555,12 @@ pub mod resources;
 pub mod prelude {
     #[cfg(feature = "debug-plugin")]
     pub use crate::plugins::debug::*;
+    #[cfg(feature = "deformable")]
+    pub use crate::plugins::deformable::*;
+    #[cfg(feature = "fracture")]
+    pub use crate::plugins::fracture::*;
+    #[cfg(all(feature = "3d", feature = "ragdoll"))]
+    pub use crate::plugins::ragdoll::*;
     pub use crate::{
         components::*,
         constraints::{joints::*, *},
@@ -503,6 +571,7 @@ pub mod prelude {
                 narrow_phase::NarrowPhaseConfig,
                 *,
             },
+            force_generator::*,
             prepare::*,
             setup::*,
             solver::solve_constraint,
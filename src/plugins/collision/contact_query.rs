@@ -15,7 +15,7 @@
 //! and point projection, see [spatial queries](spatial_query).
 
 use crate::prelude::*;
-use parry::query::{PersistentQueryDispatcher, Unsupported};
+use parry::query::{PersistentQueryDispatcher, QueryDispatcher, Unsupported};
 
 /// An error indicating that a [contact query](contact_query) is not supported for one of the [`Collider`] shapes.
 pub type UnsupportedShape = Unsupported;
@@ -74,43 +74,45 @@ pub fn contact(
     let isometry1 = utils::make_isometry(position1.into(), rotation1);
     let isometry2 = utils::make_isometry(position2.into(), rotation2);
 
-    parry::query::contact(
-        &isometry1,
-        collider1.shape_scaled().0.as_ref(),
-        &isometry2,
-        collider2.shape_scaled().0.as_ref(),
-        prediction_distance,
-    )
-    .map(|contact| {
-        if let Some(contact) = contact {
-            // Transform contact data into local space
-            let point1: Vector = rotation1.inverse().rotate(contact.point1.into());
-            let point2: Vector = rotation2.inverse().rotate(contact.point2.into());
-            let normal1: Vector = rotation1
-                .inverse()
-                .rotate(contact.normal1.into())
-                .normalize();
-            let normal2: Vector = rotation2
-                .inverse()
-                .rotate(contact.normal2.into())
-                .normalize();
+    query_dispatcher()
+        .contact(
+            &isometry1.inv_mul(&isometry2),
+            collider1.shape_scaled().0.as_ref(),
+            collider2.shape_scaled().0.as_ref(),
+            prediction_distance,
+        )
+        .map(|contact| {
+            if let Some(mut contact) = contact {
+                contact.transform_by_mut(&isometry1, &isometry2);
 
-            // Make sure normals are valid
-            if !normal1.is_normalized() || !normal2.is_normalized() {
-                return None;
-            }
+                // Transform contact data into local space
+                let point1: Vector = rotation1.inverse().rotate(contact.point1.into());
+                let point2: Vector = rotation2.inverse().rotate(contact.point2.into());
+                let normal1: Vector = rotation1
+                    .inverse()
+                    .rotate(contact.normal1.into())
+                    .normalize();
+                let normal2: Vector = rotation2
+                    .inverse()
+                    .rotate(contact.normal2.into())
+                    .normalize();
 
-            Some(ContactData {
-                point1,
-                point2,
-                normal1,
-                normal2,
-                penetration: -contact.dist,
-            })
-        } else {
-            None
-        }
-    })
+                // Make sure normals are valid
+                if !normal1.is_normalized() || !normal2.is_normalized() {
+                    return None;
+                }
+
+                Some(ContactData {
+                    point1,
+                    point2,
+                    normal1,
+                    normal2,
+                    penetration: -contact.dist,
+                })
+            } else {
+                None
+            }
+        })
 }
 
 // TODO: Add a persistent version of this that tries to reuse previous contact manifolds
@@ -167,7 +169,7 @@ pub fn contact_manifolds(
 
     // TODO: Reuse manifolds from previous frame to improve performance
     let mut manifolds: Vec<parry::query::ContactManifold<(), ()>> = vec![];
-    let _ = parry::query::DefaultQueryDispatcher.contact_manifolds(
+    let _ = query_dispatcher().contact_manifolds(
         &isometry12,
         collider1.shape_scaled().0.as_ref(),
         collider2.shape_scaled().0.as_ref(),
@@ -196,6 +198,18 @@ pub fn contact_manifolds(
                 return None;
             }
 
+            // Ignore contacts on the internal faces between neighbouring voxels
+            #[cfg(feature = "3d")]
+            if is_internal_voxel_contact(collider1, manifold.subshape1, manifold.local_n1.into())
+                || is_internal_voxel_contact(
+                    collider2,
+                    manifold.subshape2,
+                    manifold.local_n2.into(),
+                )
+            {
+                return None;
+            }
+
             Some(ContactManifold {
                 normal1,
                 normal2,
@@ -215,6 +229,16 @@ pub fn contact_manifolds(
         .collect()
 }
 
+/// Returns true if the collider is a [`VoxelCollider`] and the contact normal on the given voxel
+/// points toward a neighbouring solid voxel.
+#[cfg(feature = "3d")]
+fn is_internal_voxel_contact(collider: &Collider, voxel_index: u32, local_normal: Vector) -> bool {
+    collider
+        .shape_scaled()
+        .as_shape::<VoxelCollider>()
+        .is_some_and(|voxels| voxels.is_internal_normal(voxel_index, local_normal))
+}
+
 /// Information about the closest points between two [`Collider`]s.
 ///
 /// The closest points can be computed using [`closest_points`].
@@ -456,10 +480,9 @@ pub fn intersection_test(
     let isometry1 = utils::make_isometry(position1.into(), rotation1);
     let isometry2 = utils::make_isometry(position2.into(), rotation2);
 
-    parry::query::intersection_test(
-        &isometry1,
+    query_dispatcher().intersection_test(
+        &isometry1.inv_mul(&isometry2),
         collider1.shape_scaled().0.as_ref(),
-        &isometry2,
         collider2.shape_scaled().0.as_ref(),
     )
 }
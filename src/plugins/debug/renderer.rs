@@ -378,7 +378,43 @@ impl<'w, 's> PhysicsDebugRenderer<'w, 's> {
                     color,
                 );
             }
-            TypedShape::Custom(_) => (),
+            TypedShape::Custom(_) => {
+                if let Some(custom) = collider.shape_scaled().as_shape::<CustomShapeWrapper>() {
+                    for [a, b] in custom.shape().debug_lines() {
+                        self.draw_line(
+                            position.0 + rotation.rotate(a),
+                            position.0 + rotation.rotate(b),
+                            color,
+                        );
+                    }
+                    return;
+                }
+                #[cfg(feature = "3d")]
+                if let Some(voxels) = collider.shape_scaled().as_shape::<VoxelCollider>() {
+                    // Only draw voxels on the surface
+                    let is_exposed = |voxel: UVec3| {
+                        let voxel = voxel.as_ivec3();
+                        [IVec3::X, IVec3::Y, IVec3::Z]
+                            .into_iter()
+                            .flat_map(|axis| [voxel + axis, voxel - axis])
+                            .any(|neighbor| {
+                                neighbor.cmplt(IVec3::ZERO).any()
+                                    || !voxels.is_solid(neighbor.as_uvec3())
+                            })
+                    };
+                    for voxel in voxels.solid_voxels().filter(|voxel| is_exposed(*voxel)) {
+                        self.gizmos.cuboid(
+                            Transform::from_scale(voxels.voxel_size().as_f32())
+                                .with_translation(
+                                    (position.0 + rotation.rotate(voxels.voxel_center(voxel)))
+                                        .as_f32(),
+                                )
+                                .with_rotation(rotation.as_f32()),
+                            color,
+                        );
+                    }
+                }
+            }
         }
     }
 
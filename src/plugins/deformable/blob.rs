@@ -0,0 +1,487 @@
+//! 2D soft blobs made of a ring of particles.
+//!
+//! See [`SoftBlob`].
+
+use crate::prelude::*;
+use bevy::{
+    prelude::*,
+    render::{mesh::Indices, primitives::Aabb, render_resource::PrimitiveTopology},
+    sprite::Mesh2dHandle,
+};
+
+/// A 2D soft body made of a closed ring of particles, like a blob of jelly.
+///
+/// The shape of the blob is maintained by a combination of constraints:
+///
+/// - [Distance constraints](DistanceConstraint) between neighboring particles along the ring,
+///   controlled by the [edge compliance](SoftBlob::edge_compliance).
+/// - An area constraint that acts like gas pressure inside of the blob. The target area is
+///   the rest area multiplied by the [pressure](SoftBlob::pressure), so values above `1.0` inflate the blob.
+/// - Optional shape matching that pulls the particles towards the rest shape of the blob, rotated and
+///   translated to best match the current particles. This makes the blob keep its shape like a rigid body
+///   while still being able to squish.
+///
+/// The ring is given in local space, and the initial particle positions are computed from the entity's
+/// `Transform` when the component is added, including its scale. After that, the particles are simulated in world space.
+/// A polygon mesh for rendering can be created using [`SoftBlob::to_mesh`]. If the entity has
+/// a [`Mesh2dHandle`], the mesh is updated to match the particles every frame.
+///
+/// The blob collides with [colliders](Collider) found using the [`SpatialQueryPipeline`],
+/// and particles can be attached to rigid bodies using [`SoftBlob::with_attachment`].
+///
+/// ## Example
+///
+/// ```no_run
+/// use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
+/// use bevy_xpbd_2d::prelude::*;
+///
+/// # #[cfg(feature = "f32")]
+/// fn setup(
+///     mut commands: Commands,
+///     mut meshes: ResMut<Assets<Mesh>>,
+///     mut materials: ResMut<Assets<ColorMaterial>>,
+/// ) {
+///     // A slightly inflated blob of jelly that keeps its round shape
+///     let blob = SoftBlob::circle(50.0, 24)
+///         .with_pressure(1.1)
+///         .with_shape_matching(0.0001);
+///
+///     commands.spawn((
+///         MaterialMesh2dBundle {
+///             mesh: meshes.add(blob.to_mesh()).into(),
+///             material: materials.add(ColorMaterial::from(Color::LIME_GREEN)),
+///             ..default()
+///         },
+///         blob,
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone)]
+pub struct SoftBlob {
+    /// The particles of the blob in counterclockwise order.
+    pub particles: Particles,
+    /// The constraints between neighboring particles.
+    pub edge_constraints: Vec<DistanceConstraint>,
+    /// The compliance (inverse of stiffness) of the edge constraints. The default is `0.0`.
+    pub edge_compliance: Scalar,
+    /// The area of the blob at rest.
+    pub rest_area: Scalar,
+    /// The target area relative to the rest area. The default is `1.0`.
+    pub pressure: Scalar,
+    /// The compliance (inverse of stiffness) of the area constraint. The default is `0.0`.
+    pub area_compliance: Scalar,
+    /// The compliance (inverse of stiffness) of shape matching. The default is `None`,
+    /// which disables shape matching.
+    pub shape_matching_compliance: Option<Scalar>,
+    /// Linear damping applied to the velocities of the particles. The default is `0.1`.
+    pub damping: Scalar,
+    /// The radius of the particles used for collisions. The default is `1.0`.
+    pub particle_radius: Scalar,
+    /// The friction coefficient used for collisions. The default is `0.5`.
+    pub friction: Scalar,
+    /// A [`SpatialQueryFilter`] that determines which colliders the blob collides with.
+    pub filter: SpatialQueryFilter,
+    /// The particles that are attached to rigid bodies.
+    pub attachments: Vec<ParticleAttachment>,
+    /// The offsets of the particles from the centroid in the rest shape.
+    rest_offsets: Vec<Vector>,
+}
+
+impl SoftBlob {
+    /// Creates a circular [`SoftBlob`] with the given radius and number of particles.
+    pub fn circle(radius: Scalar, particle_count: usize) -> Self {
+        let particle_count = particle_count.max(3);
+        Self::from_polygon(
+            (0..particle_count)
+                .map(|i| {
+                    let angle = i as Scalar / particle_count as Scalar * 2.0 * PI;
+                    Vector::new(angle.cos(), angle.sin()) * radius
+                })
+                .collect(),
+        )
+    }
+
+    /// Creates a [`SoftBlob`] from the vertices of a simple polygon with a total mass of `1.0`.
+    ///
+    /// Each vertex becomes a particle. The vertices can be in either clockwise or counterclockwise order.
+    pub fn from_polygon(mut vertices: Vec<Vector>) -> Self {
+        if signed_area(&vertices) < 0.0 {
+            vertices.reverse();
+        }
+
+        let centroid = centroid(&vertices);
+        let rest_offsets = vertices.iter().map(|v| *v - centroid).collect();
+        let rest_area = signed_area(&vertices);
+        let particles = Particles::new(vertices, 1.0);
+
+        let edge_constraints = (0..particles.len())
+            .map(|i| DistanceConstraint::new(&particles, i, (i + 1) % particles.len()))
+            .collect();
+
+        Self {
+            particles,
+            edge_constraints,
+            edge_compliance: 0.0,
+            rest_area,
+            pressure: 1.0,
+            area_compliance: 0.0,
+            shape_matching_compliance: None,
+            damping: 0.1,
+            particle_radius: 1.0,
+            friction: 0.5,
+            filter: SpatialQueryFilter::default(),
+            attachments: vec![],
+            rest_offsets,
+        }
+        .with_mass(1.0)
+    }
+
+    /// Sets the total mass of the blob, distributing it evenly between the particles.
+    pub fn with_mass(mut self, mass: Scalar) -> Self {
+        let count = self.particles.len().max(1) as Scalar;
+        self.particles.set_mass(mass / count);
+        self
+    }
+
+    /// Sets the compliance (inverse of stiffness) of the edge constraints.
+    pub fn with_edge_compliance(mut self, compliance: Scalar) -> Self {
+        self.edge_compliance = compliance;
+        self
+    }
+
+    /// Sets the target area relative to the rest area.
+    pub fn with_pressure(mut self, pressure: Scalar) -> Self {
+        self.pressure = pressure;
+        self
+    }
+
+    /// Sets the compliance (inverse of stiffness) of the area constraint.
+    pub fn with_area_compliance(mut self, compliance: Scalar) -> Self {
+        self.area_compliance = compliance;
+        self
+    }
+
+    /// Enables shape matching with the given compliance (inverse of stiffness).
+    pub fn with_shape_matching(mut self, compliance: Scalar) -> Self {
+        self.shape_matching_compliance = Some(compliance);
+        self
+    }
+
+    /// Sets the linear damping applied to the velocities of the particles.
+    pub fn with_damping(mut self, damping: Scalar) -> Self {
+        self.damping = damping;
+        self
+    }
+
+    /// Sets the radius of the particles used for collisions.
+    pub fn with_particle_radius(mut self, radius: Scalar) -> Self {
+        self.particle_radius = radius;
+        self
+    }
+
+    /// Sets the friction coefficient used for collisions.
+    pub fn with_friction(mut self, friction: Scalar) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    /// Sets the [`SpatialQueryFilter`] that determines which colliders the blob collides with.
+    pub fn with_query_filter(mut self, filter: SpatialQueryFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Pins the given particles in place.
+    pub fn with_pinned_particles(mut self, particles: impl IntoIterator<Item = usize>) -> Self {
+        for particle in particles {
+            self.particles.pin(particle);
+        }
+        self
+    }
+
+    /// Attaches the given particle to the `local_anchor` point on the rigid body `entity`.
+    pub fn with_attachment(
+        mut self,
+        particle: usize,
+        entity: Entity,
+        local_anchor: Vector,
+    ) -> Self {
+        self.particles.pin(particle);
+        self.attachments.push(ParticleAttachment {
+            particle,
+            entity,
+            local_anchor,
+        });
+        self
+    }
+
+    /// Computes the current area of the blob.
+    pub fn area(&self) -> Scalar {
+        signed_area(&self.particles.positions)
+    }
+
+    /// Computes the current centroid of the particles.
+    pub fn centroid(&self) -> Vector {
+        centroid(&self.particles.positions)
+    }
+
+    /// Creates a polygon `Mesh` of the blob as a triangle fan around the centroid.
+    ///
+    /// The mesh is in the local space of the blob, so it should be created
+    /// before the component is added to an entity.
+    pub fn to_mesh(&self) -> Mesh {
+        let centroid = self.centroid();
+        let positions: Vec<Vector> = std::iter::once(centroid)
+            .chain(self.particles.positions.iter().copied())
+            .collect();
+        let count = self.particles.len() as u32;
+
+        // UVs are based on the bounding box of the rest shape
+        let (mins, maxs) = self.particles.aabb(0.0).unwrap_or_default();
+        let size = (maxs - mins).max(Vector::splat(Scalar::EPSILON));
+        let uvs: Vec<[f32; 2]> = positions
+            .iter()
+            .map(|p| {
+                let uv = (*p - mins) / size;
+                [uv.x as f32, 1.0 - uv.y as f32]
+            })
+            .collect();
+
+        let indices = (0..count)
+            .flat_map(|i| [0, i + 1, (i + 1) % count + 1])
+            .collect();
+
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            positions
+                .iter()
+                .map(|p| p.as_f32().extend(0.0))
+                .collect::<Vec<Vec3>>(),
+        );
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![Vec3::Z; positions.len()]);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        mesh.set_indices(Some(Indices::U32(indices)));
+        mesh
+    }
+
+    /// Solves the area constraint for a single substep.
+    fn solve_area(&mut self, delta_secs: Scalar) {
+        let particles = &mut self.particles;
+        let count = particles.len();
+
+        // The gradient of the area with respect to a particle is perpendicular
+        // to the line between its neighbors
+        let gradients: Vec<Vector> = (0..count)
+            .map(|i| {
+                let next = particles.positions[(i + 1) % count];
+                let previous = particles.positions[(i + count - 1) % count];
+                -0.5 * (next - previous).perp()
+            })
+            .collect();
+
+        let c = signed_area(&particles.positions) - self.pressure * self.rest_area;
+
+        // The Lagrange multiplier starts at zero every substep, so only the update is needed
+        let delta_lagrange = compute_lagrange_update(
+            0.0,
+            c,
+            &gradients,
+            &particles.inverse_masses,
+            self.area_compliance,
+            delta_secs,
+        );
+
+        for ((position, gradient), w) in particles
+            .positions
+            .iter_mut()
+            .zip(gradients)
+            .zip(&particles.inverse_masses)
+        {
+            *position += gradient * delta_lagrange * *w;
+        }
+    }
+
+    /// Moves the particles towards the rest shape rotated and translated to best match them.
+    ///
+    /// Each particle is pulled towards its goal position by a constraint with the given compliance,
+    /// so the stiffness doesn't depend on the number of substeps.
+    fn solve_shape_matching(&mut self, compliance: Scalar, delta_secs: Scalar) {
+        let particles = &mut self.particles;
+        let centroid = centroid(&particles.positions);
+
+        // The optimal rotation angle in 2D
+        let (sin, cos) = particles.positions.iter().zip(&self.rest_offsets).fold(
+            (0.0, 0.0),
+            |(sin, cos), (position, rest_offset)| {
+                let offset = *position - centroid;
+                (
+                    sin + rest_offset.perp_dot(offset),
+                    cos + rest_offset.dot(offset),
+                )
+            },
+        );
+        let rotation = Rotation::from_radians(sin.atan2(cos));
+
+        for ((position, rest_offset), w) in particles
+            .positions
+            .iter_mut()
+            .zip(&self.rest_offsets)
+            .zip(&particles.inverse_masses)
+        {
+            if *w == 0.0 {
+                continue;
+            }
+            let offset = centroid + rotation.rotate(*rest_offset) - *position;
+            let distance = offset.length();
+            if distance <= Scalar::EPSILON {
+                continue;
+            }
+
+            // The constraint is the distance to the goal, so its gradient points away from it
+            let gradient = -offset / distance;
+            let delta_lagrange =
+                compute_lagrange_update(0.0, distance, &[gradient], &[*w], compliance, delta_secs);
+            *position += gradient * delta_lagrange * *w;
+        }
+    }
+}
+
+/// Computes the signed area of a polygon. The area is positive if the vertices are in counterclockwise order.
+fn signed_area(vertices: &[Vector]) -> Scalar {
+    let count = vertices.len();
+    0.5 * (0..count)
+        .map(|i| vertices[i].perp_dot(vertices[(i + 1) % count]))
+        .sum::<Scalar>()
+}
+
+/// Computes the average of the given points.
+fn centroid(points: &[Vector]) -> Vector {
+    points.iter().sum::<Vector>() / points.len().max(1) as Scalar
+}
+
+/// Moves the particles of new blobs to world space using their `Transform`.
+pub(crate) fn init_blobs(mut blobs: Query<(&mut SoftBlob, Option<&Transform>), Added<SoftBlob>>) {
+    for (mut blob, transform) in &mut blobs {
+        let Some(transform) = transform else {
+            continue;
+        };
+        let blob = &mut *blob;
+
+        blob.particles.transform(|p| {
+            transform
+                .transform_point(p.as_f32().extend(0.0))
+                .truncate()
+                .adjust_precision()
+        });
+
+        // The rest shape was computed from the polygon, so it needs to include the scale
+        let positions = &blob.particles.positions;
+        let centroid = centroid(positions);
+        blob.rest_area = signed_area(positions);
+        blob.rest_offsets = positions.iter().map(|p| *p - centroid).collect();
+        for constraint in &mut blob.edge_constraints {
+            constraint.reset_rest_length(&blob.particles);
+        }
+    }
+}
+
+/// Simulates the particles of each [`SoftBlob`] for a single substep.
+pub(crate) fn simulate_blobs(
+    mut blobs: Query<&mut SoftBlob>,
+    bodies: Query<(&Position, &Rotation)>,
+    colliders: DeformableColliderQuery,
+    pipeline: Option<Res<SpatialQueryPipeline>>,
+    gravity: Res<Gravity>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    if delta_secs == 0.0 {
+        return;
+    }
+
+    for mut blob in &mut blobs {
+        let blob = &mut *blob;
+
+        blob.particles
+            .integrate(gravity.0, blob.damping, delta_secs);
+        apply_attachments(&mut blob.particles, &blob.attachments, &bodies);
+
+        for constraint in &blob.edge_constraints {
+            constraint.solve(&mut blob.particles, blob.edge_compliance, delta_secs);
+        }
+        blob.solve_area(delta_secs);
+        if let Some(compliance) = blob.shape_matching_compliance {
+            blob.solve_shape_matching(compliance, delta_secs);
+        }
+
+        let particles = &mut blob.particles;
+
+        if let Some((pipeline, aabb)) = pipeline.as_ref().zip(particles.aabb(blob.particle_radius))
+        {
+            let nearby = nearby_colliders(pipeline, &colliders, aabb, &blob.filter);
+            if !nearby.is_empty() {
+                for i in 0..particles.len() {
+                    if particles.inverse_masses[i] == 0.0 {
+                        continue;
+                    }
+                    collide_particle(
+                        &mut particles.positions[i],
+                        particles.previous_positions[i],
+                        blob.particle_radius,
+                        blob.friction,
+                        &nearby,
+                    );
+                }
+            }
+        }
+
+        particles.update_velocities(delta_secs);
+    }
+}
+
+type BlobMeshComponents = (
+    &'static SoftBlob,
+    &'static Mesh2dHandle,
+    Option<&'static GlobalTransform>,
+    Option<&'static mut Aabb>,
+);
+
+/// Writes the positions of the particles of each [`SoftBlob`] into its polygon mesh.
+pub(crate) fn update_blob_meshes(
+    mut blobs: Query<BlobMeshComponents, Changed<SoftBlob>>,
+    meshes: Option<ResMut<Assets<Mesh>>>,
+) {
+    let Some(mut meshes) = meshes else {
+        return;
+    };
+
+    for (blob, mesh_handle, global_transform, aabb) in &mut blobs {
+        let Some(mesh) = meshes.get_mut(&mesh_handle.0) else {
+            continue;
+        };
+
+        let world_to_local = global_transform.map_or(bevy::math::Affine3A::IDENTITY, |transform| {
+            transform.affine().inverse()
+        });
+
+        // The first vertex is the centroid of the triangle fan
+        let positions: Vec<Vec3> = std::iter::once(blob.centroid())
+            .chain(blob.particles.positions.iter().copied())
+            .map(|p| world_to_local.transform_point3(p.as_f32().extend(0.0)))
+            .collect();
+
+        if mesh.count_vertices() != positions.len() {
+            continue;
+        }
+
+        if let Some(mut aabb) = aabb {
+            if let Some(new_aabb) = Aabb::enclosing(&positions) {
+                *aabb = new_aabb;
+            }
+        }
+
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    }
+}
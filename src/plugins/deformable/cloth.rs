@@ -0,0 +1,364 @@
+//! Cloth simulation using particles and distance constraints.
+//!
+//! See [`Cloth`].
+
+use crate::prelude::*;
+use bevy::{prelude::*, render::primitives::Aabb, utils::HashMap};
+
+/// A deformable cloth simulated using XPBD.
+///
+/// The cloth is created from a triangle `Mesh` using [`Cloth::from_mesh`]. Each vertex of the mesh
+/// becomes a particle, and vertices that share the same position, like the ones at UV seams,
+/// share a particle. The particles are connected by two kinds of [distance constraints](DistanceConstraint):
+///
+/// - Stretch constraints along the edges of the triangles, controlled by the
+///   [stretch compliance](Cloth::stretch_compliance).
+/// - Bending constraints between the opposite vertices of adjacent triangles, controlled by the
+///   [bending compliance](Cloth::bending_compliance).
+///
+/// The initial particle positions are computed from the entity's `Transform` when the component is added,
+/// including its scale, and after that, the particles are simulated in world space. If the entity has a `Handle<Mesh>`,
+/// the positions and normals of the mesh are updated to match the particles every frame.
+///
+/// Vertices can be pinned in place using [`Cloth::with_pinned_vertices`], or attached to points on
+/// rigid bodies using [`Cloth::with_attachment`].
+///
+/// The cloth collides with [colliders](Collider) found using the [`SpatialQueryPipeline`].
+/// Each particle is treated as a sphere with a radius equal to the [thickness](Cloth::thickness),
+/// so the cloth doesn't detect collisions between particles, and very thin colliders may pass through it.
+///
+/// ## Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// # #[cfg(feature = "f32")]
+/// fn setup(mut commands: Commands, mut meshes: ResMut<Assets<Mesh>>) {
+///     let mesh = Mesh::from(shape::Plane {
+///         size: 2.0,
+///         subdivisions: 20,
+///     });
+///
+///     // A flag hanging from a pole
+///     let pole = commands
+///         .spawn((RigidBody::Kinematic, Collider::cylinder(3.0, 0.05)))
+///         .id();
+///
+///     let cloth = Cloth::from_mesh(&mesh)
+///         .unwrap()
+///         .with_mass(0.5)
+///         .with_bending_compliance(0.01)
+///         .with_attachment(0, pole, Vec3::new(0.0, 1.5, 0.0))
+///         .with_query_filter(SpatialQueryFilter::default().without_entities([pole]));
+///
+///     commands.spawn((
+///         PbrBundle {
+///             mesh: meshes.add(mesh),
+///             ..default()
+///         },
+///         cloth,
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone)]
+pub struct Cloth {
+    /// The particles of the cloth.
+    pub particles: Particles,
+    /// The constraints along the edges of the triangles.
+    pub stretch_constraints: Vec<DistanceConstraint>,
+    /// The constraints between the opposite vertices of adjacent triangles.
+    pub bending_constraints: Vec<DistanceConstraint>,
+    /// The compliance (inverse of stiffness) of the stretch constraints. The default is `0.0`,
+    /// which means that the cloth can't be stretched.
+    pub stretch_compliance: Scalar,
+    /// The compliance (inverse of stiffness) of the bending constraints. The default is `0.001`.
+    pub bending_compliance: Scalar,
+    /// Linear damping applied to the velocities of the particles. The default is `0.1`.
+    pub damping: Scalar,
+    /// The radius of the particles used for collisions. The default is `0.02`.
+    pub thickness: Scalar,
+    /// The friction coefficient used for collisions. The default is `0.3`.
+    pub friction: Scalar,
+    /// A [`SpatialQueryFilter`] that determines which colliders the cloth collides with.
+    pub filter: SpatialQueryFilter,
+    /// The particles that are attached to rigid bodies.
+    pub attachments: Vec<ParticleAttachment>,
+    /// The particle of each vertex of the mesh.
+    vertex_particles: Vec<usize>,
+    /// The triangles of the mesh as particle indices.
+    triangles: Vec<[usize; 3]>,
+}
+
+impl Cloth {
+    /// Creates a [`Cloth`] from a triangle `Mesh` with a total mass of `1.0`.
+    ///
+    /// Returns `None` if the mesh doesn't have vertex positions of the type `Float32x3`.
+    /// If the mesh has no indices, each group of three consecutive vertices is treated as a triangle.
+    pub fn from_mesh(mesh: &Mesh) -> Option<Self> {
+        let (vertices, mesh_triangles) = mesh_positions_and_triangles(mesh)?;
+
+        // Weld vertices that share the same position into a single particle
+        let mut particle_map: HashMap<[u32; 3], usize> = HashMap::default();
+        let mut positions = vec![];
+        let vertex_particles: Vec<usize> = vertices
+            .iter()
+            .map(|v| {
+                *particle_map
+                    .entry(v.as_f32().to_array().map(f32::to_bits))
+                    .or_insert_with(|| {
+                        positions.push(*v);
+                        positions.len() - 1
+                    })
+            })
+            .collect();
+
+        let triangles: Vec<[usize; 3]> = mesh_triangles
+            .iter()
+            .map(|t| t.map(|vertex| vertex_particles[vertex]))
+            .filter(|[a, b, c]| a != b && b != c && a != c)
+            .collect();
+
+        let particles = Particles::new(positions, 1.0);
+
+        // Each edge is shared by at most two triangles. For each edge, store the vertex
+        // opposite to it in the first triangle, and add a bending constraint when the second is found.
+        let mut edges: HashMap<(usize, usize), usize> = HashMap::default();
+        let mut stretch_constraints = vec![];
+        let mut bending_constraints = vec![];
+
+        for &[a, b, c] in &triangles {
+            for (i1, i2, opposite) in [(a, b, c), (b, c, a), (c, a, b)] {
+                let edge = (i1.min(i2), i1.max(i2));
+                if let Some(&other) = edges.get(&edge) {
+                    if other != opposite {
+                        bending_constraints
+                            .push(DistanceConstraint::new(&particles, opposite, other));
+                    }
+                } else {
+                    edges.insert(edge, opposite);
+                    stretch_constraints.push(DistanceConstraint::new(&particles, i1, i2));
+                }
+            }
+        }
+
+        let cloth = Self {
+            particles,
+            stretch_constraints,
+            bending_constraints,
+            stretch_compliance: 0.0,
+            bending_compliance: 0.001,
+            damping: 0.1,
+            thickness: 0.02,
+            friction: 0.3,
+            filter: SpatialQueryFilter::default(),
+            attachments: vec![],
+            vertex_particles,
+            triangles,
+        };
+
+        Some(cloth.with_mass(1.0))
+    }
+
+    /// Sets the total mass of the cloth, distributing it evenly between the particles.
+    pub fn with_mass(mut self, mass: Scalar) -> Self {
+        let count = self.particles.len().max(1) as Scalar;
+        self.particles.set_mass(mass / count);
+        self
+    }
+
+    /// Sets the compliance (inverse of stiffness) of the stretch constraints.
+    pub fn with_stretch_compliance(mut self, compliance: Scalar) -> Self {
+        self.stretch_compliance = compliance;
+        self
+    }
+
+    /// Sets the compliance (inverse of stiffness) of the bending constraints.
+    pub fn with_bending_compliance(mut self, compliance: Scalar) -> Self {
+        self.bending_compliance = compliance;
+        self
+    }
+
+    /// Sets the linear damping applied to the velocities of the particles.
+    pub fn with_damping(mut self, damping: Scalar) -> Self {
+        self.damping = damping;
+        self
+    }
+
+    /// Sets the radius of the particles used for collisions.
+    pub fn with_thickness(mut self, thickness: Scalar) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    /// Sets the friction coefficient used for collisions.
+    pub fn with_friction(mut self, friction: Scalar) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    /// Sets the [`SpatialQueryFilter`] that determines which colliders the cloth collides with.
+    pub fn with_query_filter(mut self, filter: SpatialQueryFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Pins the particles of the given mesh vertices in place.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a vertex is out of bounds. Use [`Cloth::vertex_particle`] to check
+    /// vertex indices that come from user input.
+    pub fn with_pinned_vertices(mut self, vertices: impl IntoIterator<Item = usize>) -> Self {
+        for vertex in vertices {
+            self.particles.pin(self.vertex_particles[vertex]);
+        }
+        self
+    }
+
+    /// Attaches the particle of the given mesh vertex to the `local_anchor` point
+    /// on the rigid body `entity`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vertex is out of bounds. Use [`Cloth::vertex_particle`] to check
+    /// vertex indices that come from user input.
+    pub fn with_attachment(mut self, vertex: usize, entity: Entity, local_anchor: Vector) -> Self {
+        let particle = self.vertex_particles[vertex];
+        self.particles.pin(particle);
+        self.attachments.push(ParticleAttachment {
+            particle,
+            entity,
+            local_anchor,
+        });
+        self
+    }
+
+    /// Returns the index of the particle of the given mesh vertex.
+    pub fn vertex_particle(&self, vertex: usize) -> Option<usize> {
+        self.vertex_particles.get(vertex).copied()
+    }
+
+    /// Returns the world-space position of the given mesh vertex.
+    pub fn vertex_position(&self, vertex: usize) -> Option<Vector> {
+        self.vertex_particle(vertex)
+            .map(|particle| self.particles.positions[particle])
+    }
+}
+
+/// Moves the particles of new cloths to world space using their `Transform`.
+pub(crate) fn init_cloth(mut cloths: Query<(&mut Cloth, Option<&Transform>), Added<Cloth>>) {
+    for (mut cloth, transform) in &mut cloths {
+        let Some(transform) = transform else {
+            continue;
+        };
+        let cloth = &mut *cloth;
+
+        cloth
+            .particles
+            .transform(|p| transform.transform_point(p.as_f32()).adjust_precision());
+
+        // The rest lengths were computed from the mesh, so they need to include the scale
+        for constraint in cloth
+            .stretch_constraints
+            .iter_mut()
+            .chain(&mut cloth.bending_constraints)
+        {
+            constraint.reset_rest_length(&cloth.particles);
+        }
+    }
+}
+
+/// Simulates the particles of each [`Cloth`] for a single substep.
+pub(crate) fn simulate_cloth(
+    mut cloths: Query<&mut Cloth>,
+    bodies: Query<(&Position, &Rotation)>,
+    colliders: DeformableColliderQuery,
+    pipeline: Option<Res<SpatialQueryPipeline>>,
+    gravity: Res<Gravity>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    if delta_secs == 0.0 {
+        return;
+    }
+
+    for mut cloth in &mut cloths {
+        let cloth = &mut *cloth;
+        let particles = &mut cloth.particles;
+
+        particles.integrate(gravity.0, cloth.damping, delta_secs);
+        apply_attachments(particles, &cloth.attachments, &bodies);
+
+        for constraint in &cloth.stretch_constraints {
+            constraint.solve(particles, cloth.stretch_compliance, delta_secs);
+        }
+        for constraint in &cloth.bending_constraints {
+            constraint.solve(particles, cloth.bending_compliance, delta_secs);
+        }
+
+        if let Some((pipeline, aabb)) = pipeline.as_ref().zip(particles.aabb(cloth.thickness)) {
+            let nearby = nearby_colliders(pipeline, &colliders, aabb, &cloth.filter);
+            if !nearby.is_empty() {
+                for i in 0..particles.len() {
+                    if particles.inverse_masses[i] == 0.0 {
+                        continue;
+                    }
+                    collide_particle(
+                        &mut particles.positions[i],
+                        particles.previous_positions[i],
+                        cloth.thickness,
+                        cloth.friction,
+                        &nearby,
+                    );
+                }
+            }
+        }
+
+        particles.update_velocities(delta_secs);
+    }
+}
+
+type ClothMeshComponents = (
+    &'static Cloth,
+    &'static Handle<Mesh>,
+    Option<&'static GlobalTransform>,
+    Option<&'static mut Aabb>,
+);
+
+/// Writes the positions and normals of the particles of each [`Cloth`] into its mesh.
+pub(crate) fn update_cloth_meshes(
+    mut cloths: Query<ClothMeshComponents, Changed<Cloth>>,
+    meshes: Option<ResMut<Assets<Mesh>>>,
+) {
+    let Some(mut meshes) = meshes else {
+        return;
+    };
+
+    for (cloth, mesh_handle, global_transform, aabb) in &mut cloths {
+        let Some(mesh) = meshes.get_mut(mesh_handle) else {
+            continue;
+        };
+
+        let positions: Vec<Vector> = cloth
+            .vertex_particles
+            .iter()
+            .map(|&particle| cloth.particles.positions[particle])
+            .collect();
+
+        // Smooth normals are computed per particle so that welded vertices share the same normal
+        let normals: Option<Vec<Vector>> =
+            mesh.contains_attribute(Mesh::ATTRIBUTE_NORMAL).then(|| {
+                let particle_normals =
+                    compute_vertex_normals(&cloth.particles.positions, &cloth.triangles);
+                cloth
+                    .vertex_particles
+                    .iter()
+                    .map(|&particle| particle_normals[particle])
+                    .collect()
+            });
+
+        write_mesh_vertices(mesh, &positions, normals.as_deref(), global_transform, aabb);
+    }
+}
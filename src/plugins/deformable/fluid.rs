@@ -0,0 +1,608 @@
+//! Position-based fluids made of particles.
+//!
+//! See [`FluidParticles`] and [`FluidEmitter`].
+
+use super::{map_particles, SpatialHash};
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// The particles of a fluid simulated using Position Based Fluids (PBF).
+///
+/// All fluid particles are stored in this resource, and they are simulated in [`DeformableSet`]
+/// during each substep. The fluid has the following properties:
+///
+/// - A density constraint for each particle that keeps the fluid incompressible, controlled by
+///   the [compliance](FluidParticles::compliance). The density is computed from the particles within
+///   the [smoothing radius](FluidParticles::smoothing_radius), which is four times the particle radius.
+/// - XSPH [viscosity](FluidParticles::viscosity) that makes nearby particles move at similar velocities.
+/// - [Surface tension](FluidParticles::surface_tension) that pulls particles at the surface of the fluid
+///   towards the fluid, making it form droplets.
+///
+/// The particles collide with [colliders](Collider) found using the [`SpatialQueryPipeline`] and exchange
+/// momentum with dynamic [rigid bodies](RigidBody), so fluids can push bodies around and make them float.
+/// Sleeping bodies are not affected by the fluid.
+///
+/// The neighbors of the particles are found using a spatial hash grid, and with the `parallel` feature,
+/// the fluid is simulated in parallel using Bevy's `ComputeTaskPool`.
+///
+/// Particles can be added using [`FluidParticles::spawn`] or a [`FluidEmitter`].
+///
+/// ## Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// # #[cfg(all(feature = "3d", feature = "f32"))]
+/// fn main() {
+///     App::new()
+///         .add_plugins((
+///             DefaultPlugins,
+///             PhysicsPlugins::default(),
+///             DeformablePlugin::default(),
+///         ))
+///         .insert_resource(
+///             FluidParticles::default()
+///                 .with_particle_radius(0.05)
+///                 .with_viscosity(0.02),
+///         )
+///         .add_systems(Startup, setup)
+///         .add_systems(Update, draw_fluid)
+///         .run();
+/// }
+///
+/// # #[cfg(all(feature = "3d", feature = "f32"))]
+/// fn setup(mut commands: Commands) {
+///     // A tap that pours water
+///     commands.spawn((
+///         FluidEmitter::new(500.0, Vec3::NEG_Y * 2.0).with_radius(0.2),
+///         TransformBundle::from_transform(Transform::from_xyz(0.0, 3.0, 0.0)),
+///     ));
+/// }
+///
+/// # #[cfg(all(feature = "3d", feature = "f32"))]
+/// fn draw_fluid(fluid: Res<FluidParticles>, mut gizmos: Gizmos) {
+///     for position in &fluid.particles.positions {
+///         gizmos.sphere(*position, Quat::IDENTITY, fluid.particle_radius, Color::BLUE);
+///     }
+/// }
+/// # #[cfg(not(all(feature = "3d", feature = "f32")))]
+/// # fn main() {}
+/// ```
+#[derive(Resource, Clone)]
+pub struct FluidParticles {
+    /// The particles of the fluid.
+    pub particles: Particles,
+    /// The radius of the particles. The rest distance between neighboring particles is twice the radius.
+    /// The default is `0.1`.
+    ///
+    /// The mass of new particles depends on the radius, so it should be set before any particles are spawned.
+    pub particle_radius: Scalar,
+    /// The density of the fluid at rest. The default is `1000.0`, which is the density of water in kg/m³.
+    pub rest_density: Scalar,
+    /// The compliance (inverse of stiffness) of the density constraints. The default is `0.0`,
+    /// which means that the fluid can't be compressed.
+    pub compliance: Scalar,
+    /// The XSPH viscosity coefficient between `0.0` and `1.0`. The default is `0.01`.
+    pub viscosity: Scalar,
+    /// The strength of the cohesion between particles at the surface of the fluid. The default is `0.01`.
+    pub surface_tension: Scalar,
+    /// The friction coefficient used for collisions. The default is `0.1`.
+    pub friction: Scalar,
+    /// The maximum number of particles. New particles are not spawned once the limit is reached.
+    /// The default is `10000`.
+    pub max_particles: usize,
+    /// A [`SpatialQueryFilter`] that determines which colliders the fluid collides with.
+    pub filter: SpatialQueryFilter,
+}
+
+impl Default for FluidParticles {
+    fn default() -> Self {
+        Self {
+            particles: Particles::default(),
+            particle_radius: 0.1,
+            rest_density: 1000.0,
+            compliance: 0.0,
+            viscosity: 0.01,
+            surface_tension: 0.01,
+            friction: 0.1,
+            max_particles: 10_000,
+            filter: SpatialQueryFilter::default(),
+        }
+    }
+}
+
+impl FluidParticles {
+    /// Sets the radius of the particles.
+    pub fn with_particle_radius(mut self, radius: Scalar) -> Self {
+        self.particle_radius = radius;
+        self
+    }
+
+    /// Sets the density of the fluid at rest.
+    pub fn with_rest_density(mut self, density: Scalar) -> Self {
+        self.rest_density = density;
+        self
+    }
+
+    /// Sets the compliance (inverse of stiffness) of the density constraints.
+    pub fn with_compliance(mut self, compliance: Scalar) -> Self {
+        self.compliance = compliance;
+        self
+    }
+
+    /// Sets the XSPH viscosity coefficient.
+    pub fn with_viscosity(mut self, viscosity: Scalar) -> Self {
+        self.viscosity = viscosity;
+        self
+    }
+
+    /// Sets the strength of the cohesion between particles at the surface of the fluid.
+    pub fn with_surface_tension(mut self, surface_tension: Scalar) -> Self {
+        self.surface_tension = surface_tension;
+        self
+    }
+
+    /// Sets the friction coefficient used for collisions.
+    pub fn with_friction(mut self, friction: Scalar) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    /// Sets the maximum number of particles.
+    pub fn with_max_particles(mut self, max_particles: usize) -> Self {
+        self.max_particles = max_particles;
+        self
+    }
+
+    /// Sets the [`SpatialQueryFilter`] that determines which colliders the fluid collides with.
+    pub fn with_query_filter(mut self, filter: SpatialQueryFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Returns the radius within which particles affect each other.
+    pub fn smoothing_radius(&self) -> Scalar {
+        4.0 * self.particle_radius
+    }
+
+    /// Returns the volume of a single particle at rest. In 2D, this is an area.
+    pub fn particle_volume(&self) -> Scalar {
+        let diameter = 2.0 * self.particle_radius;
+        #[cfg(feature = "2d")]
+        {
+            diameter * diameter
+        }
+        #[cfg(feature = "3d")]
+        {
+            diameter * diameter * diameter
+        }
+    }
+
+    /// Returns the mass of a single particle.
+    pub fn particle_mass(&self) -> Scalar {
+        self.rest_density * self.particle_volume()
+    }
+
+    /// Returns the number of particles.
+    pub fn len(&self) -> usize {
+        self.particles.len()
+    }
+
+    /// Returns `true` if there are no particles.
+    pub fn is_empty(&self) -> bool {
+        self.particles.is_empty()
+    }
+
+    /// Adds a particle at the given world-space position with the given velocity.
+    ///
+    /// Returns `false` if the particle wasn't added because the [maximum](FluidParticles::max_particles)
+    /// number of particles has been reached.
+    pub fn spawn(&mut self, position: Vector, velocity: Vector) -> bool {
+        if self.len() >= self.max_particles {
+            return false;
+        }
+
+        let inverse_mass = self.particle_mass().recip();
+        let particles = &mut self.particles;
+        particles.positions.push(position);
+        particles.previous_positions.push(position);
+        particles.velocities.push(velocity);
+        particles.inverse_masses.push(inverse_mass);
+        true
+    }
+
+    /// Keeps only the particles for which the given function returns `true`.
+    /// The function is given the position and velocity of each particle.
+    pub fn retain(&mut self, mut f: impl FnMut(Vector, Vector) -> bool) {
+        let particles = std::mem::take(&mut self.particles);
+        for i in 0..particles.len() {
+            if f(particles.positions[i], particles.velocities[i]) {
+                self.particles.positions.push(particles.positions[i]);
+                self.particles
+                    .previous_positions
+                    .push(particles.previous_positions[i]);
+                self.particles.velocities.push(particles.velocities[i]);
+                self.particles
+                    .inverse_masses
+                    .push(particles.inverse_masses[i]);
+            }
+        }
+    }
+
+    /// Removes all particles.
+    pub fn clear(&mut self) {
+        self.particles = Particles::default();
+    }
+}
+
+/// Emits particles into [`FluidParticles`] at a constant rate.
+///
+/// The particles are emitted from a disc, or a line segment in 2D, perpendicular to the emission
+/// [velocity](FluidEmitter::velocity) at the entity's `GlobalTransform`.
+#[derive(Component, Reflect, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct FluidEmitter {
+    /// The number of particles emitted per second.
+    pub rate: Scalar,
+    /// The initial velocity of the particles in the local space of the emitter.
+    pub velocity: Vector,
+    /// The radius of the area that the particles are emitted from. The default is `0.0`.
+    pub radius: Scalar,
+    /// The maximum number of particles emitted by this emitter. The default is `None`,
+    /// which means that the emitter never stops.
+    pub limit: Option<usize>,
+    /// The fraction of a particle that is left over from the previous frame.
+    accumulator: Scalar,
+    /// The number of particles emitted so far.
+    emitted: usize,
+}
+
+impl FluidEmitter {
+    /// Creates a new [`FluidEmitter`] that emits `rate` particles per second with the given
+    /// initial velocity in local space.
+    pub fn new(rate: Scalar, velocity: Vector) -> Self {
+        Self {
+            rate,
+            velocity,
+            radius: 0.0,
+            limit: None,
+            accumulator: 0.0,
+            emitted: 0,
+        }
+    }
+
+    /// Sets the radius of the area that the particles are emitted from.
+    pub fn with_radius(mut self, radius: Scalar) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sets the maximum number of particles emitted by this emitter.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Returns the number of particles emitted so far.
+    pub fn emitted(&self) -> usize {
+        self.emitted
+    }
+
+    /// Returns the local-space offset of the particle with the given index from the center
+    /// of the emitter. The offsets are spread evenly over the emission area without randomness.
+    fn offset(&self, index: usize) -> Vector {
+        const GOLDEN_RATIO_FRACT: Scalar = 0.618_034;
+        let fraction = (index as Scalar * GOLDEN_RATIO_FRACT).fract();
+        let direction = self.velocity.try_normalize().unwrap_or(Vector::Y);
+
+        #[cfg(feature = "2d")]
+        {
+            direction.perp() * (2.0 * fraction - 1.0) * self.radius
+        }
+        #[cfg(feature = "3d")]
+        {
+            const GOLDEN_ANGLE: Scalar = 2.399_963;
+            let (u, v) = direction.any_orthonormal_pair();
+            let angle = index as Scalar * GOLDEN_ANGLE;
+            (u * angle.cos() + v * angle.sin()) * fraction.sqrt() * self.radius
+        }
+    }
+}
+
+/// Emits new particles from each [`FluidEmitter`].
+pub(crate) fn emit_fluid(
+    mut emitters: Query<(&mut FluidEmitter, &GlobalTransform)>,
+    mut fluid: ResMut<FluidParticles>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for (mut emitter, transform) in &mut emitters {
+        emitter.accumulator += emitter.rate * delta_secs;
+
+        let (_, rotation, _) = transform.to_scale_rotation_translation();
+        #[cfg(feature = "2d")]
+        let velocity = (rotation * emitter.velocity.as_f32().extend(0.0))
+            .truncate()
+            .adjust_precision();
+        #[cfg(feature = "3d")]
+        let velocity = (rotation * emitter.velocity.as_f32()).adjust_precision();
+
+        while emitter.accumulator >= 1.0 {
+            if emitter.limit.is_some_and(|limit| emitter.emitted >= limit) {
+                emitter.accumulator = 0.0;
+                break;
+            }
+
+            let offset = emitter.offset(emitter.emitted);
+            #[cfg(feature = "2d")]
+            let position = transform
+                .transform_point(offset.as_f32().extend(0.0))
+                .truncate()
+                .adjust_precision();
+            #[cfg(feature = "3d")]
+            let position = transform
+                .transform_point(offset.as_f32())
+                .adjust_precision();
+
+            if !fluid.spawn(position, velocity) {
+                emitter.accumulator = 0.0;
+                break;
+            }
+
+            emitter.accumulator -= 1.0;
+            emitter.emitted += 1;
+        }
+    }
+}
+
+type FluidBodyComponents = (
+    &'static RigidBody,
+    &'static Position,
+    &'static Rotation,
+    &'static CenterOfMass,
+    &'static InverseMass,
+    &'static InverseInertia,
+    Option<&'static LockedAxes>,
+    &'static mut LinearVelocity,
+    &'static mut AngularVelocity,
+);
+
+/// Simulates the [`FluidParticles`] for a single substep.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn simulate_fluid(
+    mut fluid: ResMut<FluidParticles>,
+    mut bodies: Query<FluidBodyComponents, Without<Sleeping>>,
+    colliders: DeformableColliderQuery,
+    collider_parents: Query<&ColliderParent>,
+    pipeline: Option<Res<SpatialQueryPipeline>>,
+    gravity: Res<Gravity>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    if delta_secs == 0.0 || fluid.is_empty() {
+        return;
+    }
+
+    let fluid = &mut *fluid;
+    let h = fluid.smoothing_radius();
+    let volume = fluid.particle_volume();
+    let mass = fluid.particle_mass();
+    let particles = &mut fluid.particles;
+
+    particles.integrate(gravity.0, 0.0, delta_secs);
+
+    let neighbors = find_neighbors(&particles.positions, h);
+
+    // Solve the density constraints. The constraint of each particle is C = ρ / ρ0 - 1,
+    // but only compression is prevented so that the particles don't clump together.
+    let positions = &particles.positions;
+    let compliance = fluid.compliance / delta_secs.powi(2);
+    let lagrange_multipliers = map_particles(positions.len(), |i| {
+        let mut density = poly6(0.0, h);
+        let mut gradient_i = Vector::ZERO;
+        let mut gradient_sum_squared = 0.0;
+
+        for &j in &neighbors[i] {
+            let delta = positions[i] - positions[j];
+            density += poly6(delta.length_squared(), h);
+            let gradient_j = volume * spiky_gradient(delta, h);
+            gradient_i += gradient_j;
+            gradient_sum_squared += gradient_j.length_squared();
+        }
+        gradient_sum_squared += gradient_i.length_squared();
+
+        let c = (density * volume - 1.0).max(0.0);
+        let denominator = gradient_sum_squared / mass + compliance;
+
+        if denominator <= Scalar::EPSILON {
+            0.0
+        } else {
+            -c / denominator
+        }
+    });
+
+    let surface_tension = fluid.surface_tension;
+    let position_deltas = map_particles(positions.len(), |i| {
+        let mut delta_position = Vector::ZERO;
+        for &j in &neighbors[i] {
+            let delta = positions[i] - positions[j];
+            delta_position += (lagrange_multipliers[i] + lagrange_multipliers[j])
+                * volume
+                * spiky_gradient(delta, h)
+                / mass;
+            // Cohesion pulls particles towards the weighted center of their neighbors,
+            // which only has an effect at the surface of the fluid
+            delta_position -= surface_tension * volume * poly6(delta.length_squared(), h) * delta;
+        }
+        delta_position
+    });
+
+    for (position, delta) in particles.positions.iter_mut().zip(position_deltas) {
+        *position += delta;
+    }
+
+    // Collide with colliders and exchange momentum with rigid bodies.
+    // The colliders are queried for each particle so that spread-out fluids don't test
+    // every particle against every collider near the fluid.
+    let radius = fluid.particle_radius;
+    if let Some(pipeline) = &pipeline {
+        let positions = &particles.positions;
+        let previous_positions = &particles.previous_positions;
+        let results = map_particles(positions.len(), |i| {
+            let mut position = positions[i];
+            let mut impulses: Vec<(Entity, Vector, Vector)> = vec![];
+            let aabb = (
+                position - Vector::splat(radius),
+                position + Vector::splat(radius),
+            );
+
+            for (entity, isometry, collider) in
+                nearby_colliders(pipeline, &colliders, aabb, &fluid.filter)
+            {
+                let Some((normal, penetration)) =
+                    particle_contact(position, radius, &isometry, collider)
+                else {
+                    continue;
+                };
+
+                let start = position;
+                position += normal * penetration;
+                apply_friction(
+                    &mut position,
+                    previous_positions[i],
+                    normal,
+                    penetration,
+                    fluid.friction,
+                );
+
+                // The collider receives the opposite of the impulse applied to the particle
+                let impulse = -(position - start) * mass / delta_secs;
+                impulses.push((entity, position - normal * radius, impulse));
+            }
+
+            (position, impulses)
+        });
+
+        let mut impulses = vec![];
+        for (position, (new_position, particle_impulses)) in
+            particles.positions.iter_mut().zip(results)
+        {
+            *position = new_position;
+            impulses.extend(particle_impulses);
+        }
+
+        for (entity, point, impulse) in impulses {
+            let body = collider_parents
+                .get(entity)
+                .map_or(entity, |parent| parent.get());
+            if let Ok((
+                rb,
+                position,
+                rotation,
+                center_of_mass,
+                inv_mass,
+                inv_inertia,
+                locked_axes,
+                mut lin_vel,
+                mut ang_vel,
+            )) = bodies.get_mut(body)
+            {
+                if !rb.is_dynamic() {
+                    continue;
+                }
+
+                let locked_axes =
+                    locked_axes.map_or(LockedAxes::default(), |locked_axes| *locked_axes);
+                let effective_inv_mass = locked_axes.apply_to_vec(Vector::splat(inv_mass.0));
+                let effective_inv_inertia =
+                    locked_axes.apply_to_rotation(inv_inertia.rotated(rotation).0);
+                let lever_arm = point - (position.0 + rotation.rotate(center_of_mass.0));
+
+                lin_vel.0 += impulse * effective_inv_mass;
+                #[cfg(feature = "2d")]
+                {
+                    ang_vel.0 += effective_inv_inertia * lever_arm.perp_dot(impulse);
+                }
+                #[cfg(feature = "3d")]
+                {
+                    ang_vel.0 += effective_inv_inertia * lever_arm.cross(impulse);
+                }
+            }
+        }
+    }
+
+    particles.update_velocities(delta_secs);
+
+    // XSPH viscosity
+    let viscosity = fluid.viscosity;
+    if viscosity > 0.0 {
+        let positions = &particles.positions;
+        let velocities = &particles.velocities;
+        let new_velocities = map_particles(positions.len(), |i| {
+            let mut velocity = velocities[i];
+            for &j in &neighbors[i] {
+                let weight = volume * poly6(positions[i].distance_squared(positions[j]), h);
+                velocity += viscosity * weight * (velocities[j] - velocities[i]);
+            }
+            velocity
+        });
+        particles.velocities = new_velocities;
+    }
+}
+
+/// Finds the other particles within the distance `h` of each particle using a [`SpatialHash`].
+fn find_neighbors(positions: &[Vector], h: Scalar) -> Vec<Vec<usize>> {
+    let grid = SpatialHash::new(positions, h);
+    let h_squared = h * h;
+
+    map_particles(positions.len(), |i| {
+        let position = positions[i];
+        let mut neighbors = vec![];
+        grid.for_each_nearby(position, |j| {
+            if j != i && position.distance_squared(positions[j]) < h_squared {
+                neighbors.push(j);
+            }
+        });
+        neighbors
+    })
+}
+
+/// The poly6 smoothing kernel used for densities.
+fn poly6(distance_squared: Scalar, h: Scalar) -> Scalar {
+    let h_squared = h * h;
+    if distance_squared >= h_squared {
+        return 0.0;
+    }
+    let x = h_squared - distance_squared;
+
+    #[cfg(feature = "2d")]
+    {
+        4.0 / (PI * h.powi(8)) * x * x * x
+    }
+    #[cfg(feature = "3d")]
+    {
+        315.0 / (64.0 * PI * h.powi(9)) * x * x * x
+    }
+}
+
+/// The gradient of the spiky smoothing kernel used for the density constraints.
+fn spiky_gradient(delta: Vector, h: Scalar) -> Vector {
+    let distance = delta.length();
+    if distance >= h || distance <= Scalar::EPSILON {
+        return Vector::ZERO;
+    }
+    let x = h - distance;
+
+    #[cfg(feature = "2d")]
+    let factor = -30.0 / (PI * h.powi(5));
+    #[cfg(feature = "3d")]
+    let factor = -45.0 / (PI * h.powi(6));
+
+    delta / distance * factor * x * x
+}
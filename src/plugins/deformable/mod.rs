@@ -0,0 +1,690 @@
+//! Deformable bodies and fluids simulated using particles, like ropes, cloth and soft bodies.
+//!
+//! Unlike [rigid bodies](RigidBody), deformable bodies are made of point masses called *particles*
+//! that are held together by XPBD [constraints]. The particles are stored in a [`Particles`] buffer
+//! owned by the deformable body's component, and they are simulated in [`DeformableSet`]
+//! at the end of each substep.
+//!
+//! Deformable bodies collide with [colliders](Collider) using the [`SpatialQueryPipeline`], and individual
+//! particles can be attached to rigid bodies using [`ParticleAttachment`]s. For most deformable bodies,
+//! the coupling is one-way: rigid bodies push and drag deformable bodies, but deformable bodies don't
+//! apply forces back to the rigid bodies. [Fluids](FluidParticles) are the exception, as they exchange
+//! momentum with dynamic rigid bodies in both directions, so they can push bodies around and make them float.
+//!
+//! For large numbers of small objects like debris and sand, the module also provides
+//! [`PhysicsParticles`], which are compact buffers of lightweight point masses that collide with colliders
+//! and each other.
+//!
+//! The [`DeformablePlugin`] isn't included in the [`PhysicsPlugins`], so it must be added separately
+//! after them when the `deformable` feature is enabled.
+
+#[cfg(feature = "2d")]
+pub mod blob;
+#[cfg(feature = "3d")]
+pub mod cloth;
+pub mod fluid;
+pub mod physics_particle;
+pub mod rope;
+#[cfg(feature = "3d")]
+pub mod soft_body;
+
+#[cfg(feature = "2d")]
+pub use blob::*;
+#[cfg(feature = "3d")]
+pub use cloth::*;
+pub use fluid::*;
+pub use physics_particle::*;
+pub use rope::*;
+#[cfg(feature = "3d")]
+pub use soft_body::*;
+
+use crate::{prelude::*, utils::make_isometry};
+#[cfg(feature = "3d")]
+use bevy::render::{
+    mesh::{Indices, VertexAttributeValues},
+    primitives::Aabb as RenderAabb,
+};
+#[cfg(feature = "parallel")]
+use bevy::tasks::{ComputeTaskPool, ParallelSlice};
+use bevy::{ecs::query::Has, prelude::*, utils::intern::Interned};
+use parry::bounding_volume::Aabb;
+
+/// Simulates deformable bodies like cloth and writes their deformed shapes back
+/// into their meshes for rendering.
+///
+/// The deformable bodies are simulated in [`DeformableSet`], which runs in the [`SubstepSchedule`]
+/// after [`SubstepSet::ApplyTranslation`], so that the particles see the up-to-date positions
+/// of rigid bodies. Meshes are updated after [`PhysicsSet::Sync`].
+pub struct DeformablePlugin {
+    schedule: Interned<dyn ScheduleLabel>,
+}
+
+impl DeformablePlugin {
+    /// Creates a [`DeformablePlugin`] with the schedule that is used for running the [`PhysicsSchedule`].
+    ///
+    /// The default schedule is `PostUpdate`.
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+        }
+    }
+}
+
+impl Default for DeformablePlugin {
+    fn default() -> Self {
+        Self::new(PostUpdate)
+    }
+}
+
+impl Plugin for DeformablePlugin {
+    fn build(&self, app: &mut App) {
+        let substeps = app
+            .get_schedule_mut(SubstepSchedule)
+            .expect("add SubstepSchedule first");
+
+        substeps.configure_sets(DeformableSet.after(SubstepSet::ApplyTranslation));
+        substeps.add_systems(
+            (
+                rope::simulate_ropes,
+                fluid::simulate_fluid,
+                physics_particle::simulate_physics_particles,
+            )
+                .in_set(DeformableSet),
+        );
+
+        #[cfg(feature = "2d")]
+        {
+            substeps.add_systems(blob::simulate_blobs.in_set(DeformableSet));
+
+            app.add_systems(
+                self.schedule,
+                blob::init_blobs
+                    .after(PrepareSet::InitTransforms)
+                    .before(PrepareSet::Finalize),
+            )
+            .add_systems(
+                self.schedule,
+                blob::update_blob_meshes.after(PhysicsSet::Sync),
+            );
+        }
+
+        #[cfg(feature = "3d")]
+        {
+            substeps.add_systems(
+                (cloth::simulate_cloth, soft_body::simulate_soft_bodies).in_set(DeformableSet),
+            );
+
+            app.add_systems(
+                self.schedule,
+                (cloth::init_cloth, soft_body::init_soft_bodies)
+                    .after(PrepareSet::InitTransforms)
+                    .before(PrepareSet::Finalize),
+            )
+            .add_systems(
+                self.schedule,
+                (
+                    cloth::update_cloth_meshes,
+                    soft_body::update_soft_body_meshes,
+                )
+                    .chain()
+                    .after(PhysicsSet::Sync),
+            );
+        }
+
+        app.add_systems(
+            self.schedule,
+            rope::init_ropes
+                .after(PrepareSet::InitTransforms)
+                .before(PrepareSet::Finalize),
+        );
+
+        app.init_resource::<FluidParticles>().add_systems(
+            PhysicsSchedule,
+            fluid::emit_fluid.before(PhysicsStepSet::Substeps),
+        );
+    }
+}
+
+/// A system set for simulating deformable bodies. The set runs in the [`SubstepSchedule`]
+/// after [`SubstepSet::ApplyTranslation`].
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DeformableSet;
+
+/// The state of the particles of a deformable body.
+///
+/// All of the buffers have the same length, and the particle at a given index
+/// has its data at the same index in each buffer. The positions are in world space.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Particles {
+    /// The current positions of the particles.
+    pub positions: Vec<Vector>,
+    /// The positions of the particles at the start of the current substep.
+    pub previous_positions: Vec<Vector>,
+    /// The velocities of the particles.
+    pub velocities: Vec<Vector>,
+    /// The inverse masses of the particles. Particles with an inverse mass of zero are
+    /// pinned in place, and they can only be moved by [attachments](ParticleAttachment).
+    pub inverse_masses: Vec<Scalar>,
+}
+
+impl Particles {
+    /// Creates new particles at the given positions. Each particle has the given `mass`.
+    pub fn new(positions: Vec<Vector>, mass: Scalar) -> Self {
+        let inverse_mass = if mass > 0.0 { mass.recip() } else { 0.0 };
+        Self {
+            previous_positions: positions.clone(),
+            velocities: vec![Vector::ZERO; positions.len()],
+            inverse_masses: vec![inverse_mass; positions.len()],
+            positions,
+        }
+    }
+
+    /// Returns the number of particles.
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns true if there are no particles.
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Sets the mass of every particle that isn't pinned.
+    pub fn set_mass(&mut self, mass: Scalar) {
+        let inverse_mass = if mass > 0.0 { mass.recip() } else { 0.0 };
+        for w in self.inverse_masses.iter_mut().filter(|w| **w != 0.0) {
+            *w = inverse_mass;
+        }
+    }
+
+    /// Pins the particle at the given index in place by setting its inverse mass to zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn pin(&mut self, index: usize) {
+        self.inverse_masses[index] = 0.0;
+        self.velocities[index] = Vector::ZERO;
+    }
+
+    /// Moves every particle using the given transformation function, resetting the velocities.
+    pub fn transform(&mut self, f: impl Fn(Vector) -> Vector) {
+        for (position, previous_position) in self
+            .positions
+            .iter_mut()
+            .zip(self.previous_positions.iter_mut())
+        {
+            *position = f(*position);
+            *previous_position = *position;
+        }
+        self.velocities.fill(Vector::ZERO);
+    }
+
+    /// Applies damping and the given `acceleration` to the velocities of the particles that aren't pinned,
+    /// and predicts their new positions.
+    pub fn integrate(&mut self, acceleration: Vector, damping: Scalar, delta_secs: Scalar) {
+        let damping_factor = 1.0 / (1.0 + delta_secs * damping);
+
+        for i in 0..self.len() {
+            self.previous_positions[i] = self.positions[i];
+
+            if self.inverse_masses[i] == 0.0 {
+                continue;
+            }
+
+            self.velocities[i] = (self.velocities[i] + acceleration * delta_secs) * damping_factor;
+            self.positions[i] += self.velocities[i] * delta_secs;
+        }
+    }
+
+    /// Computes the velocities of the particles based on how much they moved during the substep.
+    pub fn update_velocities(&mut self, delta_secs: Scalar) {
+        if delta_secs <= Scalar::EPSILON {
+            return;
+        }
+        for i in 0..self.len() {
+            self.velocities[i] = (self.positions[i] - self.previous_positions[i]) / delta_secs;
+        }
+    }
+
+    /// Computes the axis-aligned bounding box of the particles, expanded by the given `margin`.
+    pub fn aabb(&self, margin: Scalar) -> Option<(Vector, Vector)> {
+        let first = *self.positions.first()?;
+        let (mins, maxs) = self
+            .positions
+            .iter()
+            .fold((first, first), |(mins, maxs), p| {
+                (mins.min(*p), maxs.max(*p))
+            });
+        Some((mins - Vector::splat(margin), maxs + Vector::splat(margin)))
+    }
+}
+
+/// An XPBD constraint that keeps two particles at a given distance from each other.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct DistanceConstraint {
+    /// The indices of the constrained particles.
+    pub particles: [usize; 2],
+    /// The distance that the constraint tries to maintain between the particles.
+    pub rest_length: Scalar,
+}
+
+impl DistanceConstraint {
+    /// Creates a new [`DistanceConstraint`] between two particles, using their current distance
+    /// as the rest length.
+    pub fn new(particles: &Particles, particle1: usize, particle2: usize) -> Self {
+        Self {
+            particles: [particle1, particle2],
+            rest_length: particles.positions[particle1].distance(particles.positions[particle2]),
+        }
+    }
+
+    /// Sets the rest length to the current distance between the particles.
+    pub fn reset_rest_length(&mut self, particles: &Particles) {
+        let [i1, i2] = self.particles;
+        self.rest_length = particles.positions[i1].distance(particles.positions[i2]);
+    }
+
+    /// Solves the constraint for a single substep with the given compliance (inverse of stiffness).
+    pub fn solve(&self, particles: &mut Particles, compliance: Scalar, delta_secs: Scalar) {
+        let [i1, i2] = self.particles;
+        let w1 = particles.inverse_masses[i1];
+        let w2 = particles.inverse_masses[i2];
+
+        let delta = particles.positions[i1] - particles.positions[i2];
+        let length = delta.length();
+
+        if length <= Scalar::EPSILON {
+            return;
+        }
+
+        let gradient = delta / length;
+        let c = length - self.rest_length;
+
+        // The Lagrange multiplier starts at zero every substep, so only the update is needed
+        let delta_lagrange = compute_lagrange_update(
+            0.0,
+            c,
+            &[gradient, -gradient],
+            &[w1, w2],
+            compliance,
+            delta_secs,
+        );
+
+        particles.positions[i1] += gradient * delta_lagrange * w1;
+        particles.positions[i2] -= gradient * delta_lagrange * w2;
+    }
+}
+
+/// Attaches a particle of a deformable body to a point on a rigid body.
+///
+/// The particle follows the attachment point exactly, but it doesn't affect the motion of the body.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParticleAttachment {
+    /// The index of the attached particle.
+    pub particle: usize,
+    /// The entity of the rigid body that the particle is attached to.
+    pub entity: Entity,
+    /// The attachment point in the local space of the body.
+    pub local_anchor: Vector,
+}
+
+/// The components of the [colliders](Collider) that deformable bodies collide with.
+pub(crate) type DeformableColliderQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Position,
+        &'static Rotation,
+        &'static Collider,
+        Has<Sensor>,
+    ),
+>;
+
+/// Moves the attached particles to the world-space positions of their attachment points.
+pub(crate) fn apply_attachments(
+    particles: &mut Particles,
+    attachments: &[ParticleAttachment],
+    bodies: &Query<(&Position, &Rotation)>,
+) {
+    for attachment in attachments {
+        let Ok((position, rotation)) = bodies.get(attachment.entity) else {
+            continue;
+        };
+        particles.positions[attachment.particle] =
+            position.0 + rotation.rotate(attachment.local_anchor);
+    }
+}
+
+/// Finds the non-sensor colliders that can intersect the given world-space bounding box.
+///
+/// The candidates are found using the [`SpatialQueryPipeline`], but the current positions and rotations
+/// of the colliders are used for the returned isometries.
+pub(crate) fn nearby_colliders<'a>(
+    pipeline: &SpatialQueryPipeline,
+    colliders: &'a DeformableColliderQuery,
+    (mins, maxs): (Vector, Vector),
+    filter: &SpatialQueryFilter,
+) -> Vec<(Entity, Isometry<Scalar>, &'a Collider)> {
+    let aabb = ColliderAabb(Aabb::new(mins.into(), maxs.into()));
+    let mut nearby = vec![];
+
+    pipeline.aabb_intersections_with_aabb_callback(aabb, |entity| {
+        let Some((_, _, layers)) = pipeline.colliders.get(&entity) else {
+            return true;
+        };
+        if !filter.test(entity, *layers) {
+            return true;
+        }
+        if let Ok((position, rotation, collider, is_sensor)) = colliders.get(entity) {
+            if !is_sensor {
+                nearby.push((entity, make_isometry(*position, *rotation), collider));
+            }
+        }
+        true
+    });
+
+    nearby
+}
+
+/// Pushes a particle with the given `radius` out of the given colliders, and applies friction
+/// by removing some of its tangential movement since the `previous_position`.
+pub(crate) fn collide_particle(
+    position: &mut Vector,
+    previous_position: Vector,
+    radius: Scalar,
+    friction: Scalar,
+    colliders: &[(Entity, Isometry<Scalar>, &Collider)],
+) {
+    for (_, isometry, collider) in colliders {
+        if let Some((normal, penetration)) = particle_contact(*position, radius, isometry, collider)
+        {
+            *position += normal * penetration;
+            apply_friction(position, previous_position, normal, penetration, friction);
+        }
+    }
+}
+
+/// Computes the contact normal pointing out of the collider and the penetration depth
+/// of a particle with the given `radius`, or `None` if the particle isn't touching the collider.
+pub(crate) fn particle_contact(
+    position: Vector,
+    radius: Scalar,
+    isometry: &Isometry<Scalar>,
+    collider: &Collider,
+) -> Option<(Vector, Scalar)> {
+    let projection = collider
+        .shape_scaled()
+        .project_point(isometry, &position.into(), false);
+    let point: Vector = projection.point.into();
+    let delta = position - point;
+    let distance = delta.length();
+
+    if distance <= Scalar::EPSILON {
+        return None;
+    }
+
+    // The normal points out of the collider
+    let (normal, penetration) = if projection.is_inside {
+        (-delta / distance, distance + radius)
+    } else {
+        (delta / distance, radius - distance)
+    };
+
+    (penetration > 0.0).then_some((normal, penetration))
+}
+
+/// Applies position-based friction to a particle that was pushed out of a collider along `normal`
+/// by `penetration`, removing tangential movement since the `previous_position`
+/// proportional to the penetration depth.
+pub(crate) fn apply_friction(
+    position: &mut Vector,
+    previous_position: Vector,
+    normal: Vector,
+    penetration: Scalar,
+    friction: Scalar,
+) {
+    let movement = *position - previous_position;
+    let tangential_movement = movement - normal * movement.dot(normal);
+    let tangential_length = tangential_movement.length();
+
+    if tangential_length > Scalar::EPSILON {
+        let factor = (friction * penetration / tangential_length).min(1.0);
+        *position -= tangential_movement * factor;
+    }
+}
+
+/// Maps the index of each particle to a value, in parallel if the `parallel` feature is enabled.
+///
+/// The particles are mapped serially if the `ComputeTaskPool` hasn't been initialized by the `TaskPoolPlugin`.
+pub(crate) fn map_particles<R: Send + 'static>(
+    count: usize,
+    f: impl Fn(usize) -> R + Send + Sync,
+) -> Vec<R> {
+    #[cfg(feature = "parallel")]
+    if let Some(pool) = ComputeTaskPool::try_get() {
+        let indices: Vec<usize> = (0..count).collect();
+        return indices
+            .par_splat_map(pool, None, |chunk| {
+                chunk.iter().map(|&i| f(i)).collect::<Vec<R>>()
+            })
+            .into_iter()
+            .flatten()
+            .collect();
+    }
+
+    (0..count).map(f).collect()
+}
+
+#[cfg(feature = "2d")]
+type Cell = IVec2;
+#[cfg(feature = "3d")]
+type Cell = IVec3;
+
+/// A spatial hash grid for finding nearby particles.
+///
+/// The grid cells are hashed into a table that is twice as large as the number of particles,
+/// and the particles are sorted by their hashes, so the grid can be rebuilt every substep
+/// without allocating memory for each cell.
+pub(crate) struct SpatialHash {
+    cell_size: Scalar,
+    /// The start index of each hash bucket in `particles`. The last element is the number of particles.
+    bucket_starts: Vec<usize>,
+    /// The indices of the particles sorted by their hashes.
+    particles: Vec<usize>,
+    /// The cells of the particles in the same order as `particles`.
+    cells: Vec<Cell>,
+}
+
+impl SpatialHash {
+    /// Builds a [`SpatialHash`] from the given positions. To find all particles within a given distance,
+    /// the `cell_size` should be at least that distance.
+    pub(crate) fn new(positions: &[Vector], cell_size: Scalar) -> Self {
+        let table_size = 2 * positions.len() + 1;
+        let particle_cells: Vec<Cell> = positions
+            .iter()
+            .map(|position| cell(*position, cell_size))
+            .collect();
+        let hashes: Vec<usize> = particle_cells
+            .iter()
+            .map(|cell| hash_cell(*cell, table_size))
+            .collect();
+
+        // Count the particles in each bucket and compute the end index of each bucket
+        let mut bucket_starts = vec![0; table_size + 1];
+        for &hash in &hashes {
+            bucket_starts[hash] += 1;
+        }
+        let mut end = 0;
+        for start in bucket_starts.iter_mut() {
+            end += *start;
+            *start = end;
+        }
+
+        // Fill the buckets from the end, which leaves each bucket's start index in `bucket_starts`
+        let mut particles = vec![0; positions.len()];
+        let mut cells = vec![Cell::ZERO; positions.len()];
+        for (i, &hash) in hashes.iter().enumerate() {
+            bucket_starts[hash] -= 1;
+            particles[bucket_starts[hash]] = i;
+            cells[bucket_starts[hash]] = particle_cells[i];
+        }
+
+        Self {
+            cell_size,
+            bucket_starts,
+            particles,
+            cells,
+        }
+    }
+
+    /// Calls `f` for each particle in the cell that contains `point` and its neighboring cells.
+    pub(crate) fn for_each_nearby(&self, point: Vector, mut f: impl FnMut(usize)) {
+        let center = cell(point, self.cell_size);
+
+        #[cfg(feature = "2d")]
+        for x in -1..=1 {
+            for y in -1..=1 {
+                self.for_each_in_cell(center + IVec2::new(x, y), &mut f);
+            }
+        }
+        #[cfg(feature = "3d")]
+        for x in -1..=1 {
+            for y in -1..=1 {
+                for z in -1..=1 {
+                    self.for_each_in_cell(center + IVec3::new(x, y, z), &mut f);
+                }
+            }
+        }
+    }
+
+    /// Calls `f` for each particle in the given cell.
+    fn for_each_in_cell(&self, cell: Cell, f: &mut impl FnMut(usize)) {
+        let bucket = hash_cell(cell, self.bucket_starts.len() - 1);
+        let range = self.bucket_starts[bucket]..self.bucket_starts[bucket + 1];
+
+        // Different cells can have the same hash, so the cells of the particles are also checked
+        for (&particle, &particle_cell) in
+            self.particles[range.clone()].iter().zip(&self.cells[range])
+        {
+            if particle_cell == cell {
+                f(particle);
+            }
+        }
+    }
+}
+
+/// Returns the cell of a grid with the given cell size that contains the given point.
+fn cell(point: Vector, cell_size: Scalar) -> Cell {
+    #[cfg(feature = "2d")]
+    {
+        (point / cell_size).floor().as_ivec2()
+    }
+    #[cfg(feature = "3d")]
+    {
+        (point / cell_size).floor().as_ivec3()
+    }
+}
+
+/// Hashes a grid cell into an index in a table of the given size.
+fn hash_cell(cell: Cell, table_size: usize) -> usize {
+    #[cfg(feature = "2d")]
+    let hash = (cell.x as i64).wrapping_mul(92_837_111) ^ (cell.y as i64).wrapping_mul(689_287_499);
+    #[cfg(feature = "3d")]
+    let hash = (cell.x as i64).wrapping_mul(92_837_111)
+        ^ (cell.y as i64).wrapping_mul(689_287_499)
+        ^ (cell.z as i64).wrapping_mul(283_923_481);
+    (hash.unsigned_abs() % table_size as u64) as usize
+}
+
+/// Returns the vertex positions of a `Mesh` and its triangles as vertex indices.
+///
+/// Returns `None` if the mesh doesn't have vertex positions of the type `Float32x3`.
+/// If the mesh has no indices, each group of three consecutive vertices is treated as a triangle.
+#[cfg(feature = "3d")]
+pub(crate) fn mesh_positions_and_triangles(mesh: &Mesh) -> Option<(Vec<Vector>, Vec<[usize; 3]>)> {
+    let Some(VertexAttributeValues::Float32x3(vertices)) = mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        return None;
+    };
+
+    let indices: Vec<usize> = match mesh.indices() {
+        Some(Indices::U16(indices)) => indices.iter().map(|i| *i as usize).collect(),
+        Some(Indices::U32(indices)) => indices.iter().map(|i| *i as usize).collect(),
+        None => (0..vertices.len()).collect(),
+    };
+
+    let positions = vertices
+        .iter()
+        .map(|v| Vec3::from_array(*v).adjust_precision())
+        .collect();
+    let triangles = indices
+        .chunks_exact(3)
+        .filter(|t| t.iter().all(|i| *i < vertices.len()))
+        .map(|t| [t[0], t[1], t[2]])
+        .collect();
+
+    Some((positions, triangles))
+}
+
+/// Computes smooth vertex normals from the area-weighted normals of the triangles around each vertex.
+#[cfg(feature = "3d")]
+pub(crate) fn compute_vertex_normals(
+    positions: &[Vector],
+    triangles: &[[usize; 3]],
+) -> Vec<Vector> {
+    let mut normals = vec![Vector::ZERO; positions.len()];
+
+    for &[a, b, c] in triangles {
+        let normal = (positions[b] - positions[a]).cross(positions[c] - positions[a]);
+        normals[a] += normal;
+        normals[b] += normal;
+        normals[c] += normal;
+    }
+
+    normals
+}
+
+/// Writes world-space vertex positions and optionally normals into a `Mesh`, transforming them
+/// into the local space of the given `GlobalTransform`. The `Aabb` used for frustum culling is also updated
+/// so that the deformed mesh isn't culled incorrectly.
+#[cfg(feature = "3d")]
+pub(crate) fn write_mesh_vertices(
+    mesh: &mut Mesh,
+    positions: &[Vector],
+    normals: Option<&[Vector]>,
+    global_transform: Option<&GlobalTransform>,
+    aabb: Option<Mut<RenderAabb>>,
+) {
+    let world_to_local = global_transform.map_or(bevy::math::Affine3A::IDENTITY, |transform| {
+        transform.affine().inverse()
+    });
+
+    let local_positions: Vec<Vec3> = positions
+        .iter()
+        .map(|p| world_to_local.transform_point3(p.as_f32()))
+        .collect();
+
+    if let Some(normals) = normals {
+        let local_normals: Vec<Vec3> = normals
+            .iter()
+            .map(|n| {
+                world_to_local
+                    .transform_vector3(n.as_f32())
+                    .normalize_or_zero()
+            })
+            .collect();
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, local_normals);
+    }
+
+    if let Some(mut aabb) = aabb {
+        if let Some(new_aabb) = RenderAabb::enclosing(&local_positions) {
+            *aabb = new_aabb;
+        }
+    }
+
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, local_positions);
+}
@@ -0,0 +1,423 @@
+//! Lightweight particles for large numbers of small objects.
+//!
+//! See [`PhysicsParticles`].
+
+#[cfg(feature = "particle-render")]
+mod render;
+
+#[cfg(feature = "particle-render")]
+pub use render::*;
+
+use super::{map_particles, SpatialHash};
+use crate::{prelude::*, utils::make_isometry};
+use bevy::prelude::*;
+use parry::bounding_volume::Aabb;
+
+/// A compact buffer of lightweight point masses with spherical shapes, or circles in 2D.
+///
+/// Physics particles are much cheaper to simulate than [rigid bodies](RigidBody), because they don't
+/// rotate, they only have a radius, and they don't go through the broad phase or narrow phase.
+/// This makes them a good fit for effects that need tens of thousands of small objects,
+/// like debris, sparks and granular materials like sand.
+///
+/// The particles aren't entities. Instead, the state of all particles of a [`PhysicsParticles`] component
+/// is stored in the [`Particles`] buffers and the [radii](PhysicsParticles::radii), and the particles
+/// are simulated directly in those buffers in [`DeformableSet`] during each substep. The positions are
+/// in world space, and the entity's `Transform` doesn't affect them.
+///
+/// Particles can be added using [`PhysicsParticles::spawn`]. Their masses are computed from their radii
+/// and the [density](PhysicsParticles::density).
+///
+/// ## Collisions
+///
+/// Particles collide with [colliders](Collider) found using the [`SpatialQueryPipeline`], and with the other
+/// particles of the same component using a spatial hash grid. The colliders can be filtered using
+/// a [`SpatialQueryFilter`]. The collisions use the [restitution](PhysicsParticles::restitution)
+/// and [friction](PhysicsParticles::friction) of the component.
+///
+/// The coupling with rigid bodies is one-way: particles are pushed by rigid bodies, but they don't apply
+/// forces back to the bodies.
+///
+/// ## Rendering
+///
+/// The particles aren't rendered by default. With the `particle-render` feature and the `PhysicsParticleRenderPlugin`,
+/// the particles of entities that have a mesh are drawn using GPU instancing, so all of the particles
+/// of the entity are drawn in a single draw call. The mesh is scaled by the radius of each particle,
+/// so it should have a radius of `1.0`, and it must have normals. In 3D, the mesh is a `Handle<Mesh>`,
+/// and in 2D, it's a `Mesh2dHandle`. Like other meshes, it also needs visibility components like the ones
+/// in a `SpatialBundle`. The color of the particles can be set using `PhysicsParticleColor`.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// # #[cfg(all(feature = "3d", feature = "f32"))]
+/// fn setup(mut commands: Commands) {
+///     // A pile of sand
+///     let mut sand = PhysicsParticles::default().with_friction(0.8);
+///     for i in 0..10_000 {
+///         let position = Vec3::new((i % 20) as f32, (i / 400) as f32, (i / 20 % 20) as f32) * 0.1;
+///         sand.spawn(position, Vec3::ZERO, 0.05);
+///     }
+///
+///     commands.spawn(sand);
+/// }
+/// ```
+#[derive(Component, Clone)]
+pub struct PhysicsParticles {
+    /// The positions, velocities and inverse masses of the particles.
+    pub particles: Particles,
+    /// The radii of the particles.
+    pub radii: Vec<Scalar>,
+    /// The density used to compute the masses of new particles. The default is `1.0`.
+    pub density: Scalar,
+    /// The coefficient of restitution used for collisions. The default is `0.3`.
+    pub restitution: Scalar,
+    /// The friction coefficient used for collisions. The default is `0.5`.
+    pub friction: Scalar,
+    /// Linear damping applied to the velocities of the particles. The default is `0.0`.
+    pub damping: Scalar,
+    /// A multiplier for the [`Gravity`] applied to the particles. The default is `1.0`.
+    pub gravity_scale: Scalar,
+    /// The maximum number of particles. New particles are not spawned once the limit is reached.
+    /// The default is `100000`.
+    pub max_particles: usize,
+    /// A [`SpatialQueryFilter`] that determines which colliders the particles collide with.
+    pub filter: SpatialQueryFilter,
+}
+
+impl Default for PhysicsParticles {
+    fn default() -> Self {
+        Self {
+            particles: Particles::default(),
+            radii: vec![],
+            density: 1.0,
+            restitution: 0.3,
+            friction: 0.5,
+            damping: 0.0,
+            gravity_scale: 1.0,
+            max_particles: 100_000,
+            filter: SpatialQueryFilter::default(),
+        }
+    }
+}
+
+impl PhysicsParticles {
+    /// Sets the density used to compute the masses of new particles.
+    pub fn with_density(mut self, density: Scalar) -> Self {
+        self.density = density;
+        self
+    }
+
+    /// Sets the coefficient of restitution used for collisions.
+    pub fn with_restitution(mut self, restitution: Scalar) -> Self {
+        self.restitution = restitution;
+        self
+    }
+
+    /// Sets the friction coefficient used for collisions.
+    pub fn with_friction(mut self, friction: Scalar) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    /// Sets the linear damping applied to the velocities of the particles.
+    pub fn with_damping(mut self, damping: Scalar) -> Self {
+        self.damping = damping;
+        self
+    }
+
+    /// Sets the multiplier for the [`Gravity`] applied to the particles.
+    pub fn with_gravity_scale(mut self, gravity_scale: Scalar) -> Self {
+        self.gravity_scale = gravity_scale;
+        self
+    }
+
+    /// Sets the maximum number of particles.
+    pub fn with_max_particles(mut self, max_particles: usize) -> Self {
+        self.max_particles = max_particles;
+        self
+    }
+
+    /// Sets the [`SpatialQueryFilter`] that determines which colliders the particles collide with.
+    pub fn with_query_filter(mut self, filter: SpatialQueryFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Returns the number of particles.
+    pub fn len(&self) -> usize {
+        self.particles.len()
+    }
+
+    /// Returns `true` if there are no particles.
+    pub fn is_empty(&self) -> bool {
+        self.particles.is_empty()
+    }
+
+    /// Adds a particle with the given `radius` at the given world-space position with the given velocity.
+    ///
+    /// Returns `false` if the particle wasn't added because the [maximum](PhysicsParticles::max_particles)
+    /// number of particles has been reached.
+    pub fn spawn(&mut self, position: Vector, velocity: Vector, radius: Scalar) -> bool {
+        if self.len() >= self.max_particles {
+            return false;
+        }
+
+        #[cfg(feature = "2d")]
+        let volume = PI * radius * radius;
+        #[cfg(feature = "3d")]
+        let volume = 4.0 / 3.0 * PI * radius * radius * radius;
+        let mass = self.density * volume;
+
+        let particles = &mut self.particles;
+        particles.positions.push(position);
+        particles.previous_positions.push(position);
+        particles.velocities.push(velocity);
+        particles
+            .inverse_masses
+            .push(if mass > 0.0 { mass.recip() } else { 0.0 });
+        self.radii.push(radius);
+        true
+    }
+
+    /// Keeps only the particles for which the given function returns `true`.
+    /// The function is given the position and velocity of each particle.
+    pub fn retain(&mut self, mut f: impl FnMut(Vector, Vector) -> bool) {
+        let keep: Vec<bool> = self
+            .particles
+            .positions
+            .iter()
+            .zip(&self.particles.velocities)
+            .map(|(position, velocity)| f(*position, *velocity))
+            .collect();
+
+        let particles = &mut self.particles;
+        retain_by_mask(&mut particles.positions, &keep);
+        retain_by_mask(&mut particles.previous_positions, &keep);
+        retain_by_mask(&mut particles.velocities, &keep);
+        retain_by_mask(&mut particles.inverse_masses, &keep);
+        retain_by_mask(&mut self.radii, &keep);
+    }
+
+    /// Removes all particles.
+    pub fn clear(&mut self) {
+        self.particles = Particles::default();
+        self.radii.clear();
+    }
+}
+
+/// Keeps only the values whose index is `true` in `keep`.
+fn retain_by_mask<T>(values: &mut Vec<T>, keep: &[bool]) {
+    // `Vec::retain` visits the values in order
+    let mut keep = keep.iter();
+    values.retain(|_| keep.next().copied().unwrap_or(false));
+}
+
+/// Simulates the particles of each [`PhysicsParticles`] component for a single substep.
+pub(crate) fn simulate_physics_particles(
+    mut particle_buffers: Query<&mut PhysicsParticles>,
+    colliders: DeformableColliderQuery,
+    pipeline: Option<Res<SpatialQueryPipeline>>,
+    gravity: Res<Gravity>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    if delta_secs == 0.0 {
+        return;
+    }
+
+    let restitution_threshold = 2.0 * gravity.0.length() * delta_secs;
+
+    for mut buffer in &mut particle_buffers {
+        if buffer.is_empty() {
+            continue;
+        }
+
+        let buffer = &mut *buffer;
+        buffer
+            .particles
+            .integrate(gravity.0 * buffer.gravity_scale, buffer.damping, delta_secs);
+
+        // The velocities at the start of the substep, used for restitution
+        let initial_velocities = buffer.particles.velocities.clone();
+
+        solve_particle_collisions(buffer);
+
+        let contact_normals = if let Some(pipeline) = &pipeline {
+            solve_collider_collisions(buffer, pipeline, &colliders)
+        } else {
+            vec![None; buffer.len()]
+        };
+
+        // Update velocities and apply restitution
+        let particles = &mut buffer.particles;
+        particles.update_velocities(delta_secs);
+
+        for (i, normal) in contact_normals.into_iter().enumerate() {
+            let Some(normal) = normal else {
+                continue;
+            };
+            let initial_normal_speed = initial_velocities[i].dot(normal);
+            if initial_normal_speed < -restitution_threshold {
+                let normal_speed = particles.velocities[i].dot(normal);
+                particles.velocities[i] +=
+                    normal * (-buffer.restitution * initial_normal_speed - normal_speed);
+            }
+        }
+    }
+}
+
+/// Pushes overlapping particles apart and applies friction between them.
+///
+/// The corrections are computed for all particles in parallel and averaged over the contacts of each particle.
+fn solve_particle_collisions(buffer: &mut PhysicsParticles) {
+    let max_radius = buffer.radii.iter().copied().fold(0.0, Scalar::max);
+
+    if max_radius <= 0.0 {
+        return;
+    }
+
+    let Particles {
+        positions,
+        previous_positions,
+        inverse_masses,
+        ..
+    } = &buffer.particles;
+    let radii = &buffer.radii;
+    let friction = buffer.friction;
+
+    let grid = SpatialHash::new(positions, 2.0 * max_radius);
+
+    let deltas = map_particles(positions.len(), |i| {
+        if inverse_masses[i] == 0.0 {
+            return Vector::ZERO;
+        }
+
+        let position = positions[i];
+        let mut delta = Vector::ZERO;
+        let mut contact_count = 0;
+
+        grid.for_each_nearby(position, |j| {
+            if i == j {
+                return;
+            }
+
+            let offset = position - positions[j];
+            let distance = offset.length();
+            let penetration = radii[i] + radii[j] - distance;
+
+            if penetration <= 0.0 || distance <= Scalar::EPSILON {
+                return;
+            }
+
+            let normal = offset / distance;
+            let weight = inverse_masses[i] / (inverse_masses[i] + inverse_masses[j]);
+            let mut correction = normal * penetration * weight;
+
+            // Position-based friction from the relative tangential movement
+            let relative_movement =
+                (position - previous_positions[i]) - (positions[j] - previous_positions[j]);
+            let tangential_movement = relative_movement - normal * relative_movement.dot(normal);
+            let tangential_length = tangential_movement.length();
+            if tangential_length > Scalar::EPSILON {
+                let factor = (friction * penetration / tangential_length).min(1.0);
+                correction -= tangential_movement * factor * weight;
+            }
+
+            delta += correction;
+            contact_count += 1;
+        });
+
+        if contact_count > 0 {
+            delta / contact_count as Scalar
+        } else {
+            Vector::ZERO
+        }
+    });
+
+    for (position, delta) in buffer.particles.positions.iter_mut().zip(deltas) {
+        *position += delta;
+    }
+}
+
+/// Pushes the particles out of colliders, and returns the last contact normal of each particle.
+fn solve_collider_collisions(
+    buffer: &mut PhysicsParticles,
+    pipeline: &SpatialQueryPipeline,
+    colliders: &DeformableColliderQuery,
+) -> Vec<Option<Vector>> {
+    let Particles {
+        positions,
+        previous_positions,
+        inverse_masses,
+        ..
+    } = &buffer.particles;
+    let radii = &buffer.radii;
+    let friction = buffer.friction;
+    let filter = &buffer.filter;
+
+    let results = map_particles(positions.len(), |i| {
+        let mut position = positions[i];
+        let radius = radii[i];
+        let mut contact_normal = None;
+
+        if inverse_masses[i] == 0.0 {
+            return (position, contact_normal);
+        }
+
+        let aabb = ColliderAabb(Aabb::new(
+            (position - Vector::splat(radius)).into(),
+            (position + Vector::splat(radius)).into(),
+        ));
+
+        pipeline.aabb_intersections_with_aabb_callback(aabb, |entity| {
+            let Some((_, _, layers)) = pipeline.colliders.get(&entity) else {
+                return true;
+            };
+            if !filter.test(entity, *layers) {
+                return true;
+            }
+            let Ok((collider_position, rotation, collider, is_sensor)) = colliders.get(entity)
+            else {
+                return true;
+            };
+            if is_sensor {
+                return true;
+            }
+
+            let isometry = make_isometry(*collider_position, *rotation);
+            if let Some((normal, penetration)) =
+                particle_contact(position, radius, &isometry, collider)
+            {
+                position += normal * penetration;
+                apply_friction(
+                    &mut position,
+                    previous_positions[i],
+                    normal,
+                    penetration,
+                    friction,
+                );
+                contact_normal = Some(normal);
+            }
+            true
+        });
+
+        (position, contact_normal)
+    });
+
+    results
+        .into_iter()
+        .zip(buffer.particles.positions.iter_mut())
+        .map(|((new_position, normal), position)| {
+            *position = new_position;
+            normal
+        })
+        .collect()
+}
@@ -0,0 +1,28 @@
+#import bevy_sprite::mesh2d_view_bindings::view
+
+struct Vertex {
+    @location(0) position: vec3<f32>,
+    @location(8) i_position_radius: vec4<f32>,
+    @location(9) i_color: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+};
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    // The particle positions are in world space, so the transform of the entity is ignored
+    let world_position = vertex.position * vertex.i_position_radius.w + vertex.i_position_radius.xyz;
+
+    var out: VertexOutput;
+    out.clip_position = view.view_proj * vec4<f32>(world_position, 1.0);
+    out.color = vertex.i_color;
+    return out;
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    return in.color;
+}
@@ -0,0 +1,33 @@
+#import bevy_pbr::view_transformations::position_world_to_clip
+
+struct Vertex {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(8) i_position_radius: vec4<f32>,
+    @location(9) i_color: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) normal: vec3<f32>,
+    @location(1) color: vec4<f32>,
+};
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    // The particle positions are in world space, so the transform of the entity is ignored
+    let world_position = vertex.position * vertex.i_position_radius.w + vertex.i_position_radius.xyz;
+
+    var out: VertexOutput;
+    out.clip_position = position_world_to_clip(world_position);
+    out.normal = vertex.normal;
+    out.color = vertex.i_color;
+    return out;
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    // Simple shading with light from above so that the particles don't look flat
+    let light = 0.6 + 0.4 * dot(normalize(in.normal), vec3<f32>(0.0, 1.0, 0.0));
+    return vec4<f32>(in.color.rgb * light, in.color.a);
+}
@@ -0,0 +1,383 @@
+//! Renders [`PhysicsParticles`] using GPU instancing.
+
+use super::PhysicsParticles;
+use crate::prelude::*;
+use bevy::{
+    asset::load_internal_asset,
+    ecs::system::{lifetimeless::*, SystemParamItem},
+    prelude::*,
+    render::{
+        batching::NoAutomaticBatching,
+        mesh::{GpuBufferInfo, MeshVertexBufferLayout},
+        render_asset::RenderAssets,
+        render_phase::{
+            AddRenderCommand, DrawFunctions, PhaseItem, RenderCommand, RenderCommandResult,
+            RenderPhase, SetItemPipeline, TrackedRenderPass,
+        },
+        render_resource::*,
+        renderer::RenderDevice,
+        view::{ExtractedView, NoFrustumCulling, VisibilitySystems},
+        Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+    },
+};
+#[cfg(feature = "2d")]
+use bevy::{
+    core_pipeline::core_2d::Transparent2d,
+    sprite::{
+        Mesh2dPipeline as MeshPipeline, Mesh2dPipelineKey as MeshPipelineKey,
+        RenderMesh2dInstances as RenderMeshInstances, SetMesh2dBindGroup as SetMeshBindGroup,
+        SetMesh2dViewBindGroup as SetMeshViewBindGroup,
+    },
+    utils::FloatOrd,
+};
+#[cfg(feature = "3d")]
+use bevy::{
+    core_pipeline::core_3d::Transparent3d,
+    pbr::{
+        MeshPipeline, MeshPipelineKey, RenderMeshInstances, SetMeshBindGroup, SetMeshViewBindGroup,
+    },
+};
+
+#[cfg(feature = "2d")]
+type Transparent = Transparent2d;
+#[cfg(feature = "3d")]
+type Transparent = Transparent3d;
+
+const PHYSICS_PARTICLE_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(248_509_611_913_480_532_992_418_830_437_069_312_741);
+
+/// The color of rendered [`PhysicsParticles`]. The default is white.
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq)]
+#[reflect(Component)]
+pub struct PhysicsParticleColor(pub Color);
+
+impl Default for PhysicsParticleColor {
+    fn default() -> Self {
+        Self(Color::WHITE)
+    }
+}
+
+/// Draws the [`PhysicsParticles`] of entities that have a mesh using GPU instancing.
+///
+/// The plugin isn't included in the [`PhysicsPlugins`], so it must be added separately when the
+/// `particle-render` feature is enabled. It does nothing if rendering isn't enabled.
+pub struct PhysicsParticleRenderPlugin;
+
+impl Plugin for PhysicsParticleRenderPlugin {
+    fn build(&self, app: &mut App) {
+        if app.get_sub_app(RenderApp).is_err() {
+            return;
+        }
+
+        #[cfg(feature = "2d")]
+        load_internal_asset!(
+            app,
+            PHYSICS_PARTICLE_SHADER_HANDLE,
+            "physics_particle_2d.wgsl",
+            Shader::from_wgsl
+        );
+        #[cfg(feature = "3d")]
+        load_internal_asset!(
+            app,
+            PHYSICS_PARTICLE_SHADER_HANDLE,
+            "physics_particle_3d.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<PhysicsParticleColor>().add_systems(
+            PostUpdate,
+            prepare_particle_meshes.before(VisibilitySystems::CalculateBounds),
+        );
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        // The mesh pipeline only exists if the renderer for meshes has been added
+        if !render_app.world.contains_resource::<MeshPipeline>() {
+            return;
+        }
+
+        render_app
+            .add_render_command::<Transparent, DrawPhysicsParticles>()
+            .init_resource::<PhysicsParticlePipeline>()
+            .init_resource::<SpecializedMeshPipelines<PhysicsParticlePipeline>>()
+            .add_systems(ExtractSchedule, extract_physics_particles)
+            .add_systems(
+                Render,
+                (
+                    queue_physics_particles.in_set(RenderSet::QueueMeshes),
+                    prepare_instance_buffers.in_set(RenderSet::PrepareResources),
+                ),
+            );
+    }
+}
+
+/// Disables frustum culling and automatic batching for the meshes of new [`PhysicsParticles`].
+///
+/// The mesh of the entity isn't drawn at the entity's position, so it can't be culled or batched
+/// based on the entity's `Transform`.
+fn prepare_particle_meshes(
+    mut commands: Commands,
+    particles: Query<Entity, Added<PhysicsParticles>>,
+) {
+    for entity in &particles {
+        commands
+            .entity(entity)
+            .insert((NoFrustumCulling, NoAutomaticBatching));
+    }
+}
+
+/// The world-space position and radius followed by the color of each particle.
+#[derive(Component)]
+struct ParticleInstances(Vec<[f32; 8]>);
+
+/// The GPU buffer containing the [`ParticleInstances`] of an entity.
+#[derive(Component)]
+struct ParticleInstanceBuffer {
+    buffer: Buffer,
+    length: usize,
+}
+
+type ExtractedParticlesQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static PhysicsParticles,
+        Option<&'static PhysicsParticleColor>,
+        &'static ViewVisibility,
+    ),
+>;
+
+#[allow(clippy::unnecessary_cast)]
+fn extract_physics_particles(
+    mut commands: Commands,
+    mut previous_len: Local<usize>,
+    particles: Extract<ExtractedParticlesQuery>,
+) {
+    let mut values = Vec::with_capacity(*previous_len);
+
+    for (entity, particles, color, view_visibility) in &particles {
+        if !view_visibility.get() || particles.is_empty() {
+            continue;
+        }
+
+        let [r, g, b, a] = color.copied().unwrap_or_default().0.as_linear_rgba_f32();
+        let instances = particles
+            .particles
+            .positions
+            .iter()
+            .zip(&particles.radii)
+            .map(|(position, radius)| {
+                let position = position.as_f32();
+                #[cfg(feature = "2d")]
+                let position = position.extend(0.0);
+                [
+                    position.x,
+                    position.y,
+                    position.z,
+                    *radius as f32,
+                    r,
+                    g,
+                    b,
+                    a,
+                ]
+            })
+            .collect();
+        values.push((entity, ParticleInstances(instances)));
+    }
+
+    *previous_len = values.len();
+    commands.insert_or_spawn_batch(values);
+}
+
+fn prepare_instance_buffers(
+    mut commands: Commands,
+    particles: Query<(Entity, &ParticleInstances)>,
+    render_device: Res<RenderDevice>,
+) {
+    for (entity, instances) in &particles {
+        let contents: Vec<u8> = instances
+            .0
+            .iter()
+            .flatten()
+            .flat_map(|value| value.to_le_bytes())
+            .collect();
+        let buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
+            label: Some("physics_particle_instance_buffer"),
+            contents: &contents,
+            usage: BufferUsages::VERTEX | BufferUsages::COPY_DST,
+        });
+        commands.entity(entity).insert(ParticleInstanceBuffer {
+            buffer,
+            length: instances.0.len(),
+        });
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn queue_physics_particles(
+    draw_functions: Res<DrawFunctions<Transparent>>,
+    particle_pipeline: Res<PhysicsParticlePipeline>,
+    msaa: Res<Msaa>,
+    mut pipelines: ResMut<SpecializedMeshPipelines<PhysicsParticlePipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    meshes: Res<RenderAssets<Mesh>>,
+    render_mesh_instances: Res<RenderMeshInstances>,
+    particles: Query<Entity, With<ParticleInstances>>,
+    mut views: Query<(&ExtractedView, &mut RenderPhase<Transparent>)>,
+) {
+    let draw_function = draw_functions.read().id::<DrawPhysicsParticles>();
+    let msaa_key = MeshPipelineKey::from_msaa_samples(msaa.samples());
+
+    for (view, mut transparent_phase) in &mut views {
+        let view_key = msaa_key | MeshPipelineKey::from_hdr(view.hdr);
+        #[cfg(feature = "3d")]
+        let rangefinder = view.rangefinder3d();
+
+        for entity in &particles {
+            let Some(mesh_instance) = render_mesh_instances.get(&entity) else {
+                continue;
+            };
+            let Some(mesh) = meshes.get(mesh_instance.mesh_asset_id) else {
+                continue;
+            };
+            let key = view_key | MeshPipelineKey::from_primitive_topology(mesh.primitive_topology);
+            let Ok(pipeline) =
+                pipelines.specialize(&pipeline_cache, &particle_pipeline, key, &mesh.layout)
+            else {
+                continue;
+            };
+            let translation = mesh_instance.transforms.transform.translation;
+
+            #[cfg(feature = "2d")]
+            transparent_phase.add(Transparent2d {
+                sort_key: FloatOrd(translation.z),
+                entity,
+                pipeline,
+                draw_function,
+                batch_range: 0..1,
+                dynamic_offset: None,
+            });
+            #[cfg(feature = "3d")]
+            transparent_phase.add(Transparent3d {
+                distance: rangefinder.distance_translation(&translation),
+                entity,
+                pipeline,
+                draw_function,
+                batch_range: 0..1,
+                dynamic_offset: None,
+            });
+        }
+    }
+}
+
+/// A mesh pipeline with an additional vertex buffer for the [`ParticleInstances`].
+#[derive(Resource)]
+struct PhysicsParticlePipeline {
+    mesh_pipeline: MeshPipeline,
+}
+
+impl FromWorld for PhysicsParticlePipeline {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            mesh_pipeline: world.resource::<MeshPipeline>().clone(),
+        }
+    }
+}
+
+impl SpecializedMeshPipeline for PhysicsParticlePipeline {
+    type Key = MeshPipelineKey;
+
+    fn specialize(
+        &self,
+        key: Self::Key,
+        layout: &MeshVertexBufferLayout,
+    ) -> Result<RenderPipelineDescriptor, SpecializedMeshPipelineError> {
+        let mut descriptor = self.mesh_pipeline.specialize(key, layout)?;
+
+        // The mesh bindings are in bind group 1, because there is no material bind group
+        descriptor
+            .vertex
+            .shader_defs
+            .push("MESH_BINDGROUP_1".into());
+        descriptor.vertex.shader = PHYSICS_PARTICLE_SHADER_HANDLE;
+
+        // The instance attributes use locations after the ones used by mesh attributes
+        descriptor.vertex.buffers.push(VertexBufferLayout {
+            array_stride: std::mem::size_of::<[f32; 8]>() as u64,
+            step_mode: VertexStepMode::Instance,
+            attributes: vec![
+                VertexAttribute {
+                    format: VertexFormat::Float32x4,
+                    offset: 0,
+                    shader_location: 8,
+                },
+                VertexAttribute {
+                    format: VertexFormat::Float32x4,
+                    offset: VertexFormat::Float32x4.size(),
+                    shader_location: 9,
+                },
+            ],
+        });
+        if let Some(fragment) = descriptor.fragment.as_mut() {
+            fragment.shader = PHYSICS_PARTICLE_SHADER_HANDLE;
+        }
+
+        Ok(descriptor)
+    }
+}
+
+type DrawPhysicsParticles = (
+    SetItemPipeline,
+    SetMeshViewBindGroup<0>,
+    SetMeshBindGroup<1>,
+    DrawMeshInstanced,
+);
+
+/// Draws the mesh of an entity once for each of its particles.
+struct DrawMeshInstanced;
+
+impl<P: PhaseItem> RenderCommand<P> for DrawMeshInstanced {
+    type Param = (SRes<RenderAssets<Mesh>>, SRes<RenderMeshInstances>);
+    type ViewWorldQuery = ();
+    type ItemWorldQuery = Read<ParticleInstanceBuffer>;
+
+    #[inline]
+    fn render<'w>(
+        item: &P,
+        _view: (),
+        instance_buffer: &'w ParticleInstanceBuffer,
+        (meshes, render_mesh_instances): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let Some(mesh_instance) = render_mesh_instances.get(&item.entity()) else {
+            return RenderCommandResult::Failure;
+        };
+        let Some(gpu_mesh) = meshes.into_inner().get(mesh_instance.mesh_asset_id) else {
+            return RenderCommandResult::Failure;
+        };
+
+        pass.set_vertex_buffer(0, gpu_mesh.vertex_buffer.slice(..));
+        pass.set_vertex_buffer(1, instance_buffer.buffer.slice(..));
+
+        let instances = 0..instance_buffer.length as u32;
+        match &gpu_mesh.buffer_info {
+            GpuBufferInfo::Indexed {
+                buffer,
+                index_format,
+                count,
+            } => {
+                pass.set_index_buffer(buffer.slice(..), 0, *index_format);
+                pass.draw_indexed(0..*count, 0, instances);
+            }
+            GpuBufferInfo::NonIndexed => {
+                pass.draw(0..gpu_mesh.vertex_count, instances);
+            }
+        }
+        RenderCommandResult::Success
+    }
+}
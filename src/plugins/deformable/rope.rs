@@ -0,0 +1,513 @@
+//! Ropes made of a chain of particles.
+//!
+//! See [`Rope`].
+
+use crate::prelude::*;
+use bevy::prelude::*;
+use parry::shape::Capsule;
+
+/// A lightweight rope made of a chain of particles simulated using XPBD.
+///
+/// Compared to a chain of rigid bodies connected by [`DistanceJoint`]s, a rope is much cheaper to simulate
+/// and it doesn't get wobbly when it has lots of segments. The particles are connected by two kinds of
+/// [distance constraints](DistanceConstraint):
+///
+/// - Stretch constraints between neighboring particles, controlled by the
+///   [stretch compliance](Rope::stretch_compliance).
+/// - Bending constraints between every other particle, controlled by the
+///   [bending compliance](Rope::bending_compliance).
+///
+/// The points of the rope are given in local space, and the initial particle positions are computed from
+/// the entity's `Transform` when the component is added, including its scale. After that, the particles are
+/// simulated in world space.
+/// The ends of the rope can be attached to rigid bodies using [`Rope::with_start_attachment`] and
+/// [`Rope::with_end_attachment`].
+///
+/// ## Collisions
+///
+/// The rope collides with [colliders](Collider) found using the [`SpatialQueryPipeline`].
+/// Each segment is treated as a capsule with the rope's [radius](Rope::radius), so the rope can
+/// also collide with objects that are smaller than the distance between particles. To prevent
+/// fast-moving particles from passing through thin colliders, the movement of each particle is also
+/// swept using a ray cast.
+///
+/// ## Tearing
+///
+/// Ropes can be made to tear using [`Rope::with_tearing`]. When a segment is stretched beyond the given
+/// ratio of its rest length, its constraints are removed, splitting the rope into two pieces.
+/// The pieces are still simulated using the same component.
+///
+/// ## Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// # #[cfg(all(feature = "3d", feature = "f32"))]
+/// fn setup(mut commands: Commands) {
+///     let anchor = commands
+///         .spawn((RigidBody::Static, Position(Vec3::Y * 5.0)))
+///         .id();
+///     let bob = commands
+///         .spawn((RigidBody::Dynamic, Collider::ball(0.5), Position(Vec3::X * 4.0)))
+///         .id();
+///
+///     // A rope between a static anchor and a ball
+///     commands.spawn(
+///         Rope::new(Vec3::Y * 5.0, Vec3::X * 4.0, 40)
+///             .with_radius(0.05)
+///             .with_start_attachment(anchor, Vec3::ZERO)
+///             .with_end_attachment(bob, Vec3::ZERO)
+///             .with_query_filter(SpatialQueryFilter::default().without_entities([bob]))
+///             .with_tearing(1.5),
+///     );
+/// }
+///
+/// # #[cfg(all(feature = "3d", feature = "f32"))]
+/// fn draw_ropes(ropes: Query<&Rope>, mut gizmos: Gizmos) {
+///     for rope in &ropes {
+///         for (start, end) in rope.segments() {
+///             gizmos.line(start, end, Color::WHITE);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Component, Clone)]
+pub struct Rope {
+    /// The particles of the rope, in order from the start to the end.
+    pub particles: Particles,
+    /// The constraints between neighboring particles.
+    pub stretch_constraints: Vec<DistanceConstraint>,
+    /// The constraints between every other particle.
+    pub bending_constraints: Vec<DistanceConstraint>,
+    /// The compliance (inverse of stiffness) of the stretch constraints. The default is `0.0`,
+    /// which means that the rope can't be stretched.
+    pub stretch_compliance: Scalar,
+    /// The compliance (inverse of stiffness) of the bending constraints. The default is `0.01`.
+    pub bending_compliance: Scalar,
+    /// Linear damping applied to the velocities of the particles. The default is `0.1`.
+    pub damping: Scalar,
+    /// The radius of the rope used for collisions. The default is `0.05`.
+    pub radius: Scalar,
+    /// The friction coefficient used for collisions. The default is `0.3`.
+    pub friction: Scalar,
+    /// The ratio of a segment's length to its rest length at which the segment tears.
+    /// The default is `None`, which means that the rope never tears.
+    pub max_stretch: Option<Scalar>,
+    /// A [`SpatialQueryFilter`] that determines which colliders the rope collides with.
+    pub filter: SpatialQueryFilter,
+    /// The particles that are attached to rigid bodies.
+    pub attachments: Vec<ParticleAttachment>,
+}
+
+impl Rope {
+    /// Creates a straight [`Rope`] between two points with the given number of segments
+    /// and a total mass of `1.0`.
+    pub fn new(start: Vector, end: Vector, segment_count: usize) -> Self {
+        let segment_count = segment_count.max(1);
+        Self::from_points(
+            (0..=segment_count)
+                .map(|i| start.lerp(end, i as Scalar / segment_count as Scalar))
+                .collect(),
+        )
+    }
+
+    /// Creates a [`Rope`] that goes through the given points with a total mass of `1.0`.
+    ///
+    /// Each point becomes a particle, and the distances between consecutive points are used
+    /// as the rest lengths of the segments.
+    pub fn from_points(points: Vec<Vector>) -> Self {
+        let particles = Particles::new(points, 1.0);
+        let count = particles.len();
+
+        let stretch_constraints = (1..count)
+            .map(|i| DistanceConstraint::new(&particles, i - 1, i))
+            .collect();
+        let bending_constraints = (2..count)
+            .map(|i| DistanceConstraint::new(&particles, i - 2, i))
+            .collect();
+
+        Self {
+            particles,
+            stretch_constraints,
+            bending_constraints,
+            stretch_compliance: 0.0,
+            bending_compliance: 0.01,
+            damping: 0.1,
+            radius: 0.05,
+            friction: 0.3,
+            max_stretch: None,
+            filter: SpatialQueryFilter::default(),
+            attachments: vec![],
+        }
+        .with_mass(1.0)
+    }
+
+    /// Sets the total mass of the rope, distributing it evenly between the particles.
+    pub fn with_mass(mut self, mass: Scalar) -> Self {
+        let count = self.particles.len().max(1) as Scalar;
+        self.particles.set_mass(mass / count);
+        self
+    }
+
+    /// Sets the compliance (inverse of stiffness) of the stretch constraints.
+    pub fn with_stretch_compliance(mut self, compliance: Scalar) -> Self {
+        self.stretch_compliance = compliance;
+        self
+    }
+
+    /// Sets the compliance (inverse of stiffness) of the bending constraints.
+    pub fn with_bending_compliance(mut self, compliance: Scalar) -> Self {
+        self.bending_compliance = compliance;
+        self
+    }
+
+    /// Sets the linear damping applied to the velocities of the particles.
+    pub fn with_damping(mut self, damping: Scalar) -> Self {
+        self.damping = damping;
+        self
+    }
+
+    /// Sets the radius of the rope used for collisions.
+    pub fn with_radius(mut self, radius: Scalar) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sets the friction coefficient used for collisions.
+    pub fn with_friction(mut self, friction: Scalar) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    /// Makes the rope tear when a segment is stretched beyond `max_stretch` times its rest length.
+    pub fn with_tearing(mut self, max_stretch: Scalar) -> Self {
+        self.max_stretch = Some(max_stretch);
+        self
+    }
+
+    /// Sets the [`SpatialQueryFilter`] that determines which colliders the rope collides with.
+    pub fn with_query_filter(mut self, filter: SpatialQueryFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Pins the given particles in place.
+    pub fn with_pinned_particles(mut self, particles: impl IntoIterator<Item = usize>) -> Self {
+        for particle in particles {
+            self.particles.pin(particle);
+        }
+        self
+    }
+
+    /// Attaches the given particle to the `local_anchor` point on the rigid body `entity`.
+    pub fn with_attachment(
+        mut self,
+        particle: usize,
+        entity: Entity,
+        local_anchor: Vector,
+    ) -> Self {
+        self.particles.pin(particle);
+        self.attachments.push(ParticleAttachment {
+            particle,
+            entity,
+            local_anchor,
+        });
+        self
+    }
+
+    /// Attaches the start of the rope to the `local_anchor` point on the rigid body `entity`.
+    pub fn with_start_attachment(self, entity: Entity, local_anchor: Vector) -> Self {
+        self.with_attachment(0, entity, local_anchor)
+    }
+
+    /// Attaches the end of the rope to the `local_anchor` point on the rigid body `entity`.
+    pub fn with_end_attachment(self, entity: Entity, local_anchor: Vector) -> Self {
+        let last = self.particles.len().saturating_sub(1);
+        self.with_attachment(last, entity, local_anchor)
+    }
+
+    /// Returns the current positions of the start and end points of each intact segment.
+    pub fn segments(&self) -> impl Iterator<Item = (Vector, Vector)> + '_ {
+        self.stretch_constraints.iter().map(|constraint| {
+            let [i1, i2] = constraint.particles;
+            (self.particles.positions[i1], self.particles.positions[i2])
+        })
+    }
+
+    /// Returns the total rest length of the intact segments.
+    pub fn rest_length(&self) -> Scalar {
+        self.stretch_constraints
+            .iter()
+            .map(|constraint| constraint.rest_length)
+            .sum()
+    }
+
+    /// Returns `true` if the rope has been torn into multiple pieces.
+    pub fn is_torn(&self) -> bool {
+        self.stretch_constraints.len() + 1 < self.particles.len()
+    }
+
+    /// Removes the segments that are stretched too far, along with the bending constraints across them.
+    fn tear(&mut self, max_stretch: Scalar) {
+        let positions = &self.particles.positions;
+        let mut torn_segments = vec![];
+
+        self.stretch_constraints.retain(|constraint| {
+            let [i1, i2] = constraint.particles;
+            let length = positions[i1].distance(positions[i2]);
+            let keep = length <= constraint.rest_length * max_stretch;
+            if !keep {
+                torn_segments.push(i1.min(i2));
+            }
+            keep
+        });
+
+        if !torn_segments.is_empty() {
+            self.bending_constraints.retain(|constraint| {
+                let [i1, i2] = constraint.particles;
+                let (start, end) = (i1.min(i2), i1.max(i2));
+                !torn_segments
+                    .iter()
+                    .any(|&segment| start <= segment && segment < end)
+            });
+        }
+    }
+}
+
+/// Moves the particles of new ropes to world space using their `Transform`.
+pub(crate) fn init_ropes(mut ropes: Query<(&mut Rope, Option<&Transform>), Added<Rope>>) {
+    for (mut rope, transform) in &mut ropes {
+        let Some(transform) = transform else {
+            continue;
+        };
+        let rope = &mut *rope;
+
+        #[cfg(feature = "2d")]
+        rope.particles.transform(|p| {
+            transform
+                .transform_point(p.as_f32().extend(0.0))
+                .truncate()
+                .adjust_precision()
+        });
+        #[cfg(feature = "3d")]
+        rope.particles
+            .transform(|p| transform.transform_point(p.as_f32()).adjust_precision());
+
+        // The segment lengths were computed in local space, so they need to include the scale
+        for constraint in &mut rope.stretch_constraints {
+            constraint.reset_rest_length(&rope.particles);
+        }
+    }
+}
+
+/// Simulates the particles of each [`Rope`] for a single substep.
+pub(crate) fn simulate_ropes(
+    mut ropes: Query<&mut Rope>,
+    bodies: Query<(&Position, &Rotation)>,
+    colliders: DeformableColliderQuery,
+    pipeline: Option<Res<SpatialQueryPipeline>>,
+    gravity: Res<Gravity>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    if delta_secs == 0.0 {
+        return;
+    }
+
+    for mut rope in &mut ropes {
+        let rope = &mut *rope;
+        let particles = &mut rope.particles;
+
+        particles.integrate(gravity.0, rope.damping, delta_secs);
+        apply_attachments(particles, &rope.attachments, &bodies);
+
+        for constraint in &rope.stretch_constraints {
+            constraint.solve(particles, rope.stretch_compliance, delta_secs);
+        }
+        for constraint in &rope.bending_constraints {
+            constraint.solve(particles, rope.bending_compliance, delta_secs);
+        }
+
+        if let Some(max_stretch) = rope.max_stretch {
+            rope.tear(max_stretch);
+        }
+
+        let particles = &mut rope.particles;
+
+        if let Some((pipeline, aabb)) = pipeline.as_ref().zip(swept_aabb(particles, rope.radius)) {
+            let nearby = nearby_colliders(pipeline, &colliders, aabb, &rope.filter);
+            if !nearby.is_empty() {
+                for i in 0..particles.len() {
+                    if particles.inverse_masses[i] != 0.0 {
+                        sweep_particle(
+                            &mut particles.positions[i],
+                            particles.previous_positions[i],
+                            rope.radius,
+                            &nearby,
+                        );
+                    }
+                }
+
+                for constraint in &rope.stretch_constraints {
+                    collide_segment(
+                        particles,
+                        constraint.particles,
+                        rope.radius,
+                        rope.friction,
+                        &nearby,
+                    );
+                }
+
+                // Particles that are no longer connected to any segment after tearing
+                // are treated as spheres
+                let mut connected = vec![false; particles.len()];
+                for constraint in &rope.stretch_constraints {
+                    for i in constraint.particles {
+                        connected[i] = true;
+                    }
+                }
+                for (i, connected) in connected.into_iter().enumerate() {
+                    if connected || particles.inverse_masses[i] == 0.0 {
+                        continue;
+                    }
+                    collide_particle(
+                        &mut particles.positions[i],
+                        particles.previous_positions[i],
+                        rope.radius,
+                        rope.friction,
+                        &nearby,
+                    );
+                }
+            }
+        }
+
+        particles.update_velocities(delta_secs);
+    }
+}
+
+/// Computes a bounding box that contains both the current and previous positions of the particles.
+fn swept_aabb(particles: &Particles, margin: Scalar) -> Option<(Vector, Vector)> {
+    let (mins, maxs) = particles.aabb(margin)?;
+    Some(
+        particles
+            .previous_positions
+            .iter()
+            .fold((mins, maxs), |(mins, maxs), position| {
+                (
+                    mins.min(*position - Vector::splat(margin)),
+                    maxs.max(*position + Vector::splat(margin)),
+                )
+            }),
+    )
+}
+
+/// Casts a ray along the movement of a particle since the `previous_position`, and moves the particle
+/// back to the first hit so that it can't pass through thin colliders.
+fn sweep_particle(
+    position: &mut Vector,
+    previous_position: Vector,
+    radius: Scalar,
+    colliders: &[(Entity, Isometry<Scalar>, &Collider)],
+) {
+    let movement = *position - previous_position;
+    let distance = movement.length();
+
+    // Small movements are handled by the segment collisions
+    if distance <= radius {
+        return;
+    }
+
+    let direction = movement / distance;
+    let ray = parry::query::Ray::new(previous_position.into(), direction.into());
+
+    let first_hit = colliders
+        .iter()
+        .filter_map(|(_, isometry, collider)| {
+            collider
+                .shape_scaled()
+                .cast_ray_and_get_normal(isometry, &ray, distance, true)
+        })
+        // A time of impact of zero means that the particle started inside of the collider
+        .filter(|hit| hit.toi > 0.0)
+        .min_by(|a, b| a.toi.total_cmp(&b.toi));
+
+    if let Some(hit) = first_hit {
+        let normal: Vector = hit.normal.into();
+        *position = previous_position + direction * hit.toi + normal * radius;
+    }
+}
+
+/// Pushes a segment of the rope out of the given colliders by treating it as a capsule.
+///
+/// The correction is distributed between the two particles of the segment based on
+/// where the contact is along the segment and the inverse masses of the particles.
+fn collide_segment(
+    particles: &mut Particles,
+    [i1, i2]: [usize; 2],
+    radius: Scalar,
+    friction: Scalar,
+    colliders: &[(Entity, Isometry<Scalar>, &Collider)],
+) {
+    for (_, isometry, collider) in colliders {
+        let p1 = particles.positions[i1];
+        let p2 = particles.positions[i2];
+        let capsule = Capsule::new(p1.into(), p2.into(), radius);
+
+        let Ok(Some(contact)) = parry::query::contact(
+            &Isometry::identity(),
+            &capsule,
+            isometry,
+            collider.shape_scaled().0.as_ref(),
+            0.0,
+        ) else {
+            continue;
+        };
+
+        if contact.dist >= 0.0 {
+            continue;
+        }
+
+        // The normal points from the segment towards the collider
+        let normal: Vector = contact.normal1.into();
+        let penetration = -contact.dist;
+
+        // The position of the contact along the segment
+        let segment = p2 - p1;
+        let length_squared = segment.length_squared();
+        let t = if length_squared > Scalar::EPSILON {
+            let point: Vector = contact.point1.into();
+            ((point - p1).dot(segment) / length_squared).clamp(0.0, 1.0)
+        } else {
+            0.5
+        };
+
+        let weights = [1.0 - t, t];
+        let inverse_masses = [particles.inverse_masses[i1], particles.inverse_masses[i2]];
+        let sum = weights[0] * weights[0] * inverse_masses[0]
+            + weights[1] * weights[1] * inverse_masses[1];
+
+        if sum <= Scalar::EPSILON {
+            continue;
+        }
+
+        for ((i, weight), inverse_mass) in [i1, i2].into_iter().zip(weights).zip(inverse_masses) {
+            let particle_penetration = penetration * weight * inverse_mass / sum;
+            if particle_penetration <= 0.0 {
+                continue;
+            }
+            particles.positions[i] -= normal * particle_penetration;
+            apply_friction(
+                &mut particles.positions[i],
+                particles.previous_positions[i],
+                -normal,
+                particle_penetration,
+                friction,
+            );
+        }
+    }
+}
@@ -0,0 +1,568 @@
+//! Volumetric soft bodies simulated using tetrahedral meshes.
+//!
+//! See [`SoftBody`].
+
+use crate::prelude::*;
+use bevy::{prelude::*, render::primitives::Aabb, utils::HashMap};
+
+/// A volumetric soft body simulated using XPBD.
+///
+/// The soft body is made of particles connected into a tetrahedral mesh. Each tetrahedron has
+/// two kinds of constraints:
+///
+/// - [Distance constraints](DistanceConstraint) along the edges, controlled by the
+///   [edge compliance](SoftBody::edge_compliance).
+/// - A [volume constraint](VolumeConstraint) that preserves the volume of the tetrahedron,
+///   controlled by the [volume compliance](SoftBody::volume_compliance).
+///
+/// A soft body can be created from an existing tetrahedral mesh using [`SoftBody::new`],
+/// or generated from a closed triangle `Mesh` using [`SoftBody::from_mesh`].
+///
+/// The initial particle positions are computed from the entity's `Transform` when the component is added,
+/// including its scale, and after that, the particles are simulated in world space. A render mesh can be *skinned* to the tetrahedra
+/// using [`SoftBody::with_skinned_mesh`], which makes each vertex follow the tetrahedron that contains it.
+/// If the entity has a `Handle<Mesh>`, the positions and normals of the mesh are updated every frame.
+///
+/// The soft body collides with [colliders](Collider) found using the [`SpatialQueryPipeline`],
+/// and particles can be attached to rigid bodies using [`SoftBody::with_attachment`].
+///
+/// ## Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// # #[cfg(feature = "f32")]
+/// fn setup(mut commands: Commands, mut meshes: ResMut<Assets<Mesh>>) {
+///     let mesh = Mesh::from(shape::UVSphere {
+///         radius: 0.5,
+///         ..default()
+///     });
+///
+///     // A jelly ball with 6 tetrahedral cells along its diameter
+///     let soft_body = SoftBody::from_mesh(&mesh, 6)
+///         .unwrap()
+///         .with_mass(2.0)
+///         .with_edge_compliance(0.001);
+///
+///     commands.spawn((
+///         PbrBundle {
+///             mesh: meshes.add(mesh),
+///             transform: Transform::from_xyz(0.0, 3.0, 0.0),
+///             ..default()
+///         },
+///         soft_body,
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone)]
+pub struct SoftBody {
+    /// The particles of the soft body.
+    pub particles: Particles,
+    /// The constraints along the edges of the tetrahedra.
+    pub edge_constraints: Vec<DistanceConstraint>,
+    /// The constraints that preserve the volumes of the tetrahedra.
+    pub volume_constraints: Vec<VolumeConstraint>,
+    /// The compliance (inverse of stiffness) of the edge constraints. The default is `0.0001`.
+    pub edge_compliance: Scalar,
+    /// The compliance (inverse of stiffness) of the volume constraints. The default is `0.0`,
+    /// which means that the volume is preserved as well as possible.
+    pub volume_compliance: Scalar,
+    /// Linear damping applied to the velocities of the particles. The default is `0.1`.
+    pub damping: Scalar,
+    /// The radius of the particles used for collisions. The default is `0.01`.
+    pub particle_radius: Scalar,
+    /// The friction coefficient used for collisions. The default is `0.5`.
+    pub friction: Scalar,
+    /// A [`SpatialQueryFilter`] that determines which colliders the soft body collides with.
+    pub filter: SpatialQueryFilter,
+    /// The particles that are attached to rigid bodies.
+    pub attachments: Vec<ParticleAttachment>,
+    /// The tetrahedron and barycentric weights of each vertex of the skinned render mesh.
+    skinning: Vec<([usize; 4], [Scalar; 4])>,
+    /// The triangles of the skinned render mesh as vertex indices.
+    render_triangles: Vec<[usize; 3]>,
+}
+
+impl SoftBody {
+    /// Creates a [`SoftBody`] from a tetrahedral mesh with a total mass of `1.0`.
+    ///
+    /// Each tetrahedron is given as four indices into `vertices`.
+    pub fn new(vertices: Vec<Vector>, tetrahedra: Vec<[usize; 4]>) -> Self {
+        let particles = Particles::new(vertices, 1.0);
+
+        let mut edges: Vec<(usize, usize)> = tetrahedra
+            .iter()
+            .flat_map(|&[a, b, c, d]| [(a, b), (a, c), (a, d), (b, c), (b, d), (c, d)])
+            .map(|(i1, i2)| (i1.min(i2), i1.max(i2)))
+            .collect();
+        edges.sort_unstable();
+        edges.dedup();
+
+        let edge_constraints = edges
+            .into_iter()
+            .map(|(i1, i2)| DistanceConstraint::new(&particles, i1, i2))
+            .collect();
+        let volume_constraints = tetrahedra
+            .into_iter()
+            .map(|tetrahedron| VolumeConstraint::new(&particles, tetrahedron))
+            .collect();
+
+        Self {
+            particles,
+            edge_constraints,
+            volume_constraints,
+            edge_compliance: 0.0001,
+            volume_compliance: 0.0,
+            damping: 0.1,
+            particle_radius: 0.01,
+            friction: 0.5,
+            filter: SpatialQueryFilter::default(),
+            attachments: vec![],
+            skinning: vec![],
+            render_triangles: vec![],
+        }
+        .with_mass(1.0)
+    }
+
+    /// Generates a [`SoftBody`] for a closed triangle `Mesh` and skins the mesh to it.
+    ///
+    /// The interior of the mesh is filled with cubic cells, and each cell is split into six tetrahedra.
+    /// `resolution` is the number of cells along the longest axis of the mesh's bounding box.
+    ///
+    /// The triangles of the mesh must be consistently oriented with their normals pointing outwards.
+    /// Returns `None` if the mesh doesn't have vertex positions of the type `Float32x3`,
+    /// or if no cells are inside of the mesh.
+    pub fn from_mesh(mesh: &Mesh, resolution: u32) -> Option<Self> {
+        let (vertices, triangles) = mesh_positions_and_triangles(mesh)?;
+        let (particles, tetrahedra) = tetrahedralize(&vertices, &triangles, resolution)?;
+        Some(Self::new(particles, tetrahedra).with_skinned_mesh(mesh))
+    }
+
+    /// Skins the given render `Mesh` to the tetrahedra, so that the mesh deforms with the soft body.
+    ///
+    /// Each vertex follows the tetrahedron that contains it, or the closest one if it's outside of all of them.
+    /// The mesh must be in the same space as the particles at the time of calling this,
+    /// so this should be called before the component is added to an entity.
+    pub fn with_skinned_mesh(mut self, mesh: &Mesh) -> Self {
+        let Some((vertices, triangles)) = mesh_positions_and_triangles(mesh) else {
+            return self;
+        };
+
+        // Inverse matrices for computing the barycentric coordinates of points in each tetrahedron
+        let positions = &self.particles.positions;
+        let inverse_matrices: Vec<Matrix3> = self
+            .volume_constraints
+            .iter()
+            .map(|constraint| {
+                let [a, b, c, d] = constraint.particles.map(|i| positions[i]);
+                Matrix3::from_cols(b - a, c - a, d - a).inverse()
+            })
+            .collect();
+
+        self.skinning = vertices
+            .iter()
+            .filter_map(|&vertex| {
+                // Find the tetrahedron where the smallest barycentric weight is the largest.
+                // For tetrahedra containing the vertex, all weights are non-negative.
+                self.volume_constraints
+                    .iter()
+                    .zip(&inverse_matrices)
+                    .map(|(constraint, inverse_matrix)| {
+                        let coords =
+                            *inverse_matrix * (vertex - positions[constraint.particles[0]]);
+                        let weights = [
+                            1.0 - coords.x - coords.y - coords.z,
+                            coords.x,
+                            coords.y,
+                            coords.z,
+                        ];
+                        (constraint.particles, weights)
+                    })
+                    .max_by(|(_, w1), (_, w2)| {
+                        let min1 = w1.iter().copied().fold(Scalar::MAX, Scalar::min);
+                        let min2 = w2.iter().copied().fold(Scalar::MAX, Scalar::min);
+                        min1.total_cmp(&min2)
+                    })
+            })
+            .collect();
+        self.render_triangles = if self.skinning.len() == vertices.len() {
+            triangles
+        } else {
+            self.skinning.clear();
+            vec![]
+        };
+
+        self
+    }
+
+    /// Sets the total mass of the soft body, distributing it between the particles
+    /// based on the volumes of the tetrahedra around them.
+    pub fn with_mass(mut self, mass: Scalar) -> Self {
+        let mut particle_volumes = vec![0.0; self.particles.len()];
+        for constraint in &self.volume_constraints {
+            for i in constraint.particles {
+                particle_volumes[i] += constraint.rest_volume.abs() / 4.0;
+            }
+        }
+
+        let total_volume: Scalar = particle_volumes.iter().sum();
+        if total_volume <= Scalar::EPSILON {
+            return self;
+        }
+
+        for (w, volume) in self
+            .particles
+            .inverse_masses
+            .iter_mut()
+            .zip(particle_volumes)
+        {
+            // Pinned particles keep their infinite mass
+            if *w != 0.0 && volume > 0.0 {
+                *w = total_volume / (mass * volume);
+            }
+        }
+        self
+    }
+
+    /// Sets the compliance (inverse of stiffness) of the edge constraints.
+    pub fn with_edge_compliance(mut self, compliance: Scalar) -> Self {
+        self.edge_compliance = compliance;
+        self
+    }
+
+    /// Sets the compliance (inverse of stiffness) of the volume constraints.
+    pub fn with_volume_compliance(mut self, compliance: Scalar) -> Self {
+        self.volume_compliance = compliance;
+        self
+    }
+
+    /// Sets the linear damping applied to the velocities of the particles.
+    pub fn with_damping(mut self, damping: Scalar) -> Self {
+        self.damping = damping;
+        self
+    }
+
+    /// Sets the radius of the particles used for collisions.
+    pub fn with_particle_radius(mut self, radius: Scalar) -> Self {
+        self.particle_radius = radius;
+        self
+    }
+
+    /// Sets the friction coefficient used for collisions.
+    pub fn with_friction(mut self, friction: Scalar) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    /// Sets the [`SpatialQueryFilter`] that determines which colliders the soft body collides with.
+    pub fn with_query_filter(mut self, filter: SpatialQueryFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Pins the given particles in place.
+    pub fn with_pinned_particles(mut self, particles: impl IntoIterator<Item = usize>) -> Self {
+        for particle in particles {
+            self.particles.pin(particle);
+        }
+        self
+    }
+
+    /// Attaches the given particle to the `local_anchor` point on the rigid body `entity`.
+    ///
+    /// [`SoftBody::closest_particle`] can be used for finding the particle closest to a given point.
+    pub fn with_attachment(
+        mut self,
+        particle: usize,
+        entity: Entity,
+        local_anchor: Vector,
+    ) -> Self {
+        self.particles.pin(particle);
+        self.attachments.push(ParticleAttachment {
+            particle,
+            entity,
+            local_anchor,
+        });
+        self
+    }
+
+    /// Returns the index of the particle closest to the given point.
+    pub fn closest_particle(&self, point: Vector) -> Option<usize> {
+        self.particles
+            .positions
+            .iter()
+            .enumerate()
+            .min_by(|(_, p1), (_, p2)| {
+                p1.distance_squared(point)
+                    .total_cmp(&p2.distance_squared(point))
+            })
+            .map(|(i, _)| i)
+    }
+
+    /// Computes the current total volume of the tetrahedra.
+    pub fn volume(&self) -> Scalar {
+        self.volume_constraints
+            .iter()
+            .map(|constraint| constraint.volume(&self.particles).abs())
+            .sum()
+    }
+}
+
+/// An XPBD constraint that preserves the volume of a tetrahedron formed by four particles.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct VolumeConstraint {
+    /// The indices of the particles at the corners of the tetrahedron.
+    pub particles: [usize; 4],
+    /// The signed volume that the constraint tries to maintain.
+    pub rest_volume: Scalar,
+}
+
+impl VolumeConstraint {
+    /// Creates a new [`VolumeConstraint`] for a tetrahedron, using its current volume as the rest volume.
+    pub fn new(particles: &Particles, tetrahedron: [usize; 4]) -> Self {
+        let mut constraint = Self {
+            particles: tetrahedron,
+            rest_volume: 0.0,
+        };
+        constraint.rest_volume = constraint.volume(particles);
+        constraint
+    }
+
+    /// Sets the rest volume to the current volume of the tetrahedron.
+    pub fn reset_rest_volume(&mut self, particles: &Particles) {
+        self.rest_volume = self.volume(particles);
+    }
+
+    /// Computes the current signed volume of the tetrahedron.
+    pub fn volume(&self, particles: &Particles) -> Scalar {
+        let [p0, p1, p2, p3] = self.particles.map(|i| particles.positions[i]);
+        (p1 - p0).cross(p2 - p0).dot(p3 - p0) / 6.0
+    }
+
+    /// Solves the constraint for a single substep with the given compliance (inverse of stiffness).
+    pub fn solve(&self, particles: &mut Particles, compliance: Scalar, delta_secs: Scalar) {
+        let [p0, p1, p2, p3] = self.particles.map(|i| particles.positions[i]);
+        let inverse_masses = self.particles.map(|i| particles.inverse_masses[i]);
+
+        // The gradients of six times the volume with respect to each particle
+        let gradient1 = (p2 - p0).cross(p3 - p0);
+        let gradient2 = (p3 - p0).cross(p1 - p0);
+        let gradient3 = (p1 - p0).cross(p2 - p0);
+        let gradients = [
+            -gradient1 - gradient2 - gradient3,
+            gradient1,
+            gradient2,
+            gradient3,
+        ];
+
+        let c = 6.0 * (self.volume(particles) - self.rest_volume);
+
+        // The Lagrange multiplier starts at zero every substep, so only the update is needed
+        let delta_lagrange =
+            compute_lagrange_update(0.0, c, &gradients, &inverse_masses, compliance, delta_secs);
+
+        for ((i, gradient), w) in self
+            .particles
+            .into_iter()
+            .zip(gradients)
+            .zip(inverse_masses)
+        {
+            particles.positions[i] += gradient * delta_lagrange * w;
+        }
+    }
+}
+
+/// Fills the interior of a closed triangle mesh with cubic cells that are split into six tetrahedra each.
+/// Returns the vertices and tetrahedra, or `None` if no cells are inside of the mesh.
+fn tetrahedralize(
+    vertices: &[Vector],
+    triangles: &[[usize; 3]],
+    resolution: u32,
+) -> Option<(Vec<Vector>, Vec<[usize; 4]>)> {
+    if triangles.is_empty() {
+        return None;
+    }
+
+    let trimesh = Collider::trimesh_with_config(
+        vertices.to_vec(),
+        triangles.iter().map(|t| t.map(|i| i as u32)).collect(),
+        TriMeshFlags::MERGE_DUPLICATE_VERTICES | TriMeshFlags::ORIENTED,
+    );
+
+    let (mins, maxs) = vertices
+        .iter()
+        .fold((vertices[0], vertices[0]), |(mins, maxs), v| {
+            (mins.min(*v), maxs.max(*v))
+        });
+    let extents = maxs - mins;
+    let cell_size = extents.max_element() / resolution.max(1) as Scalar;
+
+    if cell_size <= Scalar::EPSILON {
+        return None;
+    }
+
+    let to_vector = |v: UVec3| Vector::new(v.x as Scalar, v.y as Scalar, v.z as Scalar);
+
+    // Center the grid on the bounding box
+    let cell_counts = (extents / cell_size).ceil().max(Vector::ONE).as_uvec3();
+    let origin = mins - (to_vector(cell_counts) * cell_size - extents) / 2.0;
+
+    let mut node_particles: HashMap<UVec3, usize> = HashMap::default();
+    let mut particles = vec![];
+    let mut tetrahedra = vec![];
+
+    for z in 0..cell_counts.z {
+        for y in 0..cell_counts.y {
+            for x in 0..cell_counts.x {
+                let cell = UVec3::new(x, y, z);
+                let center = origin + (to_vector(cell) + 0.5) * cell_size;
+
+                if !trimesh.shape().contains_local_point(&center.into()) {
+                    continue;
+                }
+
+                // The particles at the eight corners of the cell, indexed by the bits of the corner offset
+                let corners: [usize; 8] = std::array::from_fn(|bits| {
+                    let bits = bits as u32;
+                    let node = cell + UVec3::new(bits & 1, (bits >> 1) & 1, bits >> 2);
+                    *node_particles.entry(node).or_insert_with(|| {
+                        particles.push(origin + to_vector(node) * cell_size);
+                        particles.len() - 1
+                    })
+                });
+
+                // Split the cell into six tetrahedra around its main diagonal.
+                // All cells are split the same way, so the faces of neighboring cells match.
+                for (a, b) in [(1, 2), (1, 4), (2, 1), (2, 4), (4, 1), (4, 2)] {
+                    tetrahedra.push([corners[0], corners[a], corners[a | b], corners[7]]);
+                }
+            }
+        }
+    }
+
+    (!tetrahedra.is_empty()).then_some((particles, tetrahedra))
+}
+
+/// Moves the particles of new soft bodies to world space using their `Transform`.
+pub(crate) fn init_soft_bodies(
+    mut soft_bodies: Query<(&mut SoftBody, Option<&Transform>), Added<SoftBody>>,
+) {
+    for (mut soft_body, transform) in &mut soft_bodies {
+        let Some(transform) = transform else {
+            continue;
+        };
+        let soft_body = &mut *soft_body;
+
+        soft_body
+            .particles
+            .transform(|p| transform.transform_point(p.as_f32()).adjust_precision());
+
+        // The rest lengths and volumes were computed from the mesh, so they need to include the scale
+        for constraint in &mut soft_body.edge_constraints {
+            constraint.reset_rest_length(&soft_body.particles);
+        }
+        for constraint in &mut soft_body.volume_constraints {
+            constraint.reset_rest_volume(&soft_body.particles);
+        }
+    }
+}
+
+/// Simulates the particles of each [`SoftBody`] for a single substep.
+pub(crate) fn simulate_soft_bodies(
+    mut soft_bodies: Query<&mut SoftBody>,
+    bodies: Query<(&Position, &Rotation)>,
+    colliders: DeformableColliderQuery,
+    pipeline: Option<Res<SpatialQueryPipeline>>,
+    gravity: Res<Gravity>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    if delta_secs == 0.0 {
+        return;
+    }
+
+    for mut soft_body in &mut soft_bodies {
+        let soft_body = &mut *soft_body;
+        let particles = &mut soft_body.particles;
+
+        particles.integrate(gravity.0, soft_body.damping, delta_secs);
+        apply_attachments(particles, &soft_body.attachments, &bodies);
+
+        for constraint in &soft_body.edge_constraints {
+            constraint.solve(particles, soft_body.edge_compliance, delta_secs);
+        }
+        for constraint in &soft_body.volume_constraints {
+            constraint.solve(particles, soft_body.volume_compliance, delta_secs);
+        }
+
+        if let Some((pipeline, aabb)) = pipeline
+            .as_ref()
+            .zip(particles.aabb(soft_body.particle_radius))
+        {
+            let nearby = nearby_colliders(pipeline, &colliders, aabb, &soft_body.filter);
+            if !nearby.is_empty() {
+                for i in 0..particles.len() {
+                    if particles.inverse_masses[i] == 0.0 {
+                        continue;
+                    }
+                    collide_particle(
+                        &mut particles.positions[i],
+                        particles.previous_positions[i],
+                        soft_body.particle_radius,
+                        soft_body.friction,
+                        &nearby,
+                    );
+                }
+            }
+        }
+
+        particles.update_velocities(delta_secs);
+    }
+}
+
+type SoftBodyMeshComponents = (
+    &'static SoftBody,
+    &'static Handle<Mesh>,
+    Option<&'static GlobalTransform>,
+    Option<&'static mut Aabb>,
+);
+
+/// Writes the skinned vertex positions and normals of each [`SoftBody`] into its mesh.
+pub(crate) fn update_soft_body_meshes(
+    mut soft_bodies: Query<SoftBodyMeshComponents, Changed<SoftBody>>,
+    meshes: Option<ResMut<Assets<Mesh>>>,
+) {
+    let Some(mut meshes) = meshes else {
+        return;
+    };
+
+    for (soft_body, mesh_handle, global_transform, aabb) in &mut soft_bodies {
+        if soft_body.skinning.is_empty() {
+            continue;
+        }
+        let Some(mesh) = meshes.get_mut(mesh_handle) else {
+            continue;
+        };
+
+        let positions: Vec<Vector> = soft_body
+            .skinning
+            .iter()
+            .map(|(particles, weights)| {
+                particles
+                    .iter()
+                    .zip(weights)
+                    .map(|(&i, &w)| soft_body.particles.positions[i] * w)
+                    .sum()
+            })
+            .collect();
+
+        let normals = mesh
+            .contains_attribute(Mesh::ATTRIBUTE_NORMAL)
+            .then(|| compute_vertex_normals(&positions, &soft_body.render_triangles));
+
+        write_mesh_vertices(mesh, &positions, normals.as_deref(), global_transform, aabb);
+    }
+}
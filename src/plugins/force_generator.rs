@@ -0,0 +1,229 @@
+//! Custom force laws that are evaluated every substep.
+//!
+//! See [`ForceGenerator`] and [`ForceGeneratorPlugin`].
+
+use std::marker::PhantomData;
+
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// A force law that computes forces for one or more [rigid bodies](RigidBody) every substep.
+///
+/// [`ExternalForce`] is only updated once per physics frame, which can make stiff force laws
+/// like strong springs unstable. Force generators are instead evaluated for the current state of
+/// the bodies in [`SubstepSet::Integrate`] right before the velocities are integrated,
+/// so the forces are always up to date.
+///
+/// Like [constraints](XpbdConstraint), force generators are components that store the entities
+/// they affect. The number of entities is given using generics, and the generator must be
+/// registered with the app by adding a [`ForceGeneratorPlugin`].
+///
+/// Only [dynamic bodies](RigidBody::Dynamic) are affected by the generated forces.
+/// If at least one of the bodies is active, [sleeping](Sleeping) bodies are woken up.
+///
+/// ## Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::{math::*, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::{math::*, prelude::*};")]
+///
+/// /// A damped spring between the centers of mass of two bodies.
+/// #[derive(Component)]
+/// struct Spring {
+///     entity1: Entity,
+///     entity2: Entity,
+///     rest_length: Scalar,
+///     stiffness: Scalar,
+///     damping: Scalar,
+/// }
+///
+/// impl ForceGenerator<2> for Spring {
+///     fn entities(&self) -> [Entity; 2] {
+///         [self.entity1, self.entity2]
+///     }
+///
+///     fn compute_forces(&self, bodies: [&RigidBodyQueryItem; 2], _dt: Scalar) -> [GeneratedForce; 2] {
+///         let [body1, body2] = bodies;
+///         let delta = body2.current_position() - body1.current_position();
+///         let length = delta.length();
+///         let Some(dir) = delta.try_normalize() else {
+///             return [GeneratedForce::ZERO; 2];
+///         };
+///         let relative_velocity = (body2.linear_velocity.0 - body1.linear_velocity.0).dot(dir);
+///         let force = dir * (self.stiffness * (length - self.rest_length) + self.damping * relative_velocity);
+///         [GeneratedForce::new(force), GeneratedForce::new(-force)]
+///     }
+/// }
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((
+///             DefaultPlugins,
+///             PhysicsPlugins::default(),
+///             ForceGeneratorPlugin::<Spring, 2>::default(),
+///         ))
+///         .run();
+/// }
+/// ```
+pub trait ForceGenerator<const ENTITY_COUNT: usize>: Component {
+    /// Returns the entities that the forces are computed for.
+    fn entities(&self) -> [Entity; ENTITY_COUNT];
+
+    /// Computes the forces and torques applied to each of the `bodies` for the current substep.
+    ///
+    /// The returned forces are in world space, and they are given in the same order as the entities
+    /// returned by [`entities`](Self::entities).
+    fn compute_forces(
+        &self,
+        bodies: [&RigidBodyQueryItem; ENTITY_COUNT],
+        dt: Scalar,
+    ) -> [GeneratedForce; ENTITY_COUNT];
+}
+
+/// A force and torque computed by a [`ForceGenerator`] for a single body.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GeneratedForce {
+    /// The world-space force applied at the center of mass.
+    pub force: Vector,
+    /// The world-space torque.
+    #[cfg(feature = "2d")]
+    pub torque: Scalar,
+    /// The world-space torque.
+    #[cfg(feature = "3d")]
+    pub torque: Vector,
+}
+
+impl GeneratedForce {
+    /// No force or torque.
+    pub const ZERO: Self = Self {
+        force: Vector::ZERO,
+        #[cfg(feature = "2d")]
+        torque: 0.0,
+        #[cfg(feature = "3d")]
+        torque: Vector::ZERO,
+    };
+
+    /// Creates a new [`GeneratedForce`] with the given world-space `force` applied at the center of mass.
+    pub fn new(force: Vector) -> Self {
+        Self {
+            force,
+            ..Self::ZERO
+        }
+    }
+
+    /// Creates a new [`GeneratedForce`] with the given world-space `force` applied at a world-space `point`,
+    /// which will also cause torque around the body's world-space `center_of_mass`.
+    pub fn at_point(force: Vector, point: Vector, center_of_mass: Vector) -> Self {
+        Self {
+            force,
+            #[cfg(feature = "2d")]
+            torque: (point - center_of_mass).perp_dot(force),
+            #[cfg(feature = "3d")]
+            torque: (point - center_of_mass).cross(force),
+        }
+    }
+
+    /// Adds the given world-space torque.
+    #[cfg(feature = "2d")]
+    pub fn with_torque(mut self, torque: Scalar) -> Self {
+        self.torque += torque;
+        self
+    }
+
+    /// Adds the given world-space torque.
+    #[cfg(feature = "3d")]
+    pub fn with_torque(mut self, torque: Vector) -> Self {
+        self.torque += torque;
+        self
+    }
+}
+
+/// A system set for [force generators](ForceGenerator). The set runs in [`SubstepSet::Integrate`]
+/// before the positions and rotations of bodies are integrated.
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ForceGeneratorSet;
+
+/// Registers a [`ForceGenerator`] so that it is evaluated every substep in [`ForceGeneratorSet`].
+///
+/// The plugin must be added after the [`PhysicsPlugins`], because it uses the [`SubstepSchedule`].
+pub struct ForceGeneratorPlugin<G: ForceGenerator<ENTITY_COUNT>, const ENTITY_COUNT: usize>(
+    PhantomData<G>,
+);
+
+impl<G: ForceGenerator<ENTITY_COUNT>, const ENTITY_COUNT: usize> Default
+    for ForceGeneratorPlugin<G, ENTITY_COUNT>
+{
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<G: ForceGenerator<ENTITY_COUNT>, const ENTITY_COUNT: usize> Plugin
+    for ForceGeneratorPlugin<G, ENTITY_COUNT>
+{
+    fn build(&self, app: &mut App) {
+        app.get_schedule_mut(SubstepSchedule)
+            .expect("add SubstepSchedule first")
+            .add_systems(
+                apply_force_generator::<G, ENTITY_COUNT>
+                    .in_set(ForceGeneratorSet)
+                    // The forces are accumulated into velocities, so the order doesn't matter
+                    .ambiguous_with(ForceGeneratorSet),
+            );
+    }
+}
+
+/// Computes the forces of the force generators of a given type and applies them to the velocities
+/// of the bodies. Sleeping bodies are woken up when active bodies share a force generator with them.
+///
+/// The system is added to [`ForceGeneratorSet`] by the [`ForceGeneratorPlugin`].
+pub fn apply_force_generator<G: ForceGenerator<ENTITY_COUNT>, const ENTITY_COUNT: usize>(
+    mut commands: Commands,
+    mut bodies: Query<(RigidBodyQuery, Option<&Sleeping>)>,
+    generators: Query<&G>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for generator in &generators {
+        let Ok(mut bodies) = bodies.get_many_mut(generator.entities()) else {
+            continue;
+        };
+
+        let none_dynamic = bodies.iter().all(|(body, _)| !body.rb.is_dynamic());
+        let all_inactive = bodies
+            .iter()
+            .all(|(body, sleeping)| body.rb.is_static() || sleeping.is_some());
+
+        // No forces if none of the bodies is dynamic,
+        // or if all of the bodies are either static or sleeping
+        if none_dynamic || all_inactive {
+            continue;
+        }
+
+        let forces = generator.compute_forces(std::array::from_fn(|i| &bodies[i].0), delta_secs);
+
+        for ((body, sleeping), force) in bodies.iter_mut().zip(forces) {
+            if !body.rb.is_dynamic() || force == GeneratedForce::ZERO {
+                continue;
+            }
+
+            // At least one of the participating bodies is active, so wake up any sleeping bodies
+            if sleeping.is_some() {
+                commands.entity(body.entity).remove::<Sleeping>();
+            }
+
+            let delta_lin_vel = delta_secs * force.force * body.effective_inv_mass();
+            let delta_ang_vel = delta_secs * body.effective_world_inv_inertia() * force.torque;
+
+            // avoid triggering bevy's change detection unnecessarily
+            if delta_lin_vel != Vector::ZERO {
+                body.linear_velocity.0 += delta_lin_vel;
+            }
+            if delta_ang_vel != AngularVelocity::ZERO.0 {
+                body.angular_velocity.0 += delta_ang_vel;
+            }
+        }
+    }
+}
@@ -0,0 +1,730 @@
+//! Breaks convex colliders into fragments at runtime.
+//!
+//! See [`FracturePlugin`].
+
+use crate::{plugins::solver::PenetrationConstraints, prelude::*, SubstepSchedule, SubstepSet};
+#[cfg(feature = "3d")]
+use bevy::pbr::StandardMaterial;
+#[cfg(feature = "2d")]
+use bevy::render::mesh::Indices;
+#[cfg(feature = "2d")]
+use bevy::sprite::{ColorMaterial, Mesh2dHandle};
+use bevy::{
+    ecs::system::Command, hierarchy::despawn_with_children_recursive, prelude::*,
+    render::render_resource::PrimitiveTopology, utils::HashMap,
+};
+use parry::shape::TypedShape;
+
+/// Breaks [`Fracturable`] bodies into fragments when they are hit hard enough
+/// or when a [`Fracture`] command is added.
+///
+/// The contact forces are read from the [`PenetrationConstraints`] after [`SubstepSet::SolveConstraints`],
+/// and the bodies are fractured at the end of the [`PhysicsSchedule`].
+///
+/// The plugin isn't included in the [`PhysicsPlugins`], so it must be added separately after them.
+pub struct FracturePlugin;
+
+impl Plugin for FracturePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Fracturable>().add_event::<Fractured>();
+
+        app.get_schedule_mut(SubstepSchedule)
+            .expect("add SubstepSchedule first")
+            .add_systems(store_impacts.after(SubstepSet::SolveConstraints));
+
+        app.get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first")
+            .add_systems(fracture_on_impact.after(PhysicsStepSet::SpatialQuery));
+    }
+}
+
+/// A component that allows a [dynamic rigid body](RigidBody::Dynamic) to be broken into fragments at runtime.
+///
+/// The body's [`Collider`] must be on the same entity and must be a cuboid or a convex hull. The shape is split into
+/// [Voronoi cells](https://en.wikipedia.org/wiki/Voronoi_diagram) around randomly placed sites, and each cell is spawned
+/// as a new dynamic rigid body with a convex hull collider. The original entity and its children are despawned.
+///
+/// The fragments inherit the velocity of the original body at their position, and their density is chosen so that
+/// the total [`Mass`] is preserved. They also inherit the [`CollisionLayers`], [`Friction`], [`Restitution`],
+/// [`LinearDamping`], [`AngularDamping`] and [`GravityScale`] of the original body.
+///
+/// ## Triggering fractures
+///
+/// If a [force threshold](Fracturable::force_threshold) is set, the body is fractured when the total contact force
+/// on it exceeds the threshold during a substep. Note that a resting body also receives contact forces that
+/// hold it up against gravity, so the threshold should be clearly larger than the weight of the body.
+///
+/// Bodies can also be fractured explicitly with the [`Fracture`] command.
+///
+/// When the fracture has an impact point, half of the sites are placed close to it, which produces
+/// smaller fragments around the impact.
+///
+/// ## Render meshes
+///
+/// If [`generate_meshes`](Fracturable::generate_meshes) is true, a matching mesh is generated for each fragment.
+#[cfg_attr(
+    feature = "2d",
+    doc = "The fragments use the `ColorMaterial` of the original entity if it has one."
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "The fragments use the `StandardMaterial` of the original entity if it has one."
+)]
+/// The texture coordinates are projected from the bounding box of the original shape.
+///
+/// The original entity is despawned along with the child colliders attached to it. Its other children,
+/// like effects or gameplay entities, are moved to the nearest fragment and keep their place in the world.
+///
+/// A [`Fractured`] event is sent with the spawned fragments, which can be used to add any other components.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// # #[cfg(all(feature = "3d", feature = "f32"))]
+/// fn setup(mut commands: Commands) {
+///     // A wall that breaks into 12 pieces when hit hard enough
+///     commands.spawn((
+///         RigidBody::Dynamic,
+///         Collider::cuboid(4.0, 3.0, 0.3),
+///         Fracturable::new(12).with_force_threshold(5000.0),
+///     ));
+/// }
+///
+/// # #[cfg(all(feature = "3d", feature = "f32"))]
+/// fn break_wall(mut commands: Commands, walls: Query<Entity, With<Fracturable>>) {
+///     for entity in &walls {
+///         commands.add(Fracture::new(entity).with_impact_point(Vec3::Y));
+///     }
+/// }
+/// ```
+#[derive(Component, Reflect, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct Fracturable {
+    /// The number of Voronoi sites used for splitting the shape. Very small cells are discarded,
+    /// so the number of fragments can be slightly lower.
+    pub fragment_count: usize,
+    /// The total contact force required to fracture the body.
+    /// If `None`, the body can only be fractured with the [`Fracture`] command.
+    pub force_threshold: Option<Scalar>,
+    /// If true, render meshes are generated for the fragments. The default is `true`.
+    pub generate_meshes: bool,
+    /// The seed used for placing the Voronoi sites. The same seed produces the same fragments.
+    pub seed: u64,
+    /// The largest contact force and its local contact point during the current physics frame.
+    #[reflect(ignore)]
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    impact: Option<(Vector, Scalar)>,
+}
+
+impl Default for Fracturable {
+    fn default() -> Self {
+        Self::new(8)
+    }
+}
+
+impl Fracturable {
+    /// Creates a new [`Fracturable`] that splits the body into the given number of fragments.
+    /// The body is only fractured with the [`Fracture`] command until a force threshold is set.
+    pub fn new(fragment_count: usize) -> Self {
+        Self {
+            fragment_count,
+            force_threshold: None,
+            generate_meshes: true,
+            seed: 0,
+            impact: None,
+        }
+    }
+
+    /// Sets the total contact force required to fracture the body.
+    pub fn with_force_threshold(mut self, force_threshold: Scalar) -> Self {
+        self.force_threshold = Some(force_threshold);
+        self
+    }
+
+    /// Determines if render meshes are generated for the fragments.
+    pub fn with_meshes(mut self, generate_meshes: bool) -> Self {
+        self.generate_meshes = generate_meshes;
+        self
+    }
+
+    /// Sets the seed used for placing the Voronoi sites.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+/// A command that breaks a [`Fracturable`] body into fragments.
+///
+/// See [`Fracturable`] for more information.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fracture {
+    /// The [`Fracturable`] entity to break.
+    pub entity: Entity,
+    /// The world-space point where the body was hit. Smaller fragments are created around this point.
+    pub impact_point: Option<Vector>,
+}
+
+impl Fracture {
+    /// Creates a new [`Fracture`] command for the given entity without an impact point.
+    pub fn new(entity: Entity) -> Self {
+        Self {
+            entity,
+            impact_point: None,
+        }
+    }
+
+    /// Sets the world-space point where the body was hit.
+    pub fn with_impact_point(mut self, impact_point: Vector) -> Self {
+        self.impact_point = Some(impact_point);
+        self
+    }
+}
+
+/// An event that is sent when a [`Fracturable`] body has been broken into fragments.
+#[derive(Event, Clone, Debug, PartialEq)]
+pub struct Fractured {
+    /// The original entity. It has been despawned along with its child colliders,
+    /// and its other children have been moved to the nearest fragments.
+    pub entity: Entity,
+    /// The spawned fragments.
+    pub fragments: Vec<Entity>,
+}
+
+/// Stores the largest total contact force on each [`Fracturable`] body with a force threshold.
+fn store_impacts(
+    mut fracturables: Query<&mut Fracturable>,
+    penetration_constraints: Res<PenetrationConstraints>,
+) {
+    // The total contact force on each body and its strongest contact point
+    let mut forces: HashMap<Entity, (Vector, Scalar, Scalar)> = HashMap::default();
+
+    for constraint in penetration_constraints.0.iter() {
+        let force = constraint.normal_force.length();
+        for (entity, point) in [
+            (constraint.entity1, constraint.contact.point1),
+            (constraint.entity2, constraint.contact.point2),
+        ] {
+            if !fracturables
+                .get(entity)
+                .is_ok_and(|fracturable| fracturable.force_threshold.is_some())
+            {
+                continue;
+            }
+            let (strongest_point, strongest_force, total) =
+                forces.entry(entity).or_insert((point, 0.0, 0.0));
+            if force > *strongest_force {
+                *strongest_point = point;
+                *strongest_force = force;
+            }
+            *total += force;
+        }
+    }
+
+    for (entity, (point, _, total)) in forces {
+        let Ok(mut fracturable) = fracturables.get_mut(entity) else {
+            continue;
+        };
+        if !matches!(fracturable.impact, Some((_, force)) if force >= total) {
+            fracturable.impact = Some((point, total));
+        }
+    }
+}
+
+/// Adds a [`Fracture`] command for each [`Fracturable`] body whose contact force exceeded the threshold.
+fn fracture_on_impact(
+    mut commands: Commands,
+    mut fracturables: Query<(Entity, &mut Fracturable, &Position, &Rotation)>,
+) {
+    for (entity, mut fracturable, position, rotation) in &mut fracturables {
+        let Some((point, force)) = fracturable.impact.take() else {
+            continue;
+        };
+        if fracturable
+            .force_threshold
+            .is_some_and(|threshold| force >= threshold)
+        {
+            commands
+                .add(Fracture::new(entity).with_impact_point(position.0 + rotation.rotate(point)));
+        }
+    }
+}
+
+impl Command for Fracture {
+    fn apply(self, world: &mut World) {
+        let Some((fracturable, collider, position, rotation)) = world
+            .get::<Fracturable>(self.entity)
+            .zip(world.get::<Collider>(self.entity))
+            .zip(world.get::<Position>(self.entity))
+            .zip(world.get::<Rotation>(self.entity))
+            .map(|(((fracturable, collider), position), rotation)| {
+                (fracturable.clone(), collider.clone(), position.0, *rotation)
+            })
+        else {
+            return;
+        };
+
+        let Some(vertices) = convex_vertices(&collider) else {
+            warn!("tried to fracture an entity whose collider is not a cuboid or a convex hull");
+            return;
+        };
+
+        let local_impact_point = self
+            .impact_point
+            .map(|point| rotation.inverse().rotate(point - position));
+        let sites = voronoi_sites(
+            &collider,
+            &vertices,
+            fracturable.fragment_count,
+            fracturable.seed,
+            local_impact_point,
+        );
+
+        // Keep the total mass of the body the same
+        let volume = collider.mass_properties(1.0).mass.0;
+        let density = world
+            .get::<Mass>(self.entity)
+            .map(|mass| mass.0 / volume)
+            .filter(|density| density.is_finite() && *density > 0.0)
+            .or_else(|| world.get::<ColliderDensity>(self.entity).map(|d| d.0))
+            .unwrap_or(ColliderDensity::default().0);
+
+        let linear_velocity = world
+            .get::<LinearVelocity>(self.entity)
+            .map_or(Vector::ZERO, |v| v.0);
+        let angular_velocity = world
+            .get::<AngularVelocity>(self.entity)
+            .map_or(AngularVelocity::ZERO.0, |v| v.0);
+        let center_of_mass = world
+            .get::<CenterOfMass>(self.entity)
+            .map_or(Vector::ZERO, |com| com.0);
+
+        let (min, max) = bounds(&vertices);
+        let layers = world.get::<CollisionLayers>(self.entity).copied();
+        let friction = world.get::<Friction>(self.entity).copied();
+        let restitution = world.get::<Restitution>(self.entity).copied();
+        let linear_damping = world.get::<LinearDamping>(self.entity).copied();
+        let angular_damping = world.get::<AngularDamping>(self.entity).copied();
+        let gravity_scale = world.get::<GravityScale>(self.entity).copied();
+        #[cfg(feature = "2d")]
+        let material = world.get::<Handle<ColorMaterial>>(self.entity).cloned();
+        #[cfg(feature = "3d")]
+        let material = world.get::<Handle<StandardMaterial>>(self.entity).cloned();
+        #[cfg(feature = "2d")]
+        let z = world
+            .get::<Transform>(self.entity)
+            .map_or(0.0, |transform| transform.translation.z);
+
+        let mut fragments = Vec::with_capacity(sites.len());
+
+        for cell in voronoi_cells(&vertices, &sites) {
+            let Some(cell_collider) = Collider::convex_hull(cell.clone()) else {
+                continue;
+            };
+            let cell_properties = cell_collider.mass_properties(1.0);
+            if cell_properties.mass.0 <= volume * 1e-5 {
+                continue;
+            }
+
+            // Move the origin of the fragment to its center of mass
+            let centroid = cell_properties.center_of_mass.0;
+            let cell: Vec<Vector> = cell.iter().map(|v| *v - centroid).collect();
+            let Some(fragment_collider) = Collider::convex_hull(cell.clone()) else {
+                continue;
+            };
+
+            let fragment_position = position + rotation.rotate(centroid);
+            let offset = rotation.rotate(centroid - center_of_mass);
+            #[cfg(feature = "2d")]
+            let velocity = linear_velocity + angular_velocity * offset.perp();
+            #[cfg(feature = "3d")]
+            let velocity = linear_velocity + angular_velocity.cross(offset);
+
+            #[cfg(feature = "2d")]
+            let translation = fragment_position.as_f32().extend(z);
+            #[cfg(feature = "3d")]
+            let translation = fragment_position.as_f32();
+            let transform = Transform::from_translation(translation)
+                .with_rotation(Quaternion::from(rotation).as_f32());
+
+            let mut fragment = world.spawn((
+                RigidBody::Dynamic,
+                Position(fragment_position),
+                rotation,
+                LinearVelocity(velocity),
+                AngularVelocity(angular_velocity),
+                fragment_collider,
+                ColliderDensity(density),
+                TransformBundle::from_transform(transform),
+                VisibilityBundle::default(),
+            ));
+
+            if let Some(layers) = layers {
+                fragment.insert(layers);
+            }
+            if let Some(friction) = friction {
+                fragment.insert(friction);
+            }
+            if let Some(restitution) = restitution {
+                fragment.insert(restitution);
+            }
+            if let Some(linear_damping) = linear_damping {
+                fragment.insert(linear_damping);
+            }
+            if let Some(angular_damping) = angular_damping {
+                fragment.insert(angular_damping);
+            }
+            if let Some(gravity_scale) = gravity_scale {
+                fragment.insert(gravity_scale);
+            }
+            if let Some(material) = material.clone() {
+                fragment.insert(material);
+            }
+
+            let fragment = fragment.id();
+
+            if fracturable.generate_meshes {
+                let uv_bounds = (min - centroid, max - centroid);
+                if let Some(mesh) = fragment_mesh(&cell, uv_bounds) {
+                    if let Some(mut meshes) = world.get_resource_mut::<Assets<Mesh>>() {
+                        let mesh = meshes.add(mesh);
+                        #[cfg(feature = "2d")]
+                        world.entity_mut(fragment).insert(Mesh2dHandle(mesh));
+                        #[cfg(feature = "3d")]
+                        world.entity_mut(fragment).insert(mesh);
+                    }
+                }
+            }
+
+            fragments.push(fragment);
+        }
+
+        if fragments.is_empty() {
+            return;
+        }
+
+        // Colliders attached to the body break with it, but other children are moved to the nearest fragment
+        let children = world
+            .get::<Children>(self.entity)
+            .map_or(vec![], |children| children.to_vec());
+        for child in children {
+            if world
+                .get::<ColliderParent>(child)
+                .is_some_and(|parent| parent.get() == self.entity)
+            {
+                despawn_with_children_recursive(world, child);
+                continue;
+            }
+
+            let global_transform = world
+                .get::<GlobalTransform>(child)
+                .copied()
+                .unwrap_or_default();
+            let translation = global_transform.translation();
+            let nearest = fragments
+                .iter()
+                .filter_map(|&fragment| {
+                    let transform = *world.get::<Transform>(fragment)?;
+                    Some((fragment, transform))
+                })
+                .min_by(|(_, a), (_, b)| {
+                    let distance =
+                        |transform: &Transform| transform.translation.distance_squared(translation);
+                    distance(a).total_cmp(&distance(b))
+                });
+            if let Some((fragment, fragment_transform)) = nearest {
+                // Keep the child where it is in world space
+                let transform =
+                    global_transform.reparented_to(&GlobalTransform::from(fragment_transform));
+                world
+                    .entity_mut(child)
+                    .set_parent(fragment)
+                    .insert(transform);
+            }
+        }
+
+        despawn_with_children_recursive(world, self.entity);
+
+        world.send_event(Fractured {
+            entity: self.entity,
+            fragments,
+        });
+    }
+}
+
+/// Returns the local vertices of a cuboid or convex hull collider.
+fn convex_vertices(collider: &Collider) -> Option<Vec<Vector>> {
+    match collider.shape_scaled().as_typed_shape() {
+        TypedShape::Cuboid(cuboid) => {
+            let half_extents = Vector::from(cuboid.half_extents);
+            #[cfg(feature = "2d")]
+            let corner_count = 4;
+            #[cfg(feature = "3d")]
+            let corner_count = 8;
+            let corners = (0..corner_count).map(|i| {
+                let sign = |axis: usize| if i & (1 << axis) == 0 { -1.0 } else { 1.0 };
+                #[cfg(feature = "2d")]
+                let signs = Vector::new(sign(0), sign(1));
+                #[cfg(feature = "3d")]
+                let signs = Vector::new(sign(0), sign(1), sign(2));
+                half_extents * signs
+            });
+            Some(corners.collect())
+        }
+        #[cfg(feature = "2d")]
+        TypedShape::ConvexPolygon(polygon) => {
+            Some(polygon.points().iter().copied().map(Vector::from).collect())
+        }
+        #[cfg(feature = "3d")]
+        TypedShape::ConvexPolyhedron(polyhedron) => Some(
+            polyhedron
+                .points()
+                .iter()
+                .copied()
+                .map(Vector::from)
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+/// Returns the minimum and maximum corners of the bounding box of the given points.
+fn bounds(points: &[Vector]) -> (Vector, Vector) {
+    points.iter().fold(
+        (Vector::splat(Scalar::MAX), Vector::splat(Scalar::MIN)),
+        |(min, max), point| (min.min(*point), max.max(*point)),
+    )
+}
+
+/// A small deterministic random number generator (`SplitMix64`) for placing the Voronoi sites.
+struct SiteRng(u64);
+
+impl SiteRng {
+    /// Returns a random number in the range `[0, 1)`.
+    fn next(&mut self) -> Scalar {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+        (z >> 11) as Scalar / (1u64 << 53) as Scalar
+    }
+
+    /// Returns a random point in the box between `min` and `max`.
+    fn point(&mut self, min: Vector, max: Vector) -> Vector {
+        #[cfg(feature = "2d")]
+        let t = Vector::new(self.next(), self.next());
+        #[cfg(feature = "3d")]
+        let t = Vector::new(self.next(), self.next(), self.next());
+        min + (max - min) * t
+    }
+}
+
+/// Places the Voronoi sites randomly inside the collider. If an impact point is given,
+/// half of the sites are placed near it.
+fn voronoi_sites(
+    collider: &Collider,
+    vertices: &[Vector],
+    count: usize,
+    seed: u64,
+    impact_point: Option<Vector>,
+) -> Vec<Vector> {
+    let shape = collider.shape_scaled();
+    let (min, max) = bounds(vertices);
+    let extent = (max - min).length();
+    let mut rng = SiteRng(seed);
+    let mut sites: Vec<Vector> = Vec::with_capacity(count);
+
+    // Move the impact point inside of the shape
+    let impact_point = impact_point
+        .map(|point| Vector::from(shape.project_local_point(&point.into(), true).point));
+
+    let mut attempts = 0;
+    while sites.len() < count && attempts < 64 * count {
+        attempts += 1;
+
+        let site = match impact_point {
+            Some(point) if sites.len() < count / 2 => {
+                let radius = Vector::splat(0.25 * extent);
+                rng.point(point - radius, point + radius)
+            }
+            _ => rng.point(min, max),
+        };
+
+        let too_close = sites
+            .iter()
+            .any(|other| other.distance_squared(site) < (extent * 1e-3).powi(2));
+        if !too_close && shape.contains_local_point(&site.into()) {
+            sites.push(site);
+        }
+    }
+
+    sites
+}
+
+/// Computes the Voronoi cell of each site by clipping the convex shape with the bisectors
+/// between the site and all other sites.
+fn voronoi_cells(vertices: &[Vector], sites: &[Vector]) -> Vec<Vec<Vector>> {
+    sites
+        .iter()
+        .enumerate()
+        .map(|(i, site)| {
+            let mut cell = vertices.to_vec();
+            for (j, other) in sites.iter().enumerate() {
+                if i == j || cell.is_empty() {
+                    continue;
+                }
+                let midpoint = (*site + *other) / 2.0;
+                let normal = (*other - *site).normalize_or_zero();
+                cell = clip_convex(&cell, midpoint, normal);
+            }
+            cell
+        })
+        .collect()
+}
+
+/// Clips a convex shape given by its vertices with a plane, keeping the part behind the plane.
+fn clip_convex(vertices: &[Vector], origin: Vector, normal: Vector) -> Vec<Vector> {
+    let epsilon = 1e-6;
+    let distances: Vec<Scalar> = vertices.iter().map(|v| (*v - origin).dot(normal)).collect();
+
+    if distances.iter().all(|d| *d <= epsilon) {
+        return vertices.to_vec();
+    }
+
+    let mut clipped: Vec<Vector> = vertices
+        .iter()
+        .zip(&distances)
+        .filter(|(_, d)| **d <= epsilon)
+        .map(|(v, _)| *v)
+        .collect();
+
+    if clipped.is_empty() {
+        return clipped;
+    }
+
+    // The intersections of all segments crossing the plane. Segments through the
+    // interior only add points inside the cell, which are removed by the convex hull.
+    for (a, distance_a) in vertices.iter().zip(&distances) {
+        if *distance_a >= -epsilon {
+            continue;
+        }
+        for (b, distance_b) in vertices.iter().zip(&distances) {
+            if *distance_b <= epsilon {
+                continue;
+            }
+            let t = distance_a / (distance_a - distance_b);
+            clipped.push(*a + (*b - *a) * t);
+        }
+    }
+
+    convex_hull_points(&clipped)
+}
+
+/// Returns the vertices of the convex hull of the given points, or an empty list if the hull is degenerate.
+fn convex_hull_points(points: &[Vector]) -> Vec<Vector> {
+    let points: Vec<_> = points.iter().map(|p| (*p).into()).collect();
+
+    #[cfg(feature = "2d")]
+    let hull = parry::transformation::convex_hull(&points);
+    #[cfg(feature = "3d")]
+    let hull =
+        parry::transformation::try_convex_hull(&points).map_or(vec![], |(vertices, _)| vertices);
+
+    hull.into_iter().map(Vector::from).collect()
+}
+
+/// Creates a render mesh for a fragment with the given local vertices.
+///
+/// The texture coordinates are projected from `uv_bounds`, the bounding box of the original shape.
+#[cfg(feature = "2d")]
+fn fragment_mesh(vertices: &[Vector], uv_bounds: (Vector, Vector)) -> Option<Mesh> {
+    let hull = convex_hull_points(vertices);
+    if hull.len() < 3 {
+        return None;
+    }
+
+    let (min, max) = uv_bounds;
+    let size = (max - min).max(Vector::splat(Scalar::EPSILON));
+
+    let positions: Vec<[f32; 3]> = hull.iter().map(|v| v.as_f32().extend(0.0).into()).collect();
+    let normals = vec![[0.0, 0.0, 1.0]; hull.len()];
+    let uvs: Vec<[f32; 2]> = hull
+        .iter()
+        .map(|v| {
+            let uv = ((*v - min) / size).as_f32();
+            [uv.x, 1.0 - uv.y]
+        })
+        .collect();
+    let indices = (1..hull.len() as u32 - 1)
+        .flat_map(|i| [0, i, i + 1])
+        .collect();
+
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh.set_indices(Some(Indices::U32(indices)));
+    Some(mesh)
+}
+
+/// Creates a flat-shaded render mesh for a fragment with the given local vertices.
+///
+/// The texture coordinates are projected from `uv_bounds`, the bounding box of the original shape,
+/// along the axis closest to the normal of each face.
+#[cfg(feature = "3d")]
+fn fragment_mesh(vertices: &[Vector], uv_bounds: (Vector, Vector)) -> Option<Mesh> {
+    let points: Vec<_> = vertices.iter().map(|p| (*p).into()).collect();
+    let (hull, triangles) = parry::transformation::try_convex_hull(&points).ok()?;
+    if triangles.is_empty() {
+        return None;
+    }
+
+    let hull: Vec<Vector> = hull.into_iter().map(Vector::from).collect();
+    let center = hull.iter().copied().sum::<Vector>() / hull.len() as Scalar;
+    let (min, max) = uv_bounds;
+    let size = (max - min).max(Vector::splat(Scalar::EPSILON));
+
+    let mut positions: Vec<[f32; 3]> = Vec::with_capacity(triangles.len() * 3);
+    let mut normals: Vec<[f32; 3]> = Vec::with_capacity(triangles.len() * 3);
+    let mut uvs: Vec<[f32; 2]> = Vec::with_capacity(triangles.len() * 3);
+
+    for [a, b, c] in triangles {
+        let [a, mut b, mut c] = [a, b, c].map(|i| hull[i as usize]);
+        let mut normal = (b - a).cross(c - a).normalize_or_zero();
+
+        // Make sure that the face points outwards
+        if normal.dot(a - center) < 0.0 {
+            std::mem::swap(&mut b, &mut c);
+            normal = -normal;
+        }
+
+        let abs_normal = normal.abs();
+        for vertex in [a, b, c] {
+            let uv = ((vertex - min) / size).as_f32();
+            let uv = if abs_normal.x >= abs_normal.y && abs_normal.x >= abs_normal.z {
+                [uv.z, 1.0 - uv.y]
+            } else if abs_normal.y >= abs_normal.z {
+                [uv.x, uv.z]
+            } else {
+                [uv.x, 1.0 - uv.y]
+            };
+            positions.push(vertex.as_f32().into());
+            normals.push(normal.as_f32().into());
+            uvs.push(uv);
+        }
+    }
+
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    Some(mesh)
+}
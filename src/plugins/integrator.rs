@@ -12,14 +12,36 @@ use bevy::prelude::*;
 ///
 /// The integration scheme used is very closely related to implicit Euler integration.
 ///
-/// The integration systems run in [`SubstepSet::Integrate`].
+/// In addition to the global [`Gravity`] and the [forces](ExternalForce) of the bodies themselves,
+/// the integrator also evaluates any [`GravityField`]s and [`ForceEffector`]s that contain the bodies,
+/// and applies drag and lift to bodies with [`Aerodynamics`].
+///
+/// The integration systems run in [`SubstepSet::Integrate`], after any [force generators](ForceGenerator)
+/// in [`ForceGeneratorSet`].
 pub struct IntegratorPlugin;
 
 impl Plugin for IntegratorPlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<Atmosphere>();
+
         app.get_schedule_mut(SubstepSchedule)
             .expect("add SubstepSchedule first")
-            .add_systems((integrate_pos, integrate_rot).in_set(SubstepSet::Integrate));
+            .configure_sets(
+                ForceGeneratorSet
+                    .in_set(SubstepSet::Integrate)
+                    .before(integrate_pos)
+                    .before(integrate_rot),
+            )
+            .add_systems(
+                (
+                    // Drag and lift depend on the velocities produced by the force generators
+                    apply_aerodynamics.after(ForceGeneratorSet),
+                    integrate_pos,
+                    integrate_rot,
+                )
+                    .chain()
+                    .in_set(SubstepSet::Integrate),
+            );
         app.get_schedule_mut(PhysicsSchedule)
             .expect("add PhysicsSchedule first")
             .add_systems(
@@ -31,37 +53,105 @@ impl Plugin for IntegratorPlugin {
     }
 }
 
+type AerodynamicsComponents = (
+    &'static RigidBody,
+    &'static Aerodynamics,
+    &'static Collider,
+    &'static Rotation,
+    &'static mut LinearVelocity,
+    &'static InverseMass,
+    Option<&'static LockedAxes>,
+);
+
+/// Applies [aerodynamic](Aerodynamics) drag and lift to the linear velocities of bodies
+/// based on the [`Atmosphere`].
+fn apply_aerodynamics(
+    mut bodies: Query<AerodynamicsComponents, Without<Sleeping>>,
+    atmosphere: Res<Atmosphere>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for (rb, aerodynamics, collider, rot, mut lin_vel, inv_mass, locked_axes) in &mut bodies {
+        if !rb.is_dynamic() {
+            continue;
+        }
+
+        let locked_axes = locked_axes.map_or(LockedAxes::default(), |locked_axes| *locked_axes);
+        let effective_inv_mass = locked_axes.apply_to_vec(Vector::splat(inv_mass.0));
+
+        let relative_velocity = lin_vel.0 - atmosphere.wind;
+        let drag = aerodynamics.drag(collider, rot, relative_velocity, atmosphere.air_density);
+        let lift = aerodynamics.lift(collider, rot, relative_velocity, atmosphere.air_density);
+
+        // The drag is applied explicitly, so a large enough drag could overshoot and reverse
+        // the motion relative to the air. Clamp it so that it can at most stop the relative motion.
+        let mut delta_drag = delta_secs * drag * effective_inv_mass;
+        let speed = relative_velocity.length();
+        let drag_speed = -delta_drag.dot(relative_velocity.normalize_or_zero());
+        if drag_speed > speed {
+            delta_drag *= speed / drag_speed;
+        }
+
+        let delta_lin_vel = delta_drag + delta_secs * lift * effective_inv_mass;
+        // avoid triggering bevy's change detection unnecessarily
+        if delta_lin_vel != Vector::ZERO {
+            lin_vel.0 += delta_lin_vel;
+        }
+    }
+}
+
 type PosIntegrationComponents = (
+    Entity,
     &'static RigidBody,
     &'static Position,
+    &'static Rotation,
     &'static mut PreviousPosition,
     &'static mut AccumulatedTranslation,
     &'static mut LinearVelocity,
     Option<&'static LinearDamping>,
     Option<&'static GravityScale>,
+    Option<&'static GravityOverride>,
     &'static ExternalForce,
     &'static Mass,
     &'static InverseMass,
     Option<&'static LockedAxes>,
 );
 
+type FieldComponents<T> = (
+    Entity,
+    &'static T,
+    Option<&'static Position>,
+    Option<&'static Rotation>,
+    Option<&'static Collider>,
+);
+
 /// Explicitly integrates the positions and linear velocities of bodies taking only external forces
 /// like gravity into account. This acts as a prediction for the next positions of the bodies.
 fn integrate_pos(
     mut bodies: Query<PosIntegrationComponents, Without<Sleeping>>,
+    gravity_fields: Query<FieldComponents<GravityField>>,
+    force_effectors: Query<FieldComponents<ForceEffector>>,
     gravity: Res<Gravity>,
     time: Res<Time>,
 ) {
     let delta_secs = time.delta_seconds_adjusted();
 
+    // Gravity fields are evaluated in order of increasing priority
+    let mut gravity_fields = gravity_fields.iter().collect::<Vec<_>>();
+    gravity_fields.sort_by_key(|(_, field, ..)| field.priority);
+
     for (
+        entity,
         rb,
         pos,
+        rot,
         mut prev_pos,
         mut translation,
         mut lin_vel,
         lin_damping,
         gravity_scale,
+        gravity_override,
         external_force,
         mass,
         inv_mass,
@@ -86,10 +176,54 @@ fn integrate_pos(
             let effective_mass = locked_axes.apply_to_vec(Vector::splat(mass.0));
             let effective_inv_mass = locked_axes.apply_to_vec(Vector::splat(inv_mass.0));
 
+            // Evaluate gravity fields and force effectors that contain the body
+            let mut body_gravity = gravity_override.map_or(gravity.0, |gravity| gravity.0);
+            for (field_entity, field, field_pos, field_rot, field_collider) in &gravity_fields {
+                if *field_entity != entity
+                    && field_contains_point(*field_pos, *field_rot, *field_collider, pos.0)
+                {
+                    let field_rot = field_rot.copied().unwrap_or_default();
+                    body_gravity = field.blend(
+                        body_gravity,
+                        field_pos.map_or(Vector::ZERO, |pos| pos.0),
+                        &field_rot,
+                        pos.0,
+                    );
+                }
+            }
+            let mut effector_force = Vector::ZERO;
+            let mut effector_acceleration = Vector::ZERO;
+            for (effector_entity, effector, effector_pos, effector_rot, effector_collider) in
+                &force_effectors
+            {
+                if effector_entity == entity
+                    || !field_contains_point(effector_pos, effector_rot, effector_collider, pos.0)
+                {
+                    continue;
+                }
+                let force = effector.force_at(
+                    effector_pos.map_or(Vector::ZERO, |pos| pos.0),
+                    &effector_rot.copied().unwrap_or_default(),
+                    pos.0,
+                    lin_vel.0,
+                );
+                if effector.ignore_mass {
+                    effector_acceleration += force;
+                } else {
+                    effector_force += force;
+                }
+            }
+
             // Apply forces
-            let gravitation_force =
-                effective_mass * gravity.0 * gravity_scale.map_or(1.0, |scale| scale.0);
-            let external_forces = gravitation_force + external_force.force();
+            let gravitation_force = effective_mass
+                * (body_gravity * gravity_scale.map_or(1.0, |scale| scale.0)
+                    + effector_acceleration);
+            let body_force = if external_force.local {
+                rot.rotate(external_force.force())
+            } else {
+                external_force.force()
+            };
+            let external_forces = gravitation_force + body_force + effector_force;
             let delta_lin_vel = delta_secs * external_forces * effective_inv_mass;
             // avoid triggering bevy's change detection unnecessarily
             if delta_lin_vel != Vector::ZERO {
@@ -102,6 +236,26 @@ fn integrate_pos(
     }
 }
 
+/// Returns true if the given world-space `point` is inside the area of a [`GravityField`]
+/// or [`ForceEffector`]. Fields without a collider are global and contain every point.
+fn field_contains_point(
+    field_pos: Option<&Position>,
+    field_rot: Option<&Rotation>,
+    field_collider: Option<&Collider>,
+    point: Vector,
+) -> bool {
+    let Some(collider) = field_collider else {
+        return true;
+    };
+    collider.shape_scaled().contains_point(
+        &utils::make_isometry(
+            field_pos.copied().unwrap_or_default(),
+            field_rot.copied().unwrap_or_default(),
+        ),
+        &point.into(),
+    )
+}
+
 type RotIntegrationComponents = (
     &'static RigidBody,
     &'static mut Rotation,
@@ -211,10 +365,17 @@ fn integrate_rot(mut bodies: Query<RotIntegrationComponents, Without<Sleeping>>,
             let effective_inertia = locked_axes.apply_to_rotation(inertia.rotated(&rot).0);
             let effective_inv_inertia = locked_axes.apply_to_rotation(inv_inertia.rotated(&rot).0);
 
+            // Torque caused by local forces is also in local space
+            let force_torque = if external_force.local {
+                rot.rotate(external_force.torque())
+            } else {
+                external_force.torque()
+            };
+
             // Apply external torque
             let delta_ang_vel = delta_secs
                 * effective_inv_inertia
-                * ((external_torque.torque() + external_force.torque())
+                * ((external_torque.torque() + force_torque)
                     - ang_vel.0.cross(effective_inertia * ang_vel.0));
             // avoid triggering bevy's change detection unnecessarily
             if delta_ang_vel != Vector::ZERO {
@@ -268,10 +429,28 @@ fn apply_impulses(mut bodies: Query<ImpulseQueryComponents, Without<Sleeping>>)
         let effective_inv_mass = locked_axes.apply_to_vec(Vector::splat(inv_mass.0));
         let effective_inv_inertia = locked_axes.apply_to_rotation(inv_inertia.rotated(rotation).0);
 
+        let (lin_impulse, impulse_ang_impulse) = if impulse.local {
+            #[cfg(feature = "2d")]
+            {
+                (
+                    rotation.rotate(impulse.impulse()),
+                    impulse.angular_impulse(),
+                )
+            }
+            #[cfg(feature = "3d")]
+            {
+                (
+                    rotation.rotate(impulse.impulse()),
+                    rotation.rotate(impulse.angular_impulse()),
+                )
+            }
+        } else {
+            (impulse.impulse(), impulse.angular_impulse())
+        };
+
         // avoid triggering bevy's change detection unnecessarily
-        let delta_lin_vel = impulse.impulse() * effective_inv_mass;
-        let delta_ang_vel =
-            effective_inv_inertia * (ang_impulse.impulse() + impulse.angular_impulse());
+        let delta_lin_vel = lin_impulse * effective_inv_mass;
+        let delta_ang_vel = effective_inv_inertia * (ang_impulse.impulse() + impulse_ang_impulse);
 
         if delta_lin_vel != Vector::ZERO {
             lin_vel.0 += delta_lin_vel;
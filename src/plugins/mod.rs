@@ -23,8 +23,15 @@
 pub mod collision;
 #[cfg(feature = "debug-plugin")]
 pub mod debug;
+#[cfg(feature = "deformable")]
+pub mod deformable;
+pub mod force_generator;
+#[cfg(feature = "fracture")]
+pub mod fracture;
 pub mod integrator;
 pub mod prepare;
+#[cfg(all(feature = "3d", feature = "ragdoll"))]
+pub mod ragdoll;
 pub mod setup;
 pub mod sleeping;
 pub mod solver;
@@ -38,8 +45,15 @@ pub use collision::{
 };
 #[cfg(feature = "debug-plugin")]
 pub use debug::PhysicsDebugPlugin;
+#[cfg(feature = "deformable")]
+pub use deformable::DeformablePlugin;
+pub use force_generator::ForceGeneratorPlugin;
+#[cfg(feature = "fracture")]
+pub use fracture::FracturePlugin;
 pub use integrator::IntegratorPlugin;
 pub use prepare::PreparePlugin;
+#[cfg(all(feature = "3d", feature = "ragdoll"))]
+pub use ragdoll::RagdollPlugin;
 pub use setup::PhysicsSetupPlugin;
 pub use sleeping::SleepingPlugin;
 pub use solver::SolverPlugin;
@@ -70,6 +84,13 @@ use bevy::prelude::*;
 /// - `PhysicsDebugPlugin`: Renders physics objects and events like [AABBs](ColliderAabb) and [contacts](Collision)
 /// for debugging purposes (only with `debug-plugin` feature enabled).
 ///
+/// The following optional plugins aren't added by default. They must be added separately
+/// after the [`PhysicsPlugins`] when their features are enabled:
+///
+/// - `DeformablePlugin`: Simulates deformable bodies and fluids made of particles (requires the `deformable` feature).
+/// - `FracturePlugin`: Breaks convex bodies into fragments at runtime (requires the `fracture` feature).
+/// - `RagdollPlugin`: Builds ragdolls from skinned meshes (only in 3D, requires the `ragdoll` feature).
+///
 /// Refer to the documentation of the plugins for more information about their responsibilities and implementations.
 ///
 /// You can also find more information regarding the engine's general plugin architecture [here](plugins).
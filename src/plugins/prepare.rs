@@ -8,11 +8,18 @@
 use crate::prelude::*;
 #[cfg(all(feature = "3d", feature = "async-collider"))]
 use bevy::scene::SceneInstance;
+#[cfg(feature = "async-collider")]
+use bevy::tasks::AsyncComputeTaskPool;
 use bevy::{
     ecs::query::Has,
     prelude::*,
     utils::{intern::Interned, HashMap},
 };
+#[cfg(feature = "async-collider")]
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, OnceLock,
+};
 
 /// Runs systems at the start of each physics frame; initializes [rigid bodies](RigidBody)
 /// and [colliders](Collider) and updates components.
@@ -167,8 +174,41 @@ impl Plugin for PreparePlugin {
             ),
         );
 
+        #[cfg(feature = "2d")]
+        app.add_systems(
+            self.schedule,
+            update_tile_grid_colliders.in_set(PrepareSet::PreInit),
+        );
+
+        #[cfg(feature = "collider-asset")]
+        app.init_asset::<ColliderAsset>()
+            .init_asset_loader::<ColliderAssetLoader>()
+            .add_systems(Update, update_collider_assets);
+
+        #[cfg(feature = "async-collider")]
+        app.add_event::<ColliderComputed>().add_systems(
+            Update,
+            (
+                insert_pending_colliders.before(init_async_colliders),
+                init_async_colliders,
+                handle_collider_tasks,
+            ),
+        );
+
+        #[cfg(all(feature = "2d", feature = "async-collider"))]
+        app.add_systems(
+            Update,
+            init_async_image_colliders.after(insert_pending_colliders),
+        );
+
         #[cfg(all(feature = "3d", feature = "async-collider"))]
-        app.add_systems(Update, (init_async_colliders, init_async_scene_colliders));
+        app.add_systems(
+            Update,
+            (
+                init_async_scene_colliders.after(insert_pending_colliders),
+                handle_collision_only_meshes.after(handle_collider_tasks),
+            ),
+        );
     }
 }
 
@@ -514,93 +554,347 @@ fn init_colliders(
     }
 }
 
-/// Creates [`Collider`]s from [`AsyncCollider`]s if the meshes have become available.
-#[cfg(all(feature = "3d", feature = "async-collider"))]
+/// Rebuilds the colliders of the chunks of [`TileGridCollider`]s whose tiles have changed.
+///
+/// The old chunk entities are despawned, and a new [`TileGridChunk`] child entity is spawned
+/// for each set of tile properties in the chunk.
+#[cfg(feature = "2d")]
+pub fn update_tile_grid_colliders(
+    mut commands: Commands,
+    mut grids: Query<(Entity, &mut TileGridCollider, Option<&Children>), Changed<TileGridCollider>>,
+    chunks: Query<&TileGridChunk>,
+) {
+    for (entity, mut grid, children) in &mut grids {
+        // Clearing the dirty chunks shouldn't count as a change
+        let dirty_chunks = grid.bypass_change_detection().take_dirty_chunks();
+
+        let rebuilt_chunks: Vec<UVec2> = match &dirty_chunks {
+            Some(dirty_chunks) if dirty_chunks.is_empty() => continue,
+            Some(dirty_chunks) => dirty_chunks.iter().copied().collect(),
+            None => {
+                let count = grid.chunk_count();
+                (0..count.y)
+                    .flat_map(|y| (0..count.x).map(move |x| UVec2::new(x, y)))
+                    .collect()
+            }
+        };
+
+        // Despawn the old colliders of the rebuilt chunks
+        for &child in children.into_iter().flatten() {
+            let Ok(chunk) = chunks.get(child) else {
+                continue;
+            };
+            let rebuilt = match &dirty_chunks {
+                Some(dirty_chunks) => dirty_chunks.contains(&chunk.chunk),
+                None => true,
+            };
+            if rebuilt {
+                commands.entity(child).despawn_recursive();
+            }
+        }
+
+        for chunk in rebuilt_chunks {
+            for (tile, collider) in grid.chunk_colliders(chunk) {
+                let mut chunk_entity = commands.spawn((
+                    TileGridChunk { chunk },
+                    collider,
+                    TransformBundle::default(),
+                ));
+                if let Some(layers) = tile.layers {
+                    chunk_entity.insert(layers);
+                }
+                if let Some(friction) = tile.friction {
+                    chunk_entity.insert(friction);
+                }
+                let chunk_entity = chunk_entity.id();
+                commands.entity(entity).add_child(chunk_entity);
+            }
+        }
+    }
+}
+
+/// Inserts [`ColliderPending`] for entities whose [`Collider`]s are computed in the background
+/// as soon as the components describing the colliders are added, even if the assets haven't been loaded yet.
+#[cfg(feature = "async-collider")]
+fn insert_pending_colliders(
+    mut commands: Commands,
+    #[cfg(feature = "2d")] added: Query<
+        Entity,
+        Or<(Added<AsyncCollider>, Added<AsyncImageCollider>)>,
+    >,
+    #[cfg(feature = "3d")] added: Query<
+        Entity,
+        Or<(Added<AsyncCollider>, Added<AsyncSceneCollider>)>,
+    >,
+) {
+    for entity in &added {
+        commands.entity(entity).insert(ColliderPending);
+    }
+}
+
+/// Starts computing [`Collider`]s from [`AsyncCollider`]s in the background if the meshes have become available.
+///
+/// The colliders are inserted by `handle_collider_tasks` once they have been computed.
+#[cfg(feature = "async-collider")]
 pub fn init_async_colliders(
     mut commands: Commands,
     meshes: Res<Assets<Mesh>>,
-    async_colliders: Query<(Entity, &Handle<Mesh>, &AsyncCollider)>,
+    #[cfg(feature = "2d")] async_colliders: Query<(
+        Entity,
+        &bevy::sprite::Mesh2dHandle,
+        &AsyncCollider,
+    )>,
+    #[cfg(feature = "3d")] async_colliders: Query<(Entity, &Handle<Mesh>, &AsyncCollider)>,
+    #[cfg(feature = "collider-asset")] decomposition_cache: Option<Res<ConvexDecompositionCache>>,
 ) {
     for (entity, mesh_handle, async_collider) in async_colliders.iter() {
+        #[cfg(feature = "2d")]
+        let mesh_handle = &mesh_handle.0;
+
         if let Some(mesh) = meshes.get(mesh_handle) {
-            let collider = match &async_collider.0 {
-                ComputedCollider::TriMesh => Collider::trimesh_from_mesh(mesh),
-                ComputedCollider::TriMeshWithFlags(flags) => {
-                    Collider::trimesh_from_mesh_with_config(mesh, *flags)
-                }
-                ComputedCollider::ConvexHull => Collider::convex_hull_from_mesh(mesh),
-                ComputedCollider::ConvexDecomposition(params) => {
-                    Collider::convex_decomposition_from_mesh_with_config(mesh, params)
+            let task = spawn_mesh_collider_task(
+                async_collider.0.clone(),
+                mesh.clone(),
+                #[cfg(feature = "collider-asset")]
+                decomposition_cache.as_deref().cloned(),
+            );
+            commands
+                .entity(entity)
+                .insert((task, ColliderPending))
+                .remove::<AsyncCollider>();
+        }
+    }
+}
+
+/// A background task that computes the [`Collider`] of an entity with the [`ColliderPending`] component.
+///
+/// The task is detached, and its result is set once the computation has finished. This works
+/// with both the multi-threaded and the single-threaded task pools. Dropping the task, for example
+/// by despawning the entity, cancels the computation if it hasn't started yet.
+#[cfg(feature = "async-collider")]
+#[derive(Component)]
+struct ColliderTask {
+    result: Arc<OnceLock<Option<Collider>>>,
+    cancelled: Arc<AtomicBool>,
+}
+
+#[cfg(feature = "async-collider")]
+impl ColliderTask {
+    /// Spawns a task that runs `compute` on the `AsyncComputeTaskPool`.
+    fn spawn(compute: impl FnOnce() -> Option<Collider> + Send + 'static) -> Self {
+        let result = Arc::new(OnceLock::new());
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let (task_result, task_cancelled) = (result.clone(), cancelled.clone());
+        AsyncComputeTaskPool::get()
+            .spawn(async move {
+                if !task_cancelled.load(Ordering::Relaxed) {
+                    let _ = task_result.set(compute());
                 }
-            };
-            if let Some(collider) = collider {
-                commands
-                    .entity(entity)
-                    .insert(collider)
-                    .remove::<AsyncCollider>();
-            } else {
-                error!("Unable to generate collider from mesh {:?}", mesh);
-            }
+            })
+            .detach();
+        Self { result, cancelled }
+    }
+}
+
+#[cfg(feature = "async-collider")]
+impl Drop for ColliderTask {
+    fn drop(&mut self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Spawns a task that computes a [`Collider`] from a `Mesh` on the `AsyncComputeTaskPool`.
+#[cfg(feature = "async-collider")]
+fn spawn_mesh_collider_task(
+    shape: ComputedCollider,
+    mesh: Mesh,
+    #[cfg(feature = "collider-asset")] decomposition_cache: Option<ConvexDecompositionCache>,
+) -> ColliderTask {
+    #[cfg(feature = "collider-asset")]
+    let compute = move || shape.compute_cached(&mesh, decomposition_cache.as_ref());
+    #[cfg(not(feature = "collider-asset"))]
+    let compute = move || shape.compute(&mesh);
+    ColliderTask::spawn(compute)
+}
+
+/// Inserts the [`Collider`]s computed by finished [`ColliderTask`]s, removes [`ColliderPending`]
+/// and sends [`ColliderComputed`] events.
+#[cfg(feature = "async-collider")]
+fn handle_collider_tasks(
+    mut commands: Commands,
+    tasks: Query<(Entity, &ColliderTask, Option<&Name>)>,
+    mut computed_events: EventWriter<ColliderComputed>,
+) {
+    for (entity, task, name) in &tasks {
+        let Some(collider) = task.result.get().cloned() else {
+            continue;
+        };
+        let succeeded = collider.is_some();
+
+        let mut entity_commands = commands.entity(entity);
+        entity_commands.remove::<(ColliderTask, ColliderPending)>();
+        if let Some(collider) = collider {
+            entity_commands.insert(collider);
+        } else if let Some(name) = name {
+            error!("Unable to generate collider for entity {entity:?} with name {name}");
+        } else {
+            error!("Unable to generate collider for entity {entity:?}");
+        }
+
+        computed_events.send(ColliderComputed { entity, succeeded });
+    }
+}
+
+/// Inserts the [`Collider`]s of [`ColliderAsset`]s for entities with a `Handle<ColliderAsset>`
+/// once the assets have been loaded, and replaces them when the assets are modified.
+#[cfg(feature = "collider-asset")]
+pub fn update_collider_assets(
+    mut commands: Commands,
+    collider_assets: Res<Assets<ColliderAsset>>,
+    mut asset_events: EventReader<AssetEvent<ColliderAsset>>,
+    handles: Query<(Entity, Ref<Handle<ColliderAsset>>)>,
+) {
+    let changed_assets: bevy::utils::HashSet<AssetId<ColliderAsset>> = asset_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Added { id }
+            | AssetEvent::Modified { id }
+            | AssetEvent::LoadedWithDependencies { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+
+    for (entity, handle) in &handles {
+        if !handle.is_changed() && !changed_assets.contains(&handle.id()) {
+            continue;
+        }
+        if let Some(collider_asset) = collider_assets.get(&*handle) {
+            // Cloning the collider only clones a reference to its shape
+            commands.entity(entity).insert(collider_asset.0.clone());
         }
     }
 }
 
-/// Creates [`Collider`]s from [`AsyncSceneCollider`]s if the scenes have become available.
+/// Starts computing [`Collider`]s from [`AsyncImageCollider`]s in the background if the images have become available.
+#[cfg(all(feature = "2d", feature = "async-collider"))]
+pub fn init_async_image_colliders(
+    mut commands: Commands,
+    images: Res<Assets<Image>>,
+    async_colliders: Query<(Entity, &Handle<Image>, &AsyncImageCollider)>,
+) {
+    for (entity, image_handle, async_collider) in async_colliders.iter() {
+        if let Some(image) = images.get(image_handle) {
+            let image = image.clone();
+            let async_collider = async_collider.clone();
+            let task = ColliderTask::spawn(move || {
+                async_collider.shape.compute(
+                    &image,
+                    async_collider.alpha_threshold,
+                    async_collider.simplify_tolerance,
+                )
+            });
+            commands
+                .entity(entity)
+                .insert((task, ColliderPending))
+                .remove::<AsyncImageCollider>();
+        }
+    }
+}
+
+/// Starts computing [`Collider`]s for the meshes of [`AsyncSceneCollider`]s in the background
+/// if the scenes have become available.
 #[cfg(all(feature = "3d", feature = "async-collider"))]
+#[allow(clippy::too_many_arguments)]
 pub fn init_async_scene_colliders(
     mut commands: Commands,
     meshes: Res<Assets<Mesh>>,
     scene_spawner: Res<SceneSpawner>,
     async_colliders: Query<(Entity, &SceneInstance, &AsyncSceneCollider)>,
     children: Query<&Children>,
-    mesh_handles: Query<(&Name, &Handle<Mesh>)>,
+    names: Query<&Name>,
+    mesh_handles: Query<(&Name, &Handle<Mesh>, Option<&Parent>)>,
+    #[cfg(feature = "collider-asset")] decomposition_cache: Option<Res<ConvexDecompositionCache>>,
 ) {
     for (scene_entity, scene_instance, async_scene_collider) in async_colliders.iter() {
         if scene_spawner.instance_is_ready(**scene_instance) {
             for child_entity in children.iter_descendants(scene_entity) {
-                if let Ok((name, handle)) = mesh_handles.get(child_entity) {
+                if let Ok((name, handle, parent)) = mesh_handles.get(child_entity) {
+                    let node_name = parent.and_then(|parent| names.get(parent.get()).ok());
                     let Some(collider_data) = async_scene_collider
-                        .meshes_by_name
-                        .get(name.as_str())
-                        .cloned()
-                        .unwrap_or(
-                            async_scene_collider
-                                .default_shape
-                                .clone()
-                                .map(|shape| AsyncSceneColliderData { shape, ..default() }),
-                        )
+                        .data_for_mesh(name.as_str(), node_name.map(|name| name.as_str()))
                     else {
                         continue;
                     };
 
                     let mesh = meshes.get(handle).expect("mesh should already be loaded");
 
-                    let collider = match collider_data.shape {
-                        ComputedCollider::TriMesh => Collider::trimesh_from_mesh(mesh),
-                        ComputedCollider::TriMeshWithFlags(flags) => {
-                            Collider::trimesh_from_mesh_with_config(mesh, flags)
-                        }
-                        ComputedCollider::ConvexHull => Collider::convex_hull_from_mesh(mesh),
-                        ComputedCollider::ConvexDecomposition(params) => {
-                            Collider::convex_decomposition_from_mesh_with_config(mesh, &params)
-                        }
-                    };
-                    if let Some(collider) = collider {
-                        commands.entity(child_entity).insert((
-                            collider,
-                            collider_data.layers,
-                            ColliderDensity(collider_data.density),
+                    let task = spawn_mesh_collider_task(
+                        collider_data.shape,
+                        mesh.clone(),
+                        #[cfg(feature = "collider-asset")]
+                        decomposition_cache.as_deref().cloned(),
+                    );
+                    let mut child_commands = commands.entity(child_entity);
+                    child_commands.insert((
+                        task,
+                        ColliderPending,
+                        collider_data.layers,
+                        ColliderDensity(collider_data.density),
+                    ));
+                    if collider_data.sensor {
+                        child_commands.insert(Sensor);
+                    }
+                    if collider_data.collision_only
+                        && async_scene_collider.collision_mesh_handling
+                            != CollisionMeshHandling::Keep
+                    {
+                        child_commands.insert(CollisionOnlyMesh(
+                            async_scene_collider.collision_mesh_handling,
                         ));
-                    } else {
-                        error!(
-                            "unable to generate collider from mesh {:?} with name {}",
-                            mesh, name
-                        );
                     }
                 }
             }
 
-            commands.entity(scene_entity).remove::<AsyncSceneCollider>();
+            commands
+                .entity(scene_entity)
+                .remove::<(AsyncSceneCollider, ColliderPending)>();
+        }
+    }
+}
+
+/// A component for collision-only meshes of an [`AsyncSceneCollider`] that should be
+/// hidden or removed once their colliders have been built.
+#[cfg(all(feature = "3d", feature = "async-collider"))]
+#[derive(Component)]
+struct CollisionOnlyMesh(CollisionMeshHandling);
+
+/// Hides or removes collision-only meshes once their colliders have been built.
+///
+/// If a collider couldn't be generated, the mesh is kept so that the missing collider is noticeable.
+#[cfg(all(feature = "3d", feature = "async-collider"))]
+fn handle_collision_only_meshes(
+    mut commands: Commands,
+    mut computed_events: EventReader<ColliderComputed>,
+    meshes: Query<&CollisionOnlyMesh>,
+) {
+    for &ColliderComputed { entity, succeeded } in computed_events.read() {
+        let Ok(collision_only_mesh) = meshes.get(entity) else {
+            continue;
+        };
+
+        let mut entity_commands = commands.entity(entity);
+        entity_commands.remove::<CollisionOnlyMesh>();
+        if !succeeded {
+            continue;
+        }
+        match collision_only_mesh.0 {
+            CollisionMeshHandling::Keep => (),
+            CollisionMeshHandling::Hide => {
+                entity_commands.insert(Visibility::Hidden);
+            }
+            CollisionMeshHandling::Remove => {
+                entity_commands.remove::<Handle<Mesh>>();
+            }
         }
     }
 }
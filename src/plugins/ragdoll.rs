@@ -0,0 +1,785 @@
+//! Builds ragdolls from the joint hierarchies of skinned meshes.
+//!
+//! See [`RagdollPlugin`].
+
+use crate::prelude::*;
+use bevy::{
+    prelude::*,
+    render::mesh::skinning::SkinnedMesh,
+    transform::TransformSystem,
+    utils::{intern::Interned, HashMap},
+};
+
+/// Builds [`Ragdoll`]s from skinned meshes and switches them between animation and physics.
+///
+/// All systems run in the schedule that is used for running the [`PhysicsSchedule`]. The animated pose
+/// is sampled before [`PhysicsSet::Prepare`], where disabled ragdolls follow the animated bones and the
+/// [drives](RagdollDrive) of enabled ragdolls get their targets. The bones of enabled ragdolls are updated
+/// from the bodies after [`PhysicsSet::Sync`].
+///
+/// In `PostUpdate`, ragdolls are built after `TransformSystem::TransformPropagate`, so that the bones
+/// have up-to-date global transforms, and the bones are updated before it. Other schedules don't propagate
+/// transforms, so ragdolls are built before the animated pose is sampled, using the global transforms
+/// of the previous frame.
+///
+/// The plugin isn't included in the [`PhysicsPlugins`], so it must be added separately after them.
+pub struct RagdollPlugin {
+    schedule: Interned<dyn ScheduleLabel>,
+}
+
+impl RagdollPlugin {
+    /// Creates a [`RagdollPlugin`] with the schedule that is used for running the [`PhysicsSchedule`].
+    ///
+    /// The default schedule is `PostUpdate`.
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+        }
+    }
+}
+
+impl Default for RagdollPlugin {
+    fn default() -> Self {
+        Self::new(PostUpdate)
+    }
+}
+
+impl Plugin for RagdollPlugin {
+    fn build(&self, app: &mut App) {
+        let build_ragdolls = (despawn_orphaned_ragdoll_bodies, build_ragdolls).chain();
+        let drive_bones = drive_bones.after(PhysicsSet::Sync);
+
+        // Transforms are only propagated in `PostUpdate`
+        if self.schedule == PostUpdate.intern() {
+            app.add_systems(
+                self.schedule,
+                (
+                    build_ragdolls.after(TransformSystem::TransformPropagate),
+                    drive_bones.before(TransformSystem::TransformPropagate),
+                ),
+            );
+        } else {
+            app.add_systems(
+                self.schedule,
+                (build_ragdolls.before(follow_animation), drive_bones),
+            );
+        }
+
+        app.add_systems(
+            self.schedule,
+            (follow_animation, update_ragdoll_drives)
+                .chain()
+                .before(PhysicsSet::Prepare),
+        );
+    }
+}
+
+/// A component that turns the skinned meshes below an entity into a ragdoll.
+///
+/// The joints of all `SkinnedMesh` descendants are matched with [bone profiles](RagdollBone) by their `Name`.
+/// For each matched joint, a rigid body with a capsule [`Collider`] is spawned. The capsule starts at the joint
+/// and reaches to its first child joint, or it extends along the joint's local `Y` axis if the
+/// [length](RagdollBone::length) is set. Each body is connected to the body of its closest simulated ancestor
+/// with a [`SphericalJoint`] or a [`RevoluteJoint`] anchored at the joint.
+///
+/// All bodies have the rotation of the ragdoll entity when the ragdoll is built, so joint axes
+/// and limits are given in the local space of the ragdoll entity in its bind pose.
+///
+/// ## Switching between animation and physics
+///
+/// When the ragdoll is [disabled](Ragdoll::enabled), the bodies are [kinematic](RigidBody::Kinematic)
+/// and follow the animated bones, so they can still push other bodies and be hit by spatial queries.
+/// When the ragdoll is enabled, the bodies become [dynamic](RigidBody::Dynamic) and keep the velocity
+/// of the animation, and the rotations of the bones are driven by the bodies. The translation of the topmost
+/// simulated bones is also driven by their bodies, so the whole skeleton moves with the ragdoll.
+///
+/// ## Active ragdolls
+///
+/// Bones with a [`RagdollDrive`] are pulled toward the animated pose by a [`RotationMotor`] on their
+/// [`SphericalJoint`] while the ragdoll is enabled. The [animation blend](Ragdoll::animation_blend) sets
+/// the stiffness of the drives, from a fully physical ragdoll at `0.0` to drives that follow
+/// the animation as closely as their maximum torque allows at `1.0`. Bones connected with a
+/// [revolute joint](RagdollJoint::Revolute) are not driven.
+///
+/// The animated pose is read from the local `Transform`s of the bones before [`PhysicsSet::Prepare`],
+/// so animations should be applied before it. The bones of enabled ragdolls are overwritten with the
+/// simulated pose after [`PhysicsSet::Sync`], so the animation has to write them again every frame.
+///
+/// Every body has a [`RagdollBody`] component that links it to its bone. The bodies and joints are despawned
+/// when the ragdoll entity is despawned or the component is removed, and they are rebuilt when the
+/// [bone profiles](Ragdoll::bones) or [collision layers](Ragdoll::layers) are changed.
+///
+/// ## Self-collisions
+///
+/// The capsules of neighboring bones touch at the joints, which can make the ragdoll jitter. Use
+/// [`Ragdoll::with_collision_layers`] with a layer that doesn't interact with itself to avoid collisions
+/// between the bodies of the ragdoll.
+///
+/// ## Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// # #[cfg(feature = "f32")]
+/// fn setup(mut commands: Commands, assets: Res<AssetServer>) {
+///     let arm = RagdollBone::new(0.06, 2.0).with_joint(RagdollJoint::Spherical {
+///         swing_axis: Vec3::Z,
+///         twist_axis: Vec3::X,
+///         swing_limit: Some(AngleLimit::new(-1.5, 1.5)),
+///         twist_limit: Some(AngleLimit::new(-0.5, 0.5)),
+///     });
+///     let forearm = RagdollBone::new(0.05, 1.5)
+///         .with_joint(RagdollJoint::Revolute {
+///             axis: Vec3::Y,
+///             angle_limit: Some(AngleLimit::new(0.0, 2.5)),
+///         });
+///
+///     commands.spawn((
+///         SceneBundle {
+///             scene: assets.load("character.gltf#Scene0"),
+///             ..default()
+///         },
+///         Ragdoll::new()
+///             .with_bone("hips", RagdollBone::new(0.15, 10.0))
+///             .with_bone("spine", RagdollBone::new(0.15, 15.0))
+///             .with_bone("head", RagdollBone::new(0.12, 5.0).with_length(0.25))
+///             .with_bone("upper_arm.L", arm.clone())
+///             .with_bone("upper_arm.R", arm)
+///             .with_bone("forearm.L", forearm.clone())
+///             .with_bone("forearm.R", forearm),
+///     ));
+/// }
+///
+/// fn die(mut ragdolls: Query<&mut Ragdoll>) {
+///     for mut ragdoll in &mut ragdolls {
+///         ragdoll.enabled = true;
+///     }
+/// }
+/// ```
+#[derive(Component, Clone, Debug, Default, PartialEq)]
+pub struct Ragdoll {
+    /// The bone profiles by the `Name` of the joint.
+    pub bones: HashMap<String, RagdollBone>,
+    /// The profile used for joints that are not in [`bones`](Ragdoll::bones).
+    /// If `None`, those joints are not simulated and follow their parents.
+    pub default_bone: Option<RagdollBone>,
+    /// If true, the bones are driven by physics. Otherwise, the bodies follow the animated bones.
+    pub enabled: bool,
+    /// How strongly the [drives](RagdollDrive) of an enabled ragdoll pull the bodies toward the animated pose,
+    /// from `0.0` (fully physical) to `1.0` (as animated as the drives allow). The default is `0.0`.
+    pub animation_blend: Scalar,
+    /// The [`CollisionLayers`] of the bodies.
+    pub layers: CollisionLayers,
+    /// The bodies ordered so that parents come before their children.
+    bodies: Vec<Entity>,
+    /// The properties that the bodies were built with, or `None` if the ragdoll hasn't been built yet.
+    built: Option<RagdollBuild>,
+}
+
+impl Ragdoll {
+    /// Creates a new disabled [`Ragdoll`] without any bones.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a profile for the joint with the given name.
+    pub fn with_bone(mut self, name: impl Into<String>, bone: RagdollBone) -> Self {
+        self.bones.insert(name.into(), bone);
+        self
+    }
+
+    /// Sets the profile used for joints that don't have their own profile.
+    pub fn with_default_bone(mut self, bone: RagdollBone) -> Self {
+        self.default_bone = Some(bone);
+        self
+    }
+
+    /// Determines if the bones are driven by physics.
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Sets how strongly the drives pull the bodies toward the animated pose, from `0.0` to `1.0`.
+    pub fn with_animation_blend(mut self, animation_blend: Scalar) -> Self {
+        self.animation_blend = animation_blend;
+        self
+    }
+
+    /// Sets the [`CollisionLayers`] of the bodies.
+    pub fn with_collision_layers(mut self, layers: CollisionLayers) -> Self {
+        self.layers = layers;
+        self
+    }
+
+    /// Returns the spawned bodies, ordered so that parents come before their children.
+    /// The list is empty until the ragdoll has been built.
+    pub fn bodies(&self) -> &[Entity] {
+        &self.bodies
+    }
+
+    /// Returns the profile used for the joint with the given name.
+    fn profile(&self, name: &str) -> Option<&RagdollBone> {
+        self.bones.get(name).or(self.default_bone.as_ref())
+    }
+}
+
+/// The properties of a [`Ragdoll`] that its bodies are built from.
+#[derive(Clone, Debug, PartialEq)]
+struct RagdollBuild {
+    bones: HashMap<String, RagdollBone>,
+    default_bone: Option<RagdollBone>,
+    layers: CollisionLayers,
+}
+
+impl RagdollBuild {
+    fn new(ragdoll: &Ragdoll) -> Self {
+        Self {
+            bones: ragdoll.bones.clone(),
+            default_bone: ragdoll.default_bone.clone(),
+            layers: ragdoll.layers,
+        }
+    }
+
+    /// Returns true if the ragdoll still has the properties that its bodies were built with.
+    fn matches(&self, ragdoll: &Ragdoll) -> bool {
+        self.bones == ragdoll.bones
+            && self.default_bone == ragdoll.default_bone
+            && self.layers == ragdoll.layers
+    }
+}
+
+/// The physical properties of a single bone of a [`Ragdoll`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct RagdollBone {
+    /// The radius of the capsule.
+    pub radius: Scalar,
+    /// The length of the capsule along the joint's local `Y` axis.
+    /// If `None`, the capsule reaches to the first child joint.
+    pub length: Option<Scalar>,
+    /// The mass of the body.
+    pub mass: Scalar,
+    /// The joint that connects the body to its parent.
+    pub joint: RagdollJoint,
+    /// The drive that pulls the body toward the animated pose.
+    pub drive: Option<RagdollDrive>,
+}
+
+impl RagdollBone {
+    /// Creates a new [`RagdollBone`] with the given capsule radius and mass,
+    /// connected to its parent with a [spherical joint](RagdollJoint::Spherical) without limits.
+    pub fn new(radius: Scalar, mass: Scalar) -> Self {
+        Self {
+            radius,
+            length: None,
+            mass,
+            joint: RagdollJoint::default(),
+            drive: None,
+        }
+    }
+
+    /// Sets the length of the capsule along the joint's local `Y` axis.
+    pub fn with_length(mut self, length: Scalar) -> Self {
+        self.length = Some(length);
+        self
+    }
+
+    /// Sets the joint that connects the body to its parent.
+    pub fn with_joint(mut self, joint: RagdollJoint) -> Self {
+        self.joint = joint;
+        self
+    }
+
+    /// Sets the drive that pulls the body toward the animated pose.
+    pub fn with_drive(mut self, drive: RagdollDrive) -> Self {
+        self.drive = Some(drive);
+        self
+    }
+}
+
+/// A drive that pulls a [`RagdollBone`] toward its animated rotation relative to its parent.
+///
+/// See [active ragdolls](Ragdoll#active-ragdolls).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RagdollDrive {
+    /// The compliance of the drive at an [animation blend](Ragdoll::animation_blend) of `0.5`.
+    /// The compliance is the inverse of stiffness.
+    pub compliance: Scalar,
+    /// The maximum torque that the drive can apply.
+    pub max_torque: Scalar,
+}
+
+impl RagdollDrive {
+    /// Creates a new [`RagdollDrive`] with the given compliance and maximum torque.
+    pub fn new(compliance: Scalar, max_torque: Scalar) -> Self {
+        Self {
+            compliance,
+            max_torque,
+        }
+    }
+
+    /// Returns the [`RotationMotor`] for the given target rotation and animation blend.
+    ///
+    /// The compliance is scaled by `(1 - blend) / blend`, so the drive is disabled at a blend of `0.0`
+    /// and has no compliance at a blend of `1.0`.
+    fn motor(&self, target_rotation: Rotation, blend: Scalar) -> Option<RotationMotor> {
+        let blend = blend.clamp(0.0, 1.0);
+        (blend > 0.0).then(|| {
+            RotationMotor::new(target_rotation)
+                .with_compliance(self.compliance * (1.0 - blend) / blend)
+                .with_max_torque(self.max_torque)
+        })
+    }
+}
+
+/// The joint that connects a [`RagdollBone`] to its parent. The axes are in the local space
+/// of the [`Ragdoll`] entity in its bind pose.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RagdollJoint {
+    /// A [`SphericalJoint`] for bones like shoulders, hips and the neck.
+    Spherical {
+        /// The axis that the bone can swing around.
+        swing_axis: Vector3,
+        /// The axis that the bone can twist around.
+        twist_axis: Vector3,
+        /// The limits of the rotation around the `swing_axis`.
+        swing_limit: Option<AngleLimit>,
+        /// The limits of the rotation around the `twist_axis`.
+        twist_limit: Option<AngleLimit>,
+    },
+    /// A [`RevoluteJoint`] for hinge-like bones like elbows and knees.
+    Revolute {
+        /// The axis that the bone rotates around.
+        axis: Vector,
+        /// The limits of the rotation around the `axis`.
+        angle_limit: Option<AngleLimit>,
+    },
+}
+
+impl Default for RagdollJoint {
+    fn default() -> Self {
+        Self::Spherical {
+            swing_axis: Vector3::X,
+            twist_axis: Vector3::Y,
+            swing_limit: None,
+            twist_limit: None,
+        }
+    }
+}
+
+/// A component for the rigid bodies of a [`Ragdoll`].
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct RagdollBody {
+    /// The entity with the [`Ragdoll`] component.
+    pub ragdoll: Entity,
+    /// The joint entity of the skinned mesh that the body belongs to.
+    pub bone: Entity,
+    /// The joint connecting the body to its parent body, if it has one.
+    pub joint: Option<Entity>,
+    /// The rotation of the bone relative to the body.
+    bone_rotation: Quat,
+    /// The drive that pulls the body toward the animated pose.
+    drive: Option<RagdollDrive>,
+    /// The velocities of the animated bone, applied to the body when the ragdoll is enabled.
+    linear_velocity: Vector,
+    angular_velocity: Vector,
+}
+
+/// Despawns the bodies and joints of ragdolls that have been despawned or have had their [`Ragdoll`] removed.
+fn despawn_orphaned_ragdoll_bodies(
+    mut commands: Commands,
+    bodies: Query<(Entity, &RagdollBody)>,
+    ragdolls: Query<(), With<Ragdoll>>,
+) {
+    for (entity, body) in &bodies {
+        if !ragdolls.contains(body.ragdoll) {
+            commands.entity(entity).despawn_recursive();
+            if let Some(joint) = body.joint {
+                commands.entity(joint).despawn();
+            }
+        }
+    }
+}
+
+/// Spawns the bodies and joints for ragdolls whose skinned meshes have been spawned,
+/// and rebuilds them when the properties they were built with have changed.
+#[allow(clippy::type_complexity)]
+fn build_ragdolls(
+    mut commands: Commands,
+    mut ragdolls: Query<(Entity, &mut Ragdoll, &GlobalTransform)>,
+    ragdoll_bodies: Query<&RagdollBody>,
+    skinned_meshes: Query<&SkinnedMesh>,
+    hierarchy: Query<(Option<&Children>, Option<&Parent>, Option<&Name>)>,
+    global_transforms: Query<&GlobalTransform>,
+) {
+    for (ragdoll_entity, mut ragdoll, ragdoll_transform) in &mut ragdolls {
+        if let Some(built) = &ragdoll.built {
+            // Enabling the ragdoll or changing the animation blend doesn't need new bodies
+            if !ragdoll.is_changed() || built.matches(&ragdoll) {
+                continue;
+            }
+
+            for body in std::mem::take(&mut ragdoll.bodies) {
+                if let Some(joint) = ragdoll_bodies.get(body).ok().and_then(|body| body.joint) {
+                    commands.entity(joint).despawn();
+                }
+                commands.entity(body).despawn_recursive();
+            }
+            ragdoll.built = None;
+        }
+
+        // Collect the joints of all skinned meshes below the ragdoll
+        let mut joints: Vec<Entity> = vec![];
+        let mut stack = vec![ragdoll_entity];
+        while let Some(entity) = stack.pop() {
+            if let Ok(skinned_mesh) = skinned_meshes.get(entity) {
+                for joint in skinned_mesh.joints.iter() {
+                    if !joints.contains(joint) {
+                        joints.push(*joint);
+                    }
+                }
+            }
+            if let Ok((Some(children), _, _)) = hierarchy.get(entity) {
+                stack.extend(children.iter());
+            }
+        }
+
+        // Wait for the skinned meshes to be spawned
+        if joints.is_empty() {
+            continue;
+        }
+        ragdoll.built = Some(RagdollBuild::new(&ragdoll));
+
+        // The closest ancestor of an entity that is one of the given joints
+        let ancestor_in = |entity: Entity, joints: &[Entity]| {
+            let mut current = entity;
+            while let Ok((_, Some(parent), _)) = hierarchy.get(current) {
+                if parent.get() == ragdoll_entity {
+                    return None;
+                }
+                if joints.contains(&parent.get()) {
+                    return Some(parent.get());
+                }
+                current = parent.get();
+            }
+            None
+        };
+        let depth = |entity: Entity| {
+            let mut depth = 0;
+            let mut current = entity;
+            while let Ok((_, Some(parent), _)) = hierarchy.get(current) {
+                depth += 1;
+                current = parent.get();
+            }
+            depth
+        };
+
+        // The simulated joints, ordered so that parents come before their children
+        let mut simulated: Vec<(Entity, RagdollBone)> = joints
+            .iter()
+            .filter_map(|joint| {
+                let (_, _, name) = hierarchy.get(*joint).ok()?;
+                let profile = ragdoll.profile(name.map_or("", |name| name.as_str()))?;
+                Some((*joint, profile.clone()))
+            })
+            .collect();
+        simulated.sort_by_key(|(joint, _)| depth(*joint));
+
+        if simulated.is_empty() {
+            continue;
+        }
+
+        let simulated_joints: Vec<Entity> = simulated.iter().map(|(joint, _)| *joint).collect();
+        let body_rotation = ragdoll_transform.compute_transform().rotation;
+        let inverse_body_rotation = body_rotation.inverse();
+        let mut bodies: HashMap<Entity, (Entity, Vector)> = HashMap::default();
+
+        for (joint, bone) in simulated {
+            let Ok(joint_transform) = global_transforms.get(joint) else {
+                continue;
+            };
+            let (_, bone_rotation, translation) = joint_transform.to_scale_rotation_translation();
+            let position = translation.adjust_precision();
+
+            // The end of the capsule in the local space of the body
+            let end = match bone.length {
+                Some(length) => Some((bone_rotation * Vec3::Y).adjust_precision() * length),
+                None => hierarchy
+                    .get(joint)
+                    .ok()
+                    .and_then(|(children, _, _)| children)
+                    .and_then(|children| children.iter().find(|child| joints.contains(child)))
+                    .and_then(|child| global_transforms.get(*child).ok())
+                    .map(|child| (child.translation() - translation).adjust_precision()),
+            }
+            .map(|end| inverse_body_rotation.adjust_precision() * end);
+
+            let collider = match end {
+                Some(end) if end.length() > 2.0 * bone.radius => {
+                    let direction = end.normalize();
+                    Collider::capsule_endpoints(
+                        direction * bone.radius,
+                        end - direction * bone.radius,
+                        bone.radius,
+                    )
+                }
+                Some(end) => Collider::compound(vec![(
+                    end / 2.0,
+                    Rotation::default(),
+                    Collider::ball(bone.radius),
+                )]),
+                None => Collider::ball(bone.radius),
+            };
+            let density = bone.mass / collider.mass_properties(1.0).mass.0;
+
+            let body = commands
+                .spawn((
+                    if ragdoll.enabled {
+                        RigidBody::Dynamic
+                    } else {
+                        RigidBody::Kinematic
+                    },
+                    Position(position),
+                    Rotation(body_rotation.adjust_precision()),
+                    collider,
+                    ColliderDensity(density),
+                    ragdoll.layers,
+                    TransformBundle::from_transform(
+                        Transform::from_translation(translation).with_rotation(body_rotation),
+                    ),
+                ))
+                .id();
+
+            // Connect the body to the body of the closest simulated ancestor
+            let parent_body = ancestor_in(joint, &simulated_joints)
+                .and_then(|parent| bodies.get(&parent).copied());
+            let joint_entity = parent_body.map(|(parent_body, parent_position)| {
+                let anchor =
+                    inverse_body_rotation.adjust_precision() * (position - parent_position);
+                match bone.joint {
+                    RagdollJoint::Spherical {
+                        swing_axis,
+                        twist_axis,
+                        swing_limit,
+                        twist_limit,
+                    } => commands
+                        .spawn(SphericalJoint {
+                            swing_axis,
+                            twist_axis,
+                            swing_limit,
+                            twist_limit,
+                            ..SphericalJoint::new(parent_body, body).with_local_anchor_1(anchor)
+                        })
+                        .id(),
+                    RagdollJoint::Revolute { axis, angle_limit } => commands
+                        .spawn(RevoluteJoint {
+                            angle_limit,
+                            ..RevoluteJoint::new(parent_body, body)
+                                .with_local_anchor_1(anchor)
+                                .with_aligned_axis(axis)
+                        })
+                        .id(),
+                }
+            });
+
+            commands.entity(body).insert(RagdollBody {
+                ragdoll: ragdoll_entity,
+                bone: joint,
+                joint: joint_entity,
+                bone_rotation: inverse_body_rotation * bone_rotation,
+                drive: bone.drive,
+                linear_velocity: Vector::ZERO,
+                angular_velocity: Vector::ZERO,
+            });
+
+            bodies.insert(joint, (body, position));
+            ragdoll.bodies.push(body);
+        }
+    }
+}
+
+/// Makes the bodies of disabled ragdolls follow the animated bones, and switches
+/// the bodies between kinematic and dynamic when ragdolls are enabled or disabled.
+fn follow_animation(
+    ragdolls: Query<&Ragdoll>,
+    mut bodies: Query<(
+        &mut RagdollBody,
+        &mut RigidBody,
+        &mut Position,
+        &mut Rotation,
+        &mut LinearVelocity,
+        &mut AngularVelocity,
+    )>,
+    bones: Query<&GlobalTransform>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for (mut body, mut rb, mut position, mut rotation, mut lin_vel, mut ang_vel) in &mut bodies {
+        let Ok(ragdoll) = ragdolls.get(body.ragdoll) else {
+            continue;
+        };
+
+        if ragdoll.enabled {
+            if *rb != RigidBody::Dynamic {
+                // Keep the motion of the animation
+                *rb = RigidBody::Dynamic;
+                lin_vel.0 = body.linear_velocity;
+                ang_vel.0 = body.angular_velocity;
+            }
+            continue;
+        }
+
+        if *rb != RigidBody::Kinematic {
+            *rb = RigidBody::Kinematic;
+            lin_vel.0 = Vector::ZERO;
+            ang_vel.0 = Vector::ZERO;
+        }
+
+        let Ok(bone_transform) = bones.get(body.bone) else {
+            continue;
+        };
+        let (_, bone_rotation, translation) = bone_transform.to_scale_rotation_translation();
+        let new_position = translation.adjust_precision();
+        let new_rotation = (bone_rotation * body.bone_rotation.inverse()).adjust_precision();
+
+        // Track the velocity of the animation
+        if delta_secs > 0.0 {
+            body.linear_velocity = (new_position - position.0) / delta_secs;
+            let (axis, angle) = (new_rotation * rotation.0.inverse()).to_axis_angle();
+            let angle = if angle > PI { angle - 2.0 * PI } else { angle };
+            body.angular_velocity = axis * angle / delta_secs;
+        }
+
+        position.0 = new_position;
+        *rotation = Rotation(new_rotation);
+    }
+}
+
+/// Samples the animated pose of enabled ragdolls from the local transforms of the bones,
+/// and sets it as the target of the [drives](RagdollDrive).
+fn update_ragdoll_drives(
+    ragdolls: Query<(Entity, &Ragdoll, &GlobalTransform)>,
+    bodies: Query<&RagdollBody>,
+    bones: Query<(&Transform, Option<&Parent>)>,
+    mut joints: Query<&mut SphericalJoint>,
+) {
+    for (ragdoll_entity, ragdoll, ragdoll_transform) in &ragdolls {
+        let active = ragdoll.enabled && ragdoll.animation_blend > 0.0;
+
+        // The global transforms of the bones in the animated pose
+        let mut globals: HashMap<Entity, GlobalTransform> = HashMap::default();
+        globals.insert(ragdoll_entity, *ragdoll_transform);
+
+        // The rotations of the bodies in the animated pose
+        let mut rotations: HashMap<Entity, Quat> = HashMap::default();
+
+        for body_entity in ragdoll.bodies.iter() {
+            let Ok(body) = bodies.get(*body_entity) else {
+                continue;
+            };
+
+            if active {
+                let global = global_transform(body.bone, &mut globals, &|entity| {
+                    let (transform, parent) = bones.get(entity).ok()?;
+                    Some((*transform, parent.map(|parent| parent.get())))
+                });
+                let (_, bone_rotation, _) = global.to_scale_rotation_translation();
+                rotations.insert(*body_entity, bone_rotation * body.bone_rotation.inverse());
+            }
+
+            let Some(mut joint) = body.joint.and_then(|joint| joints.get_mut(joint).ok()) else {
+                continue;
+            };
+
+            // The animated rotation of the body relative to its parent body
+            let target_rotation = rotations
+                .get(&joint.entity1)
+                .zip(rotations.get(body_entity))
+                .map(|(parent_rotation, rotation)| parent_rotation.inverse() * *rotation);
+
+            let motor = match (body.drive, target_rotation) {
+                (Some(drive), Some(target_rotation)) => drive.motor(
+                    Rotation(target_rotation.adjust_precision()),
+                    ragdoll.animation_blend,
+                ),
+                _ => None,
+            };
+
+            if joint.motor != motor {
+                joint.motor = motor;
+            }
+        }
+    }
+}
+
+/// Updates the bones of enabled ragdolls to match their bodies.
+fn drive_bones(
+    ragdolls: Query<(Entity, &Ragdoll, &GlobalTransform)>,
+    bodies: Query<(&RagdollBody, &Position, &Rotation)>,
+    mut bones: Query<(&mut Transform, Option<&Parent>), Without<RigidBody>>,
+) {
+    for (ragdoll_entity, ragdoll, ragdoll_transform) in &ragdolls {
+        if !ragdoll.enabled {
+            continue;
+        }
+
+        // The updated global transforms of the bones and their ancestors
+        let mut globals: HashMap<Entity, GlobalTransform> = HashMap::default();
+        globals.insert(ragdoll_entity, *ragdoll_transform);
+
+        for body_entity in ragdoll.bodies.iter() {
+            let Ok((body, position, rotation)) = bodies.get(*body_entity) else {
+                continue;
+            };
+
+            let parent_global = match bones.get(body.bone) {
+                Ok((_, Some(parent))) => global_transform(parent.get(), &mut globals, &|entity| {
+                    let (transform, parent) = bones.get(entity).ok()?;
+                    Some((*transform, parent.map(|parent| parent.get())))
+                }),
+                _ => GlobalTransform::IDENTITY,
+            };
+            let (_, parent_rotation, _) = parent_global.to_scale_rotation_translation();
+            let bone_rotation = rotation.0.as_f32() * body.bone_rotation;
+
+            let Ok((mut transform, _)) = bones.get_mut(body.bone) else {
+                continue;
+            };
+            transform.rotation = parent_rotation.inverse() * bone_rotation;
+
+            // Only the topmost bodies move the skeleton, so the joints can't stretch it
+            if body.joint.is_none() {
+                transform.translation = parent_global
+                    .affine()
+                    .inverse()
+                    .transform_point3(position.0.as_f32());
+            }
+
+            globals.insert(body.bone, parent_global.mul_transform(*transform));
+        }
+    }
+}
+
+/// Computes the global transform of an entity from the already computed global transforms of its ancestors.
+///
+/// `local` returns the local transform and the parent of an entity.
+fn global_transform(
+    entity: Entity,
+    globals: &mut HashMap<Entity, GlobalTransform>,
+    local: &impl Fn(Entity) -> Option<(Transform, Option<Entity>)>,
+) -> GlobalTransform {
+    if let Some(global) = globals.get(&entity) {
+        return *global;
+    }
+    let Some((transform, parent)) = local(entity) else {
+        return GlobalTransform::IDENTITY;
+    };
+    let global = match parent {
+        Some(parent) => global_transform(parent, globals, local).mul_transform(transform),
+        None => GlobalTransform::from(transform),
+    };
+    globals.insert(entity, global);
+    global
+}
@@ -76,6 +76,7 @@ impl Plugin for PhysicsSetupPlugin {
             .register_type::<SleepingThreshold>()
             .register_type::<DeactivationTime>()
             .register_type::<Gravity>()
+            .register_type::<Atmosphere>()
             .register_type::<RigidBody>()
             .register_type::<Sleeping>()
             .register_type::<SleepingDisabled>()
@@ -94,11 +95,15 @@ impl Plugin for PhysicsSetupPlugin {
             .register_type::<Friction>()
             .register_type::<LinearDamping>()
             .register_type::<AngularDamping>()
+            .register_type::<Aerodynamics>()
             .register_type::<ExternalForce>()
             .register_type::<ExternalTorque>()
             .register_type::<ExternalImpulse>()
             .register_type::<ExternalAngularImpulse>()
             .register_type::<GravityScale>()
+            .register_type::<GravityOverride>()
+            .register_type::<GravityField>()
+            .register_type::<ForceEffector>()
             .register_type::<Mass>()
             .register_type::<InverseMass>()
             .register_type::<Inertia>()
@@ -97,10 +97,11 @@ type WokeUpFilter = Or<(
     Changed<ExternalImpulse>,
     Changed<ExternalAngularImpulse>,
     Changed<GravityScale>,
+    Changed<GravityOverride>,
 )>;
 
 /// Removes the [`Sleeping`] component from sleeping bodies when properties like
-/// position, rotation, velocity and external forces are changed.
+/// position, rotation, velocity, external forces and gravity are changed.
 #[allow(clippy::type_complexity)]
 fn wake_on_changed(
     mut commands: Commands,
@@ -50,6 +50,7 @@ impl Plugin for SolverPlugin {
                 solve_constraint::<SphericalJoint, 2>,
                 solve_constraint::<PrismaticJoint, 2>,
                 solve_constraint::<DistanceJoint, 2>,
+                solve_pose_targets,
             )
                 .chain()
                 .in_set(SubstepSet::SolveConstraints),
@@ -303,6 +304,32 @@ pub fn solve_constraint<C: XpbdConstraint<ENTITY_COUNT> + Component, const ENTIT
     }
 }
 
+/// Drives dynamic bodies toward their [`PoseTarget`]s.
+///
+/// Sleeping bodies are woken up when their target is changed.
+fn solve_pose_targets(
+    mut commands: Commands,
+    mut bodies: Query<(RigidBodyQuery, &mut PoseTarget, Has<Sleeping>)>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for (mut body, mut pose_target, is_sleeping) in &mut bodies {
+        if !body.rb.is_dynamic() {
+            continue;
+        }
+
+        if is_sleeping {
+            if pose_target.is_changed() {
+                commands.entity(body.entity).remove::<Sleeping>();
+            }
+            continue;
+        }
+
+        pose_target.solve(&mut body, delta_secs);
+    }
+}
+
 /// Updates the linear velocity of all dynamic bodies based on the change in position from the previous step.
 #[allow(clippy::type_complexity)]
 fn update_lin_vel(
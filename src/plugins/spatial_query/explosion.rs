@@ -0,0 +1,269 @@
+use crate::prelude::*;
+use bevy::{ecs::system::Command, prelude::*};
+
+/// An extension trait for `Commands` that adds physics-related commands.
+pub trait PhysicsCommands {
+    /// Applies a radial [explosion](Explosion) impulse to all [dynamic rigid bodies](RigidBody::Dynamic)
+    /// whose colliders are within the given `radius` of the `center`.
+    ///
+    /// The impulse is applied at the closest point on each body, and its magnitude
+    /// is scaled based on the distance using the given `falloff`. If `line_of_sight` is true,
+    /// bodies behind other colliders are not affected. An [`ExplosionApplied`] event
+    /// is sent with the affected bodies.
+    fn explode(
+        &mut self,
+        center: Vector,
+        radius: Scalar,
+        impulse: Scalar,
+        falloff: EffectorFalloff,
+        filter: SpatialQueryFilter,
+        line_of_sight: bool,
+    );
+}
+
+impl PhysicsCommands for Commands<'_, '_> {
+    fn explode(
+        &mut self,
+        center: Vector,
+        radius: Scalar,
+        impulse: Scalar,
+        falloff: EffectorFalloff,
+        filter: SpatialQueryFilter,
+        line_of_sight: bool,
+    ) {
+        self.add(
+            Explosion::new(center, radius, impulse)
+                .with_falloff(falloff)
+                .with_filter(filter)
+                .with_line_of_sight(line_of_sight),
+        );
+    }
+}
+
+/// A command that applies a radial explosion impulse to all [dynamic rigid bodies](RigidBody::Dynamic)
+/// whose colliders are within the `radius` of the `center`.
+///
+/// The colliders are found using an [intersection test](spatial_query#intersection-tests)
+/// with the [`SpatialQueryPipeline`], so the explosion uses the collider positions
+/// from the most recent physics step.
+///
+/// For each affected body, an [`ExternalImpulse`] pointing away from the center is applied
+/// at the closest point on its colliders, which can also make the body spin. The magnitude of
+/// the impulse is scaled by the [falloff](EffectorFalloff) based on the distance to the closest point.
+/// Bodies that don't have an [`ExternalImpulse`] yet get one.
+///
+/// Once the impulses have been applied, an [`ExplosionApplied`] event is sent with the affected bodies.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// # #[cfg(all(feature = "3d", feature = "f32"))]
+/// fn explode(mut commands: Commands) {
+///     // Explosion with an impulse that fades out linearly over 5 units
+///     commands.explode(
+///         Vec3::ZERO,
+///         5.0,
+///         20.0,
+///         EffectorFalloff::Linear { radius: 5.0 },
+///         SpatialQueryFilter::default(),
+///         false,
+///     );
+///
+///     // Explosion that doesn't affect bodies behind walls
+///     commands.add(
+///         Explosion::new(Vec3::ZERO, 5.0, 20.0)
+///             .with_falloff(EffectorFalloff::Linear { radius: 5.0 })
+///             .with_line_of_sight(true),
+///     );
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Explosion {
+    /// The center of the explosion.
+    pub center: Vector,
+    /// The radius of the explosion. Bodies further away from the center are not affected.
+    pub radius: Scalar,
+    /// The magnitude of the impulse before the falloff is applied.
+    pub impulse: Scalar,
+    /// How the impulse changes based on the distance from the center.
+    pub falloff: EffectorFalloff,
+    /// A [`SpatialQueryFilter`] that determines which colliders are affected by the explosion
+    /// and considered for line of sight checks.
+    pub filter: SpatialQueryFilter,
+    /// If true, bodies are only affected if a ray from the center to the closest point on the body
+    /// doesn't hit any other colliders first. Defaults to false.
+    pub line_of_sight: bool,
+}
+
+impl Explosion {
+    /// Creates a new [`Explosion`] with a constant impulse and no line of sight checks.
+    pub fn new(center: Vector, radius: Scalar, impulse: Scalar) -> Self {
+        Self {
+            center,
+            radius,
+            impulse,
+            falloff: EffectorFalloff::Constant,
+            filter: SpatialQueryFilter::default(),
+            line_of_sight: false,
+        }
+    }
+
+    /// Sets the [falloff](EffectorFalloff) of the impulse.
+    pub fn with_falloff(mut self, falloff: EffectorFalloff) -> Self {
+        self.falloff = falloff;
+        self
+    }
+
+    /// Sets the [`SpatialQueryFilter`] of the explosion.
+    pub fn with_filter(mut self, filter: SpatialQueryFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Determines if bodies must be in the line of sight of the center to be affected.
+    pub fn with_line_of_sight(mut self, line_of_sight: bool) -> Self {
+        self.line_of_sight = line_of_sight;
+        self
+    }
+}
+
+impl Command for Explosion {
+    fn apply(self, world: &mut World) {
+        let Some(pipeline) = world.get_resource::<SpatialQueryPipeline>() else {
+            warn!("tried to apply an explosion without a `SpatialQueryPipeline`");
+            return;
+        };
+
+        let body_of = |collider: Entity| {
+            world
+                .get::<ColliderParent>(collider)
+                .map_or(collider, |parent| parent.get())
+        };
+
+        // The closest point on each body and the distance to it
+        let mut hits: Vec<(Entity, Vector, Scalar)> = vec![];
+
+        for collider_entity in pipeline.shape_intersections(
+            &Collider::ball(self.radius),
+            self.center,
+            RotationValue::default(),
+            self.filter.clone(),
+        ) {
+            let Some((isometry, collider, _)) = pipeline.colliders.get(&collider_entity) else {
+                continue;
+            };
+
+            let projection =
+                collider
+                    .shape_scaled()
+                    .project_point(isometry, &self.center.into(), true);
+            let point: Vector = projection.point.into();
+            let distance = point.distance(self.center);
+
+            if distance > self.radius {
+                continue;
+            }
+
+            let body = body_of(collider_entity);
+
+            // The explosion is blocked if the ray hits a collider of another body first
+            if self.line_of_sight && distance > Scalar::EPSILON {
+                let blocked = pipeline
+                    .cast_ray(
+                        self.center,
+                        (point - self.center) / distance,
+                        distance,
+                        true,
+                        self.filter.clone(),
+                    )
+                    .is_some_and(|hit| body_of(hit.entity) != body);
+                if blocked {
+                    continue;
+                }
+            }
+
+            if let Some(hit) = hits.iter_mut().find(|hit| hit.0 == body) {
+                if distance < hit.2 {
+                    *hit = (body, point, distance);
+                }
+            } else {
+                hits.push((body, point, distance));
+            }
+        }
+
+        let mut affected_entities = Vec::with_capacity(hits.len());
+
+        for (body, point, distance) in hits {
+            let Some((rb, position, rotation, center_of_mass)) = world
+                .get::<RigidBody>(body)
+                .zip(world.get::<Position>(body))
+                .zip(world.get::<Rotation>(body))
+                .zip(world.get::<CenterOfMass>(body))
+                .map(|(((rb, pos), rot), com)| (*rb, pos.0, *rot, com.0))
+            else {
+                continue;
+            };
+
+            if !rb.is_dynamic() {
+                continue;
+            }
+
+            let world_center_of_mass = position + rotation.rotate(center_of_mass);
+
+            // If the center is inside of the body, push it away from the center of mass instead
+            let Some(direction) = (point - self.center)
+                .try_normalize()
+                .or_else(|| (world_center_of_mass - self.center).try_normalize())
+            else {
+                continue;
+            };
+
+            let impulse = direction * self.impulse * self.falloff.factor(distance);
+
+            // Bodies that haven't been stepped yet might not have an `ExternalImpulse`
+            let mut body_entity = world.entity_mut(body);
+            if !body_entity.contains::<ExternalImpulse>() {
+                body_entity.insert(ExternalImpulse::default());
+            }
+            let Some(mut external_impulse) = body_entity.get_mut::<ExternalImpulse>() else {
+                continue;
+            };
+
+            if external_impulse.local {
+                let inverse_rotation = rotation.inverse();
+                external_impulse.apply_impulse_at_point(
+                    inverse_rotation.rotate(impulse),
+                    inverse_rotation.rotate(point - world_center_of_mass),
+                    Vector::ZERO,
+                );
+            } else {
+                external_impulse.apply_impulse_at_point(impulse, point, world_center_of_mass);
+            }
+
+            affected_entities.push(body);
+        }
+
+        world.send_event(ExplosionApplied {
+            center: self.center,
+            radius: self.radius,
+            entities: affected_entities,
+        });
+    }
+}
+
+/// An event that is sent when an [`Explosion`] has been applied.
+#[derive(Event, Clone, Debug, PartialEq)]
+pub struct ExplosionApplied {
+    /// The center of the explosion.
+    pub center: Vector,
+    /// The radius of the explosion.
+    pub radius: Scalar,
+    /// The rigid bodies that received an impulse from the explosion.
+    pub entities: Vec<Entity>,
+}
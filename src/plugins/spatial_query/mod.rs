@@ -145,13 +145,22 @@
 //! See the documentation of the components and methods for more information.
 //!
 //! To specify which colliders should be considered in the query, use a [spatial query filter](`SpatialQueryFilter`).
+//!
+//! ## Explosions
+//!
+//! Radial explosion impulses can be applied with the [`explode`](PhysicsCommands::explode) method of `Commands`
+//! or by adding an [`Explosion`] command. Explosions find the affected colliders using a shape intersection test
+//! and can optionally check for line of sight using raycasts. An [`ExplosionApplied`] event is sent with
+//! the affected bodies.
 
+mod explosion;
 mod pipeline;
 mod query_filter;
 mod ray_caster;
 mod shape_caster;
 mod system_param;
 
+pub use explosion::*;
 pub use pipeline::*;
 pub use query_filter::*;
 pub use ray_caster::*;
@@ -187,10 +196,12 @@ impl Default for SpatialQueryPlugin {
 
 impl Plugin for SpatialQueryPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<SpatialQueryPipeline>().add_systems(
-            self.schedule,
-            (init_ray_hits, init_shape_hit).in_set(PrepareSet::PreInit),
-        );
+        app.init_resource::<SpatialQueryPipeline>()
+            .add_event::<ExplosionApplied>()
+            .add_systems(
+                self.schedule,
+                (init_ray_hits, init_shape_hit).in_set(PrepareSet::PreInit),
+            );
 
         let physics_schedule = app
             .get_schedule_mut(PhysicsSchedule)
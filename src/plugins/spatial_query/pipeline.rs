@@ -12,7 +12,7 @@ use parry::{
         visitors::{
             BoundingVolumeIntersectionsVisitor, PointIntersectionsVisitor, RayIntersectionsVisitor,
         },
-        DefaultQueryDispatcher, QueryDispatcher,
+        QueryDispatcher,
     },
     shape::{Shape, TypedSimdCompositeShape},
     utils::DefaultStorage,
@@ -34,7 +34,7 @@ impl Default for SpatialQueryPipeline {
     fn default() -> Self {
         Self {
             qbvh: Qbvh::new(),
-            dispatcher: Arc::new(DefaultQueryDispatcher),
+            dispatcher: Arc::new(query_dispatcher()),
             colliders: HashMap::default(),
             entity_generations: HashMap::default(),
         }
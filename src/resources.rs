@@ -130,3 +130,42 @@ impl Gravity {
     /// Zero gravity.
     pub const ZERO: Gravity = Gravity(Vector::ZERO);
 }
+
+/// The properties of the air used for computing [aerodynamic](Aerodynamics) forces.
+///
+/// ## Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::{math::Vector, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::{math::Vector, prelude::*};")]
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, PhysicsPlugins::default()))
+///         // Thin air with a strong wind along the X axis
+///         .insert_resource(Atmosphere {
+///             air_density: 0.6,
+///             wind: Vector::X * 10.0,
+///         })
+///         .run();
+/// }
+/// ```
+#[derive(Reflect, Resource, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Resource)]
+pub struct Atmosphere {
+    /// The density of the air. The default is `1.225`, which is the density of air at sea level in kg/m³.
+    pub air_density: Scalar,
+    /// The global velocity of the wind. The default is zero.
+    pub wind: Vector,
+}
+
+impl Default for Atmosphere {
+    fn default() -> Self {
+        Self {
+            air_density: 1.225,
+            wind: Vector::ZERO,
+        }
+    }
+}
@@ -25,14 +25,23 @@ macro_rules! setup_insta {
 
 fn create_app() -> App {
     let mut app = App::new();
+    // Collider assets are registered by the physics plugins, so the asset plugin needs to be added first
+    #[cfg(any(feature = "async-collider", feature = "collider-asset"))]
+    app.add_plugins(bevy::asset::AssetPlugin::default());
     app.add_plugins((MinimalPlugins, TransformPlugin, PhysicsPlugins::default()));
+    #[cfg(feature = "deformable")]
+    app.add_plugins(DeformablePlugin::default());
+    #[cfg(feature = "fracture")]
+    app.add_plugins(FracturePlugin);
+    #[cfg(all(feature = "3d", feature = "ragdoll"))]
+    app.add_plugins(RagdollPlugin::default());
     #[cfg(feature = "async-collider")]
     {
-        app.add_plugins((
-            bevy::asset::AssetPlugin::default(),
-            bevy::scene::ScenePlugin,
-        ))
-        .init_resource::<Assets<Mesh>>();
+        app.init_resource::<Assets<Mesh>>();
+        #[cfg(feature = "2d")]
+        app.init_resource::<Assets<Image>>();
+        #[cfg(feature = "3d")]
+        app.add_plugins(bevy::scene::ScenePlugin);
     }
     app.insert_resource(TimeUpdateStrategy::ManualInstant(Instant::now()));
     app
@@ -48,6 +57,21 @@ fn tick_60_fps(app: &mut App) {
     app.update();
 }
 
+/// Ticks the app until the colliders computed in the background have been inserted.
+#[cfg(feature = "async-collider")]
+fn tick_until_colliders_computed(app: &mut App) {
+    tick_60_fps(app);
+    for _ in 0..1000 {
+        let mut pending = app.world.query_filtered::<(), With<ColliderPending>>();
+        if pending.iter(&app.world).next().is_none() {
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(5));
+        tick_60_fps(app);
+    }
+    panic!("colliders were not computed in time");
+}
+
 #[cfg(feature = "3d")]
 fn setup_cubes_simulation(mut commands: Commands) {
     let mut next_id = 0;
@@ -165,81 +189,2198 @@ fn body_with_velocity_moves() {
     }
 }
 
-#[derive(Component, Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Ord)]
-struct Id(usize);
+#[test]
+fn gravity_field_only_affects_bodies_inside_it() {
+    let mut app = create_app();
+
+    app.add_systems(Startup, |mut commands: Commands| {
+        // zero gravity zone around the origin
+        commands.spawn((
+            Collider::ball(2.0),
+            Sensor,
+            GravityField::directional(Vector::ZERO),
+        ));
+        commands.spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            MassPropertiesBundle::new_computed(&Collider::ball(0.5), 1.0),
+            Id(0),
+        ));
+        commands.spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            Position(Vector::X * 10.0),
+            MassPropertiesBundle::new_computed(&Collider::ball(0.5), 1.0),
+            Id(1),
+        ));
+    });
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    let mut app_query = app.world.query::<(&Id, &Position)>();
+    let mut bodies: Vec<(&Id, &Position)> = app_query.iter(&app.world).collect();
+    bodies.sort_by_key(|b| b.0);
+
+    assert_relative_eq!(bodies[0].1.y, 0.0);
+    assert!(bodies[1].1.y < -1.0, "body outside of the field falls");
+}
 
-#[cfg(all(feature = "3d", feature = "enhanced-determinism"))]
 #[test]
-fn cubes_simulation_is_deterministic_across_machines() {
-    setup_insta!();
+fn local_impulse_is_rotated_by_body() {
     let mut app = create_app();
 
-    app.add_systems(Startup, setup_cubes_simulation);
+    app.insert_resource(Gravity::ZERO);
 
-    const SECONDS: usize = 10;
-    const UPDATES: usize = 60 * SECONDS;
+    app.add_systems(Startup, |mut commands: Commands| {
+        // body rotated 90 degrees counterclockwise, so local X is world Y
+        commands.spawn((
+            SpatialBundle::from_transform(Transform::from_rotation(Quat::from_rotation_z(
+                std::f32::consts::FRAC_PI_2,
+            ))),
+            RigidBody::Dynamic,
+            ExternalImpulse::new(Vector::X).with_local_frame(true),
+            MassPropertiesBundle::new_computed(&Collider::ball(0.5), 1.0),
+        ));
+    });
 
-    for _ in 0..UPDATES {
+    tick_60_fps(&mut app);
+
+    let mut app_query = app.world.query::<(&LinearVelocity, &Mass)>();
+    let (lin_vel, mass) = app_query.single(&app.world);
+
+    assert_relative_eq!(lin_vel.x, 0.0, epsilon = 0.0001);
+    assert_relative_eq!(lin_vel.y, 1.0 / mass.0, epsilon = 0.0001);
+}
+
+#[test]
+fn gravity_override_wakes_up_sleeping_body() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+
+    let body = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            MassPropertiesBundle::new_computed(&Collider::ball(0.5), 1.0),
+        ))
+        .id();
+
+    for _ in 0..3 {
         tick_60_fps(&mut app);
     }
 
-    let mut app_query = app.world.query::<(&Id, &Transform)>();
+    app.world.entity_mut(body).insert(Sleeping);
+    tick_60_fps(&mut app);
+    assert!(app.world.get::<Sleeping>(body).is_some());
 
-    let mut bodies: Vec<(&Id, &Transform)> = app_query.iter(&app.world).collect();
-    bodies.sort_by_key(|b| b.0);
+    // Setting the override wakes the body up
+    app.world
+        .entity_mut(body)
+        .insert((Sleeping, GravityOverride(Vector::Y * 9.81)));
+    tick_60_fps(&mut app);
+    assert!(app.world.get::<Sleeping>(body).is_none());
 
-    assert_debug_snapshot!(bodies);
+    // Changing the override wakes the body up again
+    app.world.entity_mut(body).insert(Sleeping);
+    tick_60_fps(&mut app);
+    assert!(app.world.get::<Sleeping>(body).is_some());
+    app.world.get_mut::<GravityOverride>(body).unwrap().0 = Vector::NEG_Y;
+    tick_60_fps(&mut app);
+    assert!(app.world.get::<Sleeping>(body).is_none());
 }
 
-#[cfg(feature = "3d")]
 #[test]
-fn cubes_simulation_is_locally_deterministic() {
-    use itertools::Itertools;
-
-    fn run_cubes() -> Vec<(Id, Transform)> {
-        let mut app = create_app();
+fn force_generator_is_applied_every_substep() {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
 
-        app.add_systems(Startup, setup_cubes_simulation);
+    /// Cancels out gravity by applying an opposite force, and counts how many times it's evaluated.
+    #[derive(Component)]
+    struct AntiGravity {
+        entity: Entity,
+        calls: Arc<AtomicUsize>,
+    }
 
-        const SECONDS: usize = 5;
-        const UPDATES: usize = 60 * SECONDS;
+    impl ForceGenerator<1> for AntiGravity {
+        fn entities(&self) -> [Entity; 1] {
+            [self.entity]
+        }
 
-        for _ in 0..UPDATES {
-            tick_60_fps(&mut app);
+        fn compute_forces(
+            &self,
+            bodies: [&RigidBodyQueryItem; 1],
+            _dt: Scalar,
+        ) -> [GeneratedForce; 1] {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            [GeneratedForce::new(
+                -Gravity::default().0 * bodies[0].mass.0,
+            )]
         }
+    }
 
-        let mut app_query = app.world.query::<(&Id, &Transform)>();
+    let mut app = create_app();
 
-        let mut bodies: Vec<(Id, Transform)> = app_query
-            .iter(&app.world)
-            .map(|(id, transform)| (*id, *transform))
-            .collect();
-        bodies.sort_by_key(|b| b.0);
-        bodies
+    app.add_plugins(ForceGeneratorPlugin::<AntiGravity, 1>::default());
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let body = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            MassPropertiesBundle::new_computed(&Collider::ball(0.5), 1.0),
+        ))
+        .id();
+    app.world.spawn(AntiGravity {
+        entity: body,
+        calls: calls.clone(),
+    });
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
     }
 
-    // run simulation and check that results are equal each time
-    for (a, b) in (0..4).map(|_| run_cubes()).tuple_windows() {
-        assert_eq!(a, b);
+    let pos = app.world.get::<Position>(body).unwrap();
+    assert_relative_eq!(pos.y, 0.0, epsilon = 0.0001);
+
+    // The generator is evaluated once per substep, not once per physics step
+    calls.store(0, Ordering::Relaxed);
+    tick_60_fps(&mut app);
+    let substeps = app.world.resource::<SubstepCount>().0 as usize;
+    assert!(substeps > 1);
+    assert_eq!(calls.load(Ordering::Relaxed), substeps);
+}
+
+#[test]
+fn pose_target_drives_body_to_target_within_max_force() {
+    let mut app = create_app();
+
+    #[cfg(feature = "2d")]
+    let target_rotation = Rotation::from_radians(1.0);
+    #[cfg(feature = "3d")]
+    let target_rotation = Rotation(Quaternion::from_rotation_z(1.0));
+
+    let mut spawn_body = |max_force: Scalar| {
+        app.world
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Dynamic,
+                MassPropertiesBundle::new_computed(&Collider::ball(0.5), 1.0),
+                PoseTarget::new()
+                    .with_position(Vector::Y * 5.0)
+                    .with_rotation(target_rotation)
+                    .with_linear_drive(100_000.0, 1000.0)
+                    .with_angular_drive(1000.0, 50.0)
+                    .with_max_force(max_force),
+            ))
+            .id()
+    };
+
+    let strong = spawn_body(1000.0);
+    // The weak drive can't hold the body up against gravity
+    let weak = spawn_body(0.1);
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
     }
+
+    let position = app.world.get::<Position>(strong).unwrap().0;
+    assert_relative_eq!(position, Vector::Y * 5.0, epsilon = 0.01);
+
+    let rotation = *app.world.get::<Rotation>(strong).unwrap();
+    #[cfg(feature = "2d")]
+    assert_relative_eq!(rotation.as_radians(), 1.0, epsilon = 0.01);
+    #[cfg(feature = "3d")]
+    assert!(rotation.0.angle_between(target_rotation.0) < 0.01);
+
+    // The drive holds the body up against gravity
+    let force = app.world.get::<PoseTarget>(strong).unwrap().force;
+    let mass = app.world.get::<Mass>(strong).unwrap().0;
+    assert_relative_eq!(force, -Gravity::default().0 * mass, epsilon = 0.1);
+
+    assert!(app.world.get::<Position>(weak).unwrap().y < 0.0);
 }
 
 #[test]
-fn no_ambiguity_errors() {
-    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
-    struct DeterministicSchedule;
+fn pose_target_damps_motion_perpendicular_to_target() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
 
-    let mut app = App::new();
+    #[cfg(feature = "2d")]
+    let (target_rotation, angular_velocity) = (Rotation::from_radians(1.0), 5.0);
+    #[cfg(feature = "3d")]
+    let (target_rotation, angular_velocity) =
+        (Rotation(Quaternion::from_rotation_z(1.0)), Vector::X * 5.0);
 
-    app.add_plugins((MinimalPlugins, PhysicsPlugins::new(DeterministicSchedule)));
+    // Only damping, with the body moving sideways relative to the target
+    let entity = app
+        .world
+        .spawn((
+            SpatialBundle::from_transform(Transform::from_xyz(0.0, 1.0, 0.0)),
+            RigidBody::Dynamic,
+            MassPropertiesBundle::new_computed(&Collider::ball(0.5), 1.0),
+            LinearVelocity(Vector::X * 10.0),
+            AngularVelocity(angular_velocity),
+            PoseTarget::new()
+                .with_position(Vector::ZERO)
+                .with_rotation(target_rotation)
+                .with_linear_drive(0.0, 10.0)
+                .with_angular_drive(0.0, 1.0),
+        ))
+        .id();
 
-    #[cfg(feature = "async-collider")]
-    {
-        app.add_plugins((
-            bevy::asset::AssetPlugin::default(),
-            bevy::scene::ScenePlugin,
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    let linear_velocity = app.world.get::<LinearVelocity>(entity).unwrap().0;
+    assert!(linear_velocity.length() < 0.1);
+    let angular_velocity = app.world.get::<AngularVelocity>(entity).unwrap().0;
+    #[cfg(feature = "2d")]
+    assert!(angular_velocity.abs() < 0.1);
+    #[cfg(feature = "3d")]
+    assert!(angular_velocity.length() < 0.1);
+}
+
+#[cfg(all(feature = "2d", feature = "async-collider"))]
+#[test]
+fn colliders_are_generated_from_2d_meshes() {
+    use bevy::sprite::Mesh2dHandle;
+
+    let mesh = Mesh::from(shape::Quad::new(Vec2::new(2.0, 1.0)));
+
+    // The outline of the quad consists of its four sides
+    let polyline = Collider::polyline_from_mesh(&mesh).unwrap();
+    assert_eq!(polyline.shape().as_polyline().unwrap().num_segments(), 4);
+
+    let convex_hull = Collider::convex_hull_from_mesh(&mesh).unwrap();
+    let aabb = convex_hull.compute_aabb(Vector::ZERO, 0.0);
+    assert_relative_eq!(aabb.mins.x, -1.0);
+    assert_relative_eq!(aabb.maxs.y, 0.5);
+
+    // The collider is generated once the mesh has been added
+    let mut app = create_app();
+    let handle = app.world.resource_mut::<Assets<Mesh>>().add(mesh);
+    let entity = app
+        .world
+        .spawn((
+            AsyncCollider(ComputedCollider::ConvexDecomposition(default())),
+            Mesh2dHandle(handle),
+        ))
+        .id();
+
+    tick_until_colliders_computed(&mut app);
+
+    assert!(app.world.get::<AsyncCollider>(entity).is_none());
+    let collider = app.world.get::<Collider>(entity).unwrap();
+    assert_relative_eq!(collider.mass_properties(1.0).mass.0, 2.0, epsilon = 0.05);
+}
+
+#[cfg(all(feature = "2d", feature = "async-collider"))]
+#[test]
+fn colliders_are_traced_from_image_alpha() {
+    use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+
+    // An 8x4 image with an opaque 4x2 rectangle in the middle
+    let data = (0..4)
+        .flat_map(|y| (0..8).map(move |x| (x, y)))
+        .flat_map(|(x, y)| {
+            let alpha = if (2..6).contains(&x) && (1..3).contains(&y) {
+                255
+            } else {
+                0
+            };
+            [255, 255, 255, alpha]
+        })
+        .collect();
+    let image = Image::new(
+        Extent3d {
+            width: 8,
+            height: 4,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+    );
+
+    let polyline = Collider::from_image_alpha(&image, 0.5, 0.0).unwrap();
+    assert!(polyline.shape().as_polyline().unwrap().num_segments() >= 4);
+
+    // The outline is centered on the image, with one unit per pixel
+    let convex_hull = Collider::convex_hull_from_image_alpha(&image, 0.5).unwrap();
+    let aabb = convex_hull.compute_aabb(Vector::ZERO, 0.0);
+    assert_relative_eq!(aabb.mins.x, -2.0);
+    assert_relative_eq!(aabb.maxs.x, 2.0);
+    assert_relative_eq!(aabb.maxs.y, 1.0);
+
+    // A fully transparent image has no outline
+    let transparent = Image::new_fill(
+        Extent3d::default(),
+        TextureDimension::D2,
+        &[0, 0, 0, 0],
+        TextureFormat::Rgba8UnormSrgb,
+    );
+    assert!(Collider::from_image_alpha(&transparent, 0.5, 0.0).is_none());
+
+    // The collider is generated once the image has been added
+    let mut app = create_app();
+    let handle = app.world.resource_mut::<Assets<Image>>().add(image);
+    let entity = app
+        .world
+        .spawn((
+            AsyncImageCollider::new(ComputedImageCollider::ConvexHull),
+            handle,
+        ))
+        .id();
+
+    tick_until_colliders_computed(&mut app);
+
+    assert!(app.world.get::<AsyncImageCollider>(entity).is_none());
+    let collider = app.world.get::<Collider>(entity).unwrap();
+    // The corners of the traced outline are cut by marching squares
+    assert_relative_eq!(collider.mass_properties(1.0).mass.0, 7.5, epsilon = 0.01);
+}
+
+#[cfg(feature = "2d")]
+#[test]
+fn tile_grid_merges_tiles_and_rebuilds_changed_chunks() {
+    // Two chunks of 4x2 solid tiles
+    let grid =
+        TileGridCollider::from_solid(8, 2, Vector::splat(1.0), [true; 16]).with_chunk_size(4);
+
+    let collider = grid.collider().unwrap();
+    let shapes = collider.shape().as_compound().unwrap().shapes();
+    assert_eq!(shapes.len(), 1);
+    let aabb = collider.compute_aabb(Vector::ZERO, 0.0);
+    assert_relative_eq!(aabb.mins.x, -0.5);
+    assert_relative_eq!(aabb.maxs.x, 7.5);
+
+    // The outline of the floor is a rectangle
+    let outline = grid
+        .clone()
+        .with_mode(TileGridMode::Outlines)
+        .collider()
+        .unwrap();
+    assert_eq!(outline.shape().as_polyline().unwrap().num_segments(), 4);
+
+    let mut app = create_app();
+    let entity = app
+        .world
+        .spawn((RigidBody::Static, grid, SpatialBundle::default()))
+        .id();
+
+    tick_60_fps(&mut app);
+
+    let chunk_entities = |app: &mut App| {
+        let mut chunks = app
+            .world
+            .query::<(Entity, &TileGridChunk, &Parent)>()
+            .iter(&app.world)
+            .map(|(entity, chunk, parent)| (chunk.chunk, parent.get(), entity))
+            .collect::<Vec<_>>();
+        chunks.sort_by_key(|(chunk, _, entity)| (chunk.x, *entity));
+        chunks
+    };
+    let before = chunk_entities(&mut app);
+    assert_eq!(before.len(), 2);
+    assert!(before.iter().all(|(_, parent, _)| *parent == entity));
+
+    // Editing a tile only rebuilds its chunk, and tiles with different properties get their own collider
+    app.world
+        .get_mut::<TileGridCollider>(entity)
+        .unwrap()
+        .set_tile(
+            5,
+            1,
+            Some(GridTile::new().with_friction(Friction::new(0.1))),
+        );
+
+    tick_60_fps(&mut app);
+
+    let after = chunk_entities(&mut app);
+    assert_eq!(after.len(), 3);
+    assert_eq!(after[0], before[0]);
+    assert!(after[1..].iter().all(|(chunk, _, _)| chunk.x == 1));
+    assert!(after[1..]
+        .iter()
+        .any(|(_, _, entity)| app.world.get::<Friction>(*entity).is_some()));
+}
+
+#[cfg(feature = "2d")]
+#[test]
+fn tile_grid_outline_edits_on_chunk_borders_rebuild_neighboring_chunks() {
+    // Two chunks of 4x2 solid tiles
+    let grid = TileGridCollider::from_solid(8, 2, Vector::splat(1.0), [true; 16])
+        .with_chunk_size(4)
+        .with_mode(TileGridMode::Outlines);
+
+    let mut app = create_app();
+    let entity = app
+        .world
+        .spawn((RigidBody::Static, grid, SpatialBundle::default()))
+        .id();
+
+    tick_60_fps(&mut app);
+
+    let chunk_entities = |app: &mut App| {
+        let mut chunks = app
+            .world
+            .query::<(Entity, &TileGridChunk)>()
+            .iter(&app.world)
+            .map(|(entity, chunk)| (chunk.chunk, entity))
+            .collect::<Vec<_>>();
+        chunks.sort_by_key(|(chunk, _)| chunk.x);
+        chunks
+    };
+    let segments = |app: &App, entity: Entity| {
+        let collider = app.world.get::<Collider>(entity).unwrap();
+        collider.shape().as_polyline().unwrap().num_segments()
+    };
+    let before = chunk_entities(&mut app);
+    assert_eq!(before.len(), 2);
+    // The top, bottom and outer side of each chunk
+    assert_eq!(segments(&app, before[0].1), 3);
+
+    // Removing the first tile of the second chunk exposes an edge of the first chunk
+    app.world
+        .get_mut::<TileGridCollider>(entity)
+        .unwrap()
+        .set_tile(4, 0, None);
+
+    tick_60_fps(&mut app);
+
+    let after = chunk_entities(&mut app);
+    assert_eq!(after.len(), 2);
+    assert_ne!(after[0].1, before[0].1);
+    assert_ne!(after[1].1, before[1].1);
+    assert_eq!(segments(&app, after[0].1), 4);
+
+    // Tiles inside of a chunk only rebuild their own chunk
+    app.world
+        .get_mut::<TileGridCollider>(entity)
+        .unwrap()
+        .set_tile(6, 1, None);
+
+    tick_60_fps(&mut app);
+
+    let edited = chunk_entities(&mut app);
+    assert_eq!(edited[0], after[0]);
+    assert_ne!(edited[1].1, after[1].1);
+}
+
+#[cfg(all(feature = "3d", feature = "collider-from-image"))]
+#[test]
+fn heightfield_from_image_supports_holes_and_patching() {
+    use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+    use parry::query::Ray;
+
+    // A 3x2 heightmap that rises along the first row
+    let image = Image::new(
+        Extent3d {
+            width: 3,
+            height: 2,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        vec![0, 128, 255, 0, 0, 0],
+        TextureFormat::R8Unorm,
+    );
+    let mut collider =
+        Collider::heightfield_from_image(&image, Vector::new(4.0, 1.0, 2.0), -1.0, 3.0).unwrap();
+
+    let aabb = collider.compute_aabb(Vector::ZERO, Quaternion::IDENTITY);
+    assert_relative_eq!(aabb.mins.y, -1.0);
+    assert_relative_eq!(aabb.maxs.y, 3.0);
+    assert_relative_eq!(aabb.maxs.x, 2.0);
+    assert_relative_eq!(aabb.maxs.z, 1.0);
+
+    // A ray cast down onto the second cell
+    let ray = Ray::new(Vector::new(1.0, 10.0, 0.0).into(), Vector::NEG_Y.into());
+    let hits = |collider: &Collider| collider.shape().cast_local_ray(&ray, 100.0, true).is_some();
+    assert!(hits(&collider));
+
+    collider.set_heightfield_hole(0, 1, true).unwrap();
+    assert!(!hits(&collider));
+
+    // Lowering the corner keeps the hole
+    collider.patch_heightfield(0, 2, &[vec![-1.0]]).unwrap();
+    let aabb = collider.compute_aabb(Vector::ZERO, Quaternion::IDENTITY);
+    assert_relative_eq!(aabb.maxs.y, 1.0, epsilon = 0.01);
+    assert!(!hits(&collider));
+
+    collider.set_heightfield_hole(0, 1, false).unwrap();
+    assert!(hits(&collider));
+
+    assert!(Collider::ball(1.0)
+        .set_heightfield_hole(0, 0, true)
+        .is_err());
+}
+
+#[test]
+fn explosion_pushes_bodies_away() {
+    use bevy::ecs::system::Command;
+
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+
+    let body = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            Position(Vector::X * 2.0),
+            Collider::ball(0.5),
+        ))
+        .id();
+
+    // update the spatial query pipeline
+    tick_60_fps(&mut app);
+
+    Explosion::new(Vector::ZERO, 5.0, 10.0).apply(&mut app.world);
+
+    let events = app.world.resource::<Events<ExplosionApplied>>();
+    let event = events.get_reader().read(events).next().cloned();
+    assert_eq!(event.map(|event| event.entities), Some(vec![body]));
+
+    tick_60_fps(&mut app);
+
+    let lin_vel = app.world.get::<LinearVelocity>(body).unwrap();
+    assert!(lin_vel.x > 0.0, "body is pushed away from the center");
+    assert_relative_eq!(lin_vel.y, 0.0);
+}
+
+#[test]
+fn explode_command_respects_line_of_sight() {
+    use bevy::ecs::system::CommandQueue;
+
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+
+    app.world.spawn((
+        SpatialBundle::default(),
+        RigidBody::Static,
+        Position(Vector::X * 2.0),
+        Collider::ball(0.5),
+    ));
+    let hidden_body = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            Position(Vector::X * 4.0),
+            Collider::ball(0.5),
+        ))
+        .id();
+    let visible_body = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            Position(Vector::NEG_X * 2.0),
+            Collider::ball(0.5),
+        ))
+        .id();
+
+    // update the spatial query pipeline
+    tick_60_fps(&mut app);
+
+    // the explosion inserts a missing external impulse
+    app.world
+        .entity_mut(visible_body)
+        .remove::<ExternalImpulse>();
+
+    let mut queue = CommandQueue::default();
+    Commands::new(&mut queue, &app.world).explode(
+        Vector::ZERO,
+        5.0,
+        10.0,
+        EffectorFalloff::Constant,
+        SpatialQueryFilter::default(),
+        true,
+    );
+    queue.apply(&mut app.world);
+
+    let events = app.world.resource::<Events<ExplosionApplied>>();
+    let event = events.get_reader().read(events).next().cloned();
+    assert_eq!(event.map(|event| event.entities), Some(vec![visible_body]));
+
+    tick_60_fps(&mut app);
+
+    let lin_vel = app.world.get::<LinearVelocity>(visible_body).unwrap();
+    assert!(lin_vel.x < 0.0, "visible body is pushed away");
+    let lin_vel = app.world.get::<LinearVelocity>(hidden_body).unwrap();
+    assert_relative_eq!(lin_vel.x, 0.0);
+}
+
+#[test]
+fn aerodynamic_drag_reaches_terminal_velocity() {
+    let mut app = create_app();
+
+    let collider = Collider::ball(0.5);
+    let body = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            collider.clone(),
+            Aerodynamics::new(1.0),
         ))
-        .init_resource::<Assets<Mesh>>();
+        .id();
+
+    for _ in 0..300 {
+        tick_60_fps(&mut app);
+    }
+
+    // the drag cancels out gravity at the terminal velocity
+    let mass = app.world.get::<Mass>(body).unwrap().0;
+    let area = collider.projected_area(Vector::Y);
+    let air_density = Atmosphere::default().air_density;
+    let terminal_speed = (2.0 * mass * Gravity::default().0.length() / (air_density * area)).sqrt();
+
+    let lin_vel = app.world.get::<LinearVelocity>(body).unwrap();
+    assert_relative_eq!(lin_vel.y, -terminal_speed, epsilon = terminal_speed * 0.01);
+    assert_relative_eq!(lin_vel.x, 0.0);
+}
+
+#[test]
+fn aerodynamic_drag_carries_body_with_wind_without_overshooting() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+    app.insert_resource(Atmosphere {
+        wind: Vector::X * 5.0,
+        ..default()
+    });
+
+    // the drag is large enough to reverse the relative motion within a substep if it wasn't clamped
+    let body = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            Collider::ball(0.5),
+            Aerodynamics::new(10_000.0),
+        ))
+        .id();
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+
+        let lin_vel = app.world.get::<LinearVelocity>(body).unwrap();
+        assert!(lin_vel.x <= 5.0 + 0.0001, "body doesn't overtake the wind");
+    }
+
+    let lin_vel = app.world.get::<LinearVelocity>(body).unwrap();
+    assert_relative_eq!(lin_vel.x, 5.0, epsilon = 0.0001);
+    assert_relative_eq!(lin_vel.y, 0.0);
+}
+
+#[test]
+fn aerodynamic_lift_follows_angle_of_attack() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::cuboid(2.0, 0.1);
+    #[cfg(feature = "3d")]
+    let collider = Collider::cuboid(2.0, 0.1, 2.0);
+
+    // flat plates moving along the X axis, pitched up and down by 10 degrees
+    let mut spawn_plate = |angle: f32, y: Scalar| {
+        app.world
+            .spawn((
+                SpatialBundle::from_transform(Transform::from_rotation(Quat::from_rotation_z(
+                    angle.to_radians(),
+                ))),
+                RigidBody::Dynamic,
+                Position(Vector::Y * y),
+                LinearVelocity(Vector::X * 10.0),
+                collider.clone(),
+                Aerodynamics::new(0.0).with_lift(0.1, Vector::Y),
+            ))
+            .id()
+    };
+    let pitched_up = spawn_plate(10.0, 0.0);
+    let pitched_down = spawn_plate(-10.0, 5.0);
+
+    tick_60_fps(&mut app);
+
+    let lin_vel = app.world.get::<LinearVelocity>(pitched_up).unwrap();
+    assert!(lin_vel.y > 0.0, "lift pushes the plate up");
+    assert_relative_eq!(lin_vel.length(), 10.0, epsilon = 0.01);
+
+    let lin_vel = app.world.get::<LinearVelocity>(pitched_down).unwrap();
+    assert!(lin_vel.y < 0.0, "lift pushes the plate down");
+    assert_relative_eq!(lin_vel.length(), 10.0, epsilon = 0.01);
+}
+
+#[cfg(all(feature = "3d", feature = "deformable"))]
+#[test]
+fn cloth_falls_onto_collider_and_keeps_pinned_vertices() {
+    let mut app = create_app();
+
+    app.world.spawn((
+        RigidBody::Static,
+        Position(Vector::NEG_Y * 0.5),
+        Collider::cuboid(10.0, 1.0, 10.0),
+    ));
+
+    let mesh = Mesh::from(shape::Plane {
+        size: 1.0,
+        subdivisions: 4,
+    });
+    let cloth = Cloth::from_mesh(&mesh).unwrap();
+    let pinned_cloth = Cloth::from_mesh(&mesh).unwrap().with_pinned_vertices([0]);
+
+    let falling = app
+        .world
+        .spawn((cloth, Transform::from_xyz(0.0, 1.0, 0.0)))
+        .id();
+    let pinned = app
+        .world
+        .spawn((pinned_cloth, Transform::from_xyz(3.0, 1.0, 0.0)))
+        .id();
+    let scaled = app
+        .world
+        .spawn((
+            Cloth::from_mesh(&mesh).unwrap(),
+            Transform::from_xyz(-3.0, 1.0, 0.0).with_scale(Vec3::splat(2.0)),
+        ))
+        .id();
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    // The scaled cloth keeps its scaled size
+    let cloth = app.world.get::<Cloth>(scaled).unwrap();
+    let (mins, maxs) = cloth.particles.aabb(0.0).unwrap();
+    assert_relative_eq!(maxs.x - mins.x, 2.0, epsilon = 0.05);
+    assert_relative_eq!(maxs.z - mins.z, 2.0, epsilon = 0.05);
+
+    let cloth = app.world.get::<Cloth>(falling).unwrap();
+    for position in &cloth.particles.positions {
+        assert!(position.y > 0.0, "cloth doesn't pass through the ground");
+        assert!(position.y < 0.1, "cloth rests on the ground");
+    }
+
+    let cloth = app.world.get::<Cloth>(pinned).unwrap();
+    let pinned_position = cloth.vertex_position(0).unwrap();
+    assert_relative_eq!(pinned_position.y, 1.0);
+    assert!(cloth.particles.positions.iter().any(|p| p.y < 0.9));
+}
+
+#[cfg(all(feature = "3d", feature = "deformable"))]
+#[test]
+fn soft_body_lands_on_collider_and_keeps_volume() {
+    let mut app = create_app();
+
+    app.world.spawn((
+        RigidBody::Static,
+        Position(Vector::NEG_Y * 0.5),
+        Collider::cuboid(10.0, 1.0, 10.0),
+    ));
+
+    let soft_body = SoftBody::from_mesh(&Mesh::from(shape::Cube { size: 1.0 }), 3).unwrap();
+    assert_relative_eq!(soft_body.volume(), 1.0, epsilon = 0.0001);
+
+    let entity = app
+        .world
+        .spawn((soft_body.clone(), Transform::from_xyz(0.0, 1.5, 0.0)))
+        .id();
+    let scaled = app
+        .world
+        .spawn((
+            soft_body,
+            Transform::from_xyz(4.0, 2.0, 0.0).with_scale(Vec3::splat(2.0)),
+        ))
+        .id();
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    // The scaled soft body keeps its scaled volume
+    let scaled = app.world.get::<SoftBody>(scaled).unwrap();
+    assert_relative_eq!(scaled.volume(), 8.0, epsilon = 0.4);
+
+    let soft_body = app.world.get::<SoftBody>(entity).unwrap();
+    assert_relative_eq!(soft_body.volume(), 1.0, epsilon = 0.05);
+    for position in &soft_body.particles.positions {
+        assert!(
+            position.y > -0.01,
+            "soft body doesn't pass through the ground"
+        );
+        assert!(position.y < 1.1, "soft body has landed");
+    }
+}
+
+#[cfg(all(feature = "3d", feature = "deformable"))]
+#[test]
+fn rope_hangs_from_attachment_and_tears() {
+    let mut app = create_app();
+
+    app.world.spawn((
+        RigidBody::Static,
+        Position(Vector::NEG_Y * 0.5),
+        Collider::cuboid(10.0, 1.0, 10.0),
+    ));
+    let anchor = app
+        .world
+        .spawn((RigidBody::Static, Position(Vector::Y * 2.0)))
+        .id();
+
+    // A rope that swings down from the anchor and lands on the ground
+    let rope = app
+        .world
+        .spawn(
+            Rope::new(Vector::Y * 2.0, Vector::new(3.0, 2.0, 0.0), 30)
+                .with_start_attachment(anchor, Vector::ZERO),
+        )
+        .id();
+
+    // A heavy, stretchy rope that tears under its own weight
+    let tearing_rope = app
+        .world
+        .spawn(
+            Rope::new(Vector::new(0.0, 2.0, 5.0), Vector::new(0.0, 1.0, 5.0), 10)
+                .with_mass(100.0)
+                .with_stretch_compliance(0.01)
+                .with_pinned_particles([0])
+                .with_tearing(1.2),
+        )
+        .id();
+
+    // A rope whose length is doubled by the scale of its `Transform`
+    let scaled_rope = app
+        .world
+        .spawn((
+            Rope::new(Vector::ZERO, Vector::X, 10).with_pinned_particles([0]),
+            Transform::from_xyz(0.0, 5.0, 10.0).with_scale(Vec3::splat(2.0)),
+        ))
+        .id();
+
+    for _ in 0..180 {
+        tick_60_fps(&mut app);
+    }
+
+    let scaled_rope = app.world.get::<Rope>(scaled_rope).unwrap();
+    let length: Scalar = scaled_rope
+        .particles
+        .positions
+        .windows(2)
+        .map(|pair| pair[0].distance(pair[1]))
+        .sum();
+    assert_relative_eq!(length, 2.0, epsilon = 0.1);
+
+    let rope = app.world.get::<Rope>(rope).unwrap();
+    assert!(!rope.is_torn());
+    assert_relative_eq!(rope.particles.positions[0], Vector::Y * 2.0);
+    for position in &rope.particles.positions {
+        assert!(position.y > -0.01, "rope doesn't pass through the ground");
+    }
+    let end = rope.particles.positions[30];
+    assert!(end.y < 0.2, "rope has swung down to the ground");
+
+    let tearing_rope = app.world.get::<Rope>(tearing_rope).unwrap();
+    assert!(tearing_rope.is_torn());
+}
+
+#[cfg(all(feature = "3d", feature = "deformable"))]
+#[test]
+fn fluid_pushes_dynamic_body_and_stays_above_ground() {
+    let mut app = create_app();
+
+    app.world.spawn((
+        RigidBody::Static,
+        Position(Vector::NEG_Y * 0.5),
+        Collider::cuboid(10.0, 1.0, 10.0),
+    ));
+    let body = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            Position(Vector::Y * 0.5),
+            Collider::cuboid(1.0, 0.2, 1.0),
+            GravityScale(0.0),
+            LockedAxes::ROTATION_LOCKED,
+        ))
+        .id();
+
+    // A block of water falling onto the floating body
+    let mut fluid = FluidParticles::default().with_particle_radius(0.05);
+    for x in 0..5 {
+        for y in 0..5 {
+            for z in 0..5 {
+                let position = Vector::new(x as Scalar, y as Scalar, z as Scalar) * 0.1
+                    + Vector::new(-0.2, 0.8, -0.2);
+                fluid.spawn(position, Vector::ZERO);
+            }
+        }
+    }
+    app.insert_resource(fluid);
+
+    for _ in 0..30 {
+        tick_60_fps(&mut app);
+    }
+
+    let body_position = app.world.get::<Position>(body).unwrap();
+    assert!(body_position.y < 0.5, "fluid pushes the body down");
+
+    let fluid = app.world.resource::<FluidParticles>();
+    assert_eq!(fluid.len(), 125);
+    for position in &fluid.particles.positions {
+        assert!(position.y > -0.01, "fluid doesn't pass through the ground");
+    }
+}
+
+#[cfg(all(feature = "3d", feature = "deformable"))]
+#[test]
+fn physics_particles_pile_up_without_overlapping() {
+    let mut app = create_app();
+
+    app.world.spawn((
+        RigidBody::Static,
+        Position(Vector::NEG_Y * 0.5),
+        Collider::cuboid(10.0, 1.0, 10.0),
+    ));
+
+    let mut sand = PhysicsParticles::default();
+    for x in 0..10 {
+        for y in 0..10 {
+            for z in 0..10 {
+                let position =
+                    Vector::new(x as Scalar, y as Scalar, z as Scalar) * 0.11 + Vector::Y * 0.5;
+                sand.spawn(position, Vector::ZERO, 0.05);
+            }
+        }
+    }
+    let entity = app.world.spawn(sand).id();
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    let sand = app.world.get::<PhysicsParticles>(entity).unwrap();
+    let particles = &sand.particles;
+    assert_eq!(particles.len(), 1000);
+
+    for (position, velocity) in particles.positions.iter().zip(&particles.velocities) {
+        assert!(position.y > 0.04, "particle doesn't sink into the ground");
+        assert!(velocity.length() < 1.0, "particles have landed");
+    }
+
+    for (i, a) in particles.positions.iter().enumerate() {
+        for b in &particles.positions[i + 1..] {
+            assert!(a.distance(*b) > 0.08, "particles don't overlap");
+        }
+    }
+}
+
+#[cfg(all(feature = "2d", feature = "deformable"))]
+#[test]
+fn soft_blob_lands_on_collider_and_keeps_area() {
+    let mut app = create_app();
+
+    app.world.spawn((
+        RigidBody::Static,
+        Position(Vector::NEG_Y * 0.5),
+        Collider::cuboid(10.0, 1.0),
+    ));
+
+    let blob = SoftBlob::circle(0.5, 24).with_particle_radius(0.01);
+    let rest_area = blob.area();
+
+    let entity = app
+        .world
+        .spawn((blob.clone(), Transform::from_xyz(0.0, 1.5, 0.0)))
+        .id();
+    let scaled = app
+        .world
+        .spawn((
+            blob,
+            Transform::from_xyz(3.0, 2.0, 0.0).with_scale(Vec3::splat(2.0)),
+        ))
+        .id();
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    // The scaled blob keeps its scaled area
+    let scaled = app.world.get::<SoftBlob>(scaled).unwrap();
+    assert_relative_eq!(scaled.area(), 4.0 * rest_area, epsilon = 0.2);
+
+    let blob = app.world.get::<SoftBlob>(entity).unwrap();
+    assert_relative_eq!(blob.area(), rest_area, epsilon = 0.05);
+    for position in &blob.particles.positions {
+        assert!(position.y > -0.01, "blob doesn't pass through the ground");
+        assert!(position.y < 1.1, "blob has landed");
+    }
+}
+
+#[cfg(all(feature = "3d", feature = "fracture"))]
+#[test]
+fn fracture_keeps_mass_and_momentum() {
+    use bevy::ecs::system::Command;
+
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+
+    let velocity = Vector::new(1.0, 0.0, 0.5);
+    let box_body = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            Position(Vector::Y * 5.0),
+            LinearVelocity(velocity),
+            AngularVelocity(Vector::Y),
+            Collider::cuboid(2.0, 1.0, 1.0),
+            ColliderDensity(3.0),
+            Fracturable::new(10).with_meshes(false),
+        ))
+        .id();
+
+    // A body that breaks when it hits the wall
+    let breakable = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            Position(Vector::new(0.0, -5.0, 0.0)),
+            LinearVelocity(Vector::X * 20.0),
+            Collider::cuboid(1.0, 1.0, 1.0),
+            Fracturable::new(6).with_force_threshold(100.0),
+        ))
+        .id();
+    app.world.spawn((
+        SpatialBundle::default(),
+        RigidBody::Static,
+        Position(Vector::new(2.0, -5.0, 0.0)),
+        Collider::cuboid(1.0, 10.0, 10.0),
+    ));
+
+    tick_60_fps(&mut app);
+
+    Fracture::new(box_body)
+        .with_impact_point(Vector::new(1.0, 5.0, 0.0))
+        .apply(&mut app.world);
+
+    let events = app.world.resource::<Events<Fractured>>();
+    let fragments = events
+        .get_reader()
+        .read(events)
+        .next()
+        .unwrap()
+        .fragments
+        .clone();
+    assert!(app.world.get_entity(box_body).is_none());
+    assert!(fragments.len() >= 8);
+
+    // initialize the mass properties of the fragments
+    tick_60_fps(&mut app);
+
+    let mut total_mass = 0.0;
+    let mut momentum = Vector::ZERO;
+    for fragment in fragments {
+        let mass = app.world.get::<Mass>(fragment).unwrap().0;
+        total_mass += mass;
+        momentum += mass * app.world.get::<LinearVelocity>(fragment).unwrap().0;
+    }
+    assert_relative_eq!(total_mass, 6.0, epsilon = 0.01);
+    assert_relative_eq!(momentum, velocity * 6.0, epsilon = 0.05);
+
+    for _ in 0..30 {
+        tick_60_fps(&mut app);
+    }
+
+    assert!(
+        app.world.get_entity(breakable).is_none(),
+        "body breaks when it hits the wall"
+    );
+}
+
+#[cfg(all(feature = "3d", feature = "fracture"))]
+#[test]
+fn fracture_keeps_children_that_are_not_colliders() {
+    use bevy::ecs::system::Command;
+
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+
+    let body = app
+        .world
+        .spawn((
+            SpatialBundle::from_transform(Transform::from_xyz(0.0, 5.0, 0.0)),
+            RigidBody::Dynamic,
+            Collider::cuboid(2.0, 1.0, 1.0),
+            Fracturable::new(4).with_meshes(false),
+        ))
+        .id();
+    let collider_child = app
+        .world
+        .spawn((
+            SpatialBundle::from_transform(Transform::from_xyz(0.0, 1.0, 0.0)),
+            Collider::ball(0.25),
+        ))
+        .set_parent(body)
+        .id();
+    let effect = app
+        .world
+        .spawn(SpatialBundle::from_transform(Transform::from_xyz(
+            0.9, 0.0, 0.0,
+        )))
+        .set_parent(body)
+        .id();
+
+    tick_60_fps(&mut app);
+
+    Fracture::new(body).apply(&mut app.world);
+
+    assert!(app.world.get_entity(body).is_none());
+    assert!(app.world.get_entity(collider_child).is_none());
+
+    // The child is moved to a fragment without moving in world space
+    let parent = app.world.get::<Parent>(effect).unwrap().get();
+    assert!(app.world.get::<RigidBody>(parent).is_some());
+
+    tick_60_fps(&mut app);
+
+    let global_transform = app.world.get::<GlobalTransform>(effect).unwrap();
+    assert_relative_eq!(
+        global_transform.translation(),
+        Vec3::new(0.9, 5.0, 0.0),
+        epsilon = 0.01
+    );
+}
+
+#[cfg(all(feature = "3d", feature = "ragdoll"))]
+#[test]
+fn ragdoll_follows_animation_and_drives_bones() {
+    use bevy::render::mesh::skinning::SkinnedMesh;
+
+    // The ragdoll collides with the world but not with itself
+    let world_layers = CollisionLayers::from_bits(0b01, 0b11);
+    let ragdoll_layers = CollisionLayers::from_bits(0b10, 0b01);
+
+    let mut app = create_app();
+
+    app.world.spawn((
+        RigidBody::Static,
+        Position(Vector::NEG_Y),
+        Collider::cuboid(10.0, 2.0, 10.0),
+        world_layers,
+    ));
+
+    let bone = |name: &str| {
+        (
+            Name::new(name.to_string()),
+            TransformBundle::from_transform(Transform::from_xyz(0.0, 0.5, 0.0)),
+        )
+    };
+    let root = app
+        .world
+        .spawn((
+            SpatialBundle::from_transform(Transform::from_xyz(0.0, 2.0, 0.0)),
+            Ragdoll::new()
+                .with_default_bone(RagdollBone::new(0.1, 5.0))
+                .with_bone("head", RagdollBone::new(0.15, 3.0).with_length(0.3))
+                .with_collision_layers(ragdoll_layers),
+        ))
+        .id();
+    let hips = app.world.spawn(bone("hips")).set_parent(root).id();
+    let spine = app.world.spawn(bone("spine")).set_parent(hips).id();
+    let head = app.world.spawn(bone("head")).set_parent(spine).id();
+    app.world
+        .spawn((
+            SpatialBundle::default(),
+            SkinnedMesh {
+                inverse_bindposes: Handle::default(),
+                joints: vec![hips, spine, head],
+            },
+        ))
+        .set_parent(root);
+
+    tick_60_fps(&mut app);
+
+    let bodies = app.world.get::<Ragdoll>(root).unwrap().bodies().to_vec();
+    assert_eq!(bodies.len(), 3);
+    let mut joints = app.world.query::<&SphericalJoint>();
+    assert_eq!(joints.iter(&app.world).count(), 2);
+
+    // The bodies follow the animated bones while the ragdoll is disabled
+    app.world.get_mut::<Transform>(root).unwrap().translation.x = 1.0;
+    for _ in 0..3 {
+        tick_60_fps(&mut app);
+    }
+
+    let head_body = bodies[2];
+    assert_eq!(app.world.get::<RagdollBody>(head_body).unwrap().bone, head);
+    assert_eq!(
+        *app.world.get::<RigidBody>(head_body).unwrap(),
+        RigidBody::Kinematic
+    );
+    let head_position = app.world.get::<Position>(head_body).unwrap().0;
+    assert_relative_eq!(head_position, Vector::new(1.0, 3.5, 0.0), epsilon = 0.001);
+
+    // The ragdoll falls to the ground and drives the bones
+    app.world.get_mut::<Ragdoll>(root).unwrap().enabled = true;
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    let head_position = app.world.get::<Position>(head_body).unwrap().0;
+    let head_bone = app
+        .world
+        .get::<GlobalTransform>(head)
+        .unwrap()
+        .translation();
+    assert!(head_position.y > 0.0 && head_position.y < 0.5);
+    assert_relative_eq!(head_bone.adjust_precision(), head_position, epsilon = 0.05);
+
+    let hips_bone = app
+        .world
+        .get::<GlobalTransform>(hips)
+        .unwrap()
+        .translation();
+    let spine_bone = app
+        .world
+        .get::<GlobalTransform>(spine)
+        .unwrap()
+        .translation();
+    assert_relative_eq!(hips_bone.distance(spine_bone), 0.5, epsilon = 0.01);
+
+    // Changing the bone profiles rebuilds the ragdoll, and the head now uses the default profile
+    assert_eq!(app.world.get::<Ragdoll>(root).unwrap().bodies(), bodies);
+    app.world
+        .get_mut::<Ragdoll>(root)
+        .unwrap()
+        .bones
+        .remove("head");
+    tick_60_fps(&mut app);
+
+    let rebuilt = app.world.get::<Ragdoll>(root).unwrap().bodies().to_vec();
+    assert_eq!(rebuilt.len(), 3);
+    assert!(bodies
+        .iter()
+        .all(|body| app.world.get_entity(*body).is_none()));
+    assert_eq!(joints.iter(&app.world).count(), 2);
+}
+
+#[cfg(all(feature = "3d", feature = "ragdoll"))]
+#[test]
+fn ragdoll_is_only_built_in_its_schedule() {
+    use bevy::render::mesh::skinning::SkinnedMesh;
+
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct CustomSchedule;
+
+    let mut app = App::new();
+    #[cfg(any(feature = "async-collider", feature = "collider-asset"))]
+    app.add_plugins(bevy::asset::AssetPlugin::default());
+    app.add_plugins((
+        MinimalPlugins,
+        TransformPlugin,
+        PhysicsPlugins::new(CustomSchedule),
+        RagdollPlugin::new(CustomSchedule),
+    ));
+    #[cfg(feature = "async-collider")]
+    {
+        app.init_resource::<Assets<Mesh>>();
+        app.add_plugins(bevy::scene::ScenePlugin);
+    }
+    app.insert_resource(TimeUpdateStrategy::ManualInstant(Instant::now()));
+
+    let root = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            Ragdoll::new().with_default_bone(RagdollBone::new(0.1, 1.0).with_length(0.5)),
+        ))
+        .id();
+    let hips = app
+        .world
+        .spawn((Name::new("hips"), TransformBundle::default()))
+        .set_parent(root)
+        .id();
+    let spine = app
+        .world
+        .spawn((
+            Name::new("spine"),
+            TransformBundle::from_transform(Transform::from_xyz(0.0, 0.5, 0.0)),
+        ))
+        .set_parent(hips)
+        .id();
+    app.world
+        .spawn((
+            SpatialBundle::default(),
+            SkinnedMesh {
+                inverse_bindposes: Handle::default(),
+                joints: vec![hips, spine],
+            },
+        ))
+        .set_parent(root);
+
+    // Nothing is built while the custom schedule isn't running
+    for _ in 0..2 {
+        tick_60_fps(&mut app);
+    }
+    assert!(app.world.get::<Ragdoll>(root).unwrap().bodies().is_empty());
+
+    app.add_systems(Update, |world: &mut World| {
+        world.run_schedule(CustomSchedule);
+    });
+    tick_60_fps(&mut app);
+
+    assert_eq!(app.world.get::<Ragdoll>(root).unwrap().bodies().len(), 2);
+}
+
+#[cfg(all(feature = "3d", feature = "ragdoll"))]
+#[test]
+fn active_ragdoll_drives_follow_animation_within_max_torque() {
+    use bevy::render::mesh::skinning::SkinnedMesh;
+
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    let target_rotation = Quat::from_rotation_z(1.0);
+
+    // Spawns a ragdoll with a driven arm and returns the ragdoll and the arm bone
+    let mut spawn_ragdoll = |x: f32, drive: RagdollDrive| {
+        let root = app
+            .world
+            .spawn((
+                SpatialBundle::from_transform(Transform::from_xyz(x, 0.0, 0.0)),
+                Ragdoll::new()
+                    .with_bone("torso", RagdollBone::new(0.2, 10.0).with_length(0.6))
+                    .with_bone(
+                        "arm",
+                        RagdollBone::new(0.1, 1.0)
+                            .with_length(0.5)
+                            .with_drive(drive),
+                    )
+                    .with_enabled(true)
+                    .with_animation_blend(1.0)
+                    .with_collision_layers(CollisionLayers::none()),
+            ))
+            .id();
+        let torso = app
+            .world
+            .spawn((Name::new("torso"), TransformBundle::default()))
+            .set_parent(root)
+            .id();
+        let arm = app
+            .world
+            .spawn((
+                Name::new("arm"),
+                TransformBundle::from_transform(Transform::from_xyz(0.0, 0.6, 0.0)),
+            ))
+            .set_parent(torso)
+            .id();
+        app.world
+            .spawn((
+                SpatialBundle::default(),
+                SkinnedMesh {
+                    inverse_bindposes: Handle::default(),
+                    joints: vec![torso, arm],
+                },
+            ))
+            .set_parent(root);
+        (root, arm)
+    };
+
+    let strong = spawn_ragdoll(0.0, RagdollDrive::new(0.0, 1000.0));
+    let weak = spawn_ragdoll(5.0, RagdollDrive::new(0.0, 0.0001));
+
+    tick_60_fps(&mut app);
+
+    // Animate the arms every frame
+    for _ in 0..60 {
+        for (_, arm) in [strong, weak] {
+            app.world.get_mut::<Transform>(arm).unwrap().rotation = target_rotation;
+        }
+        tick_60_fps(&mut app);
+    }
+
+    let relative_rotation = |app: &App, root: Entity| {
+        let bodies = app.world.get::<Ragdoll>(root).unwrap().bodies();
+        let torso = app.world.get::<Rotation>(bodies[0]).unwrap().0;
+        let arm = app.world.get::<Rotation>(bodies[1]).unwrap().0;
+        (torso.inverse() * arm).as_f32()
+    };
+
+    // The strong drive reaches the animated pose, but the weak drive can't rotate the arm
+    assert!(relative_rotation(&app, strong.0).angle_between(target_rotation) < 0.05);
+    assert!(relative_rotation(&app, weak.0).angle_between(Quat::IDENTITY) < 0.1);
+}
+
+#[cfg(feature = "3d")]
+#[test]
+fn voxel_collider_supports_contacts_queries_and_edits() {
+    // A 4x1x4 floor of unit voxels with its top at y = 0
+    let voxels = VoxelCollider::from_solid(UVec3::new(4, 1, 4), Vector::ONE, [true; 16]);
+    let floor = Collider::voxels(voxels);
+    assert_relative_eq!(floor.mass_properties(1.0).mass.0, 16.0, epsilon = 0.001);
+
+    // A box that slightly overlaps the next voxel only gets contacts on the top faces,
+    // even though the overlap with the side of the next voxel is smaller than the penetration depth
+    let manifolds = contact_query::contact_manifolds(
+        &floor,
+        Vector::ZERO,
+        Quaternion::IDENTITY,
+        &Collider::cuboid(0.55, 1.0, 0.5),
+        Vector::new(1.775, 1.4, 2.5),
+        Quaternion::IDENTITY,
+        0.0,
+    );
+    assert!(!manifolds.is_empty());
+    assert!(manifolds.iter().all(|manifold| manifold.normal1.y > 0.999));
+
+    let mut app = create_app();
+    app.insert_resource(SubstepCount(20));
+
+    let floor_entity = app
+        .world
+        .spawn((
+            RigidBody::Static,
+            floor,
+            Position(Vector::new(-2.0, -1.0, -2.0)),
+        ))
+        .id();
+    let body = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            Collider::cuboid(1.0, 1.0, 1.0),
+            Position(Vector::Y * 2.0),
+        ))
+        .id();
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    let position = app.world.get::<Position>(body).unwrap();
+    assert_relative_eq!(position.y, 0.5, epsilon = 0.05);
+
+    let ray_hits = |app: &App| {
+        app.world
+            .resource::<SpatialQueryPipeline>()
+            .cast_ray(
+                Vector::new(1.5, 5.0, 1.5),
+                Vector::NEG_Y,
+                10.0,
+                true,
+                SpatialQueryFilter::default(),
+            )
+            .map(|hit| hit.time_of_impact)
+    };
+    assert_relative_eq!(ray_hits(&app).unwrap(), 5.0, epsilon = 0.001);
+
+    // Digging out the voxel under the ray lets it pass through the floor
+    app.world
+        .get_mut::<Collider>(floor_entity)
+        .unwrap()
+        .set_voxel(UVec3::new(3, 0, 3), false)
+        .unwrap();
+    tick_60_fps(&mut app);
+    assert!(ray_hits(&app).is_none());
+}
+
+#[cfg(feature = "3d")]
+#[test]
+fn custom_shapes_support_contacts_and_queries() {
+    use parry::{
+        bounding_volume::Aabb,
+        mass_properties::MassProperties,
+        query::{PointQuery, Ray, RayCast},
+        shape::Shape,
+    };
+
+    /// A non-convex ground slab with its top at y = 0 that only collides with balls
+    #[derive(Debug)]
+    struct Ground;
+
+    impl CustomShape for Ground {
+        fn compute_local_aabb(&self) -> ColliderAabb {
+            ColliderAabb(Aabb::new(
+                Vector::new(-10.0, -1.0, -10.0).into(),
+                Vector::new(10.0, 0.0, 10.0).into(),
+            ))
+        }
+
+        fn mass_properties(&self, density: Scalar) -> MassProperties {
+            MassProperties::from_cuboid(density, Vector::new(10.0, 0.5, 10.0).into())
+        }
+
+        fn project_point(&self, point: Vector, _solid: bool) -> (Vector, bool) {
+            (Vector::new(point.x, 0.0, point.z), point.y <= 0.0)
+        }
+
+        fn cast_ray(
+            &self,
+            origin: Vector,
+            direction: Vector,
+            max_time_of_impact: Scalar,
+            _solid: bool,
+        ) -> Option<(Scalar, Vector)> {
+            let time_of_impact = -origin.y / direction.y;
+            (time_of_impact >= 0.0 && time_of_impact <= max_time_of_impact)
+                .then_some((time_of_impact, Vector::Y))
+        }
+
+        fn contact(
+            &self,
+            other: &dyn Shape,
+            position: Vector,
+            rotation: Rotation,
+            prediction_distance: Scalar,
+        ) -> Option<ContactData> {
+            let radius = other.as_ball()?.radius;
+            let distance = position.y - radius;
+            (distance <= prediction_distance).then(|| ContactData {
+                point1: Vector::new(position.x, 0.0, position.z),
+                point2: rotation.inverse().rotate(Vector::NEG_Y * radius),
+                normal1: Vector::Y,
+                normal2: rotation.inverse().rotate(Vector::NEG_Y),
+                penetration: -distance,
+            })
+        }
+    }
+
+    /// A convex box described only by its support mapping
+    #[derive(Debug)]
+    struct SupportBox(Vector);
+
+    impl CustomShape for SupportBox {
+        fn compute_local_aabb(&self) -> ColliderAabb {
+            ColliderAabb(Aabb::new((-self.0).into(), self.0.into()))
+        }
+
+        fn mass_properties(&self, density: Scalar) -> MassProperties {
+            MassProperties::from_cuboid(density, self.0.into())
+        }
+
+        fn project_point(&self, point: Vector, solid: bool) -> (Vector, bool) {
+            let projection =
+                parry::shape::Cuboid::new(self.0.into()).project_local_point(&point.into(), solid);
+            (projection.point.into(), projection.is_inside)
+        }
+
+        fn cast_ray(
+            &self,
+            origin: Vector,
+            direction: Vector,
+            max_time_of_impact: Scalar,
+            solid: bool,
+        ) -> Option<(Scalar, Vector)> {
+            parry::shape::Cuboid::new(self.0.into())
+                .cast_local_ray_and_get_normal(
+                    &Ray::new(origin.into(), direction.into()),
+                    max_time_of_impact,
+                    solid,
+                )
+                .map(|hit| (hit.toi, hit.normal.into()))
+        }
+
+        fn support_point(&self, direction: Vector) -> Option<Vector> {
+            Some(Vector::select(
+                direction.cmplt(Vector::ZERO),
+                -self.0,
+                self.0,
+            ))
+        }
+    }
+
+    let custom_box = Collider::custom(SupportBox(Vector::splat(0.5)));
+    assert!(custom_box.as_custom::<SupportBox>().is_some());
+    assert!(custom_box.as_custom::<Ground>().is_none());
+    assert_relative_eq!(custom_box.mass_properties(2.0).mass.0, 2.0, epsilon = 0.001);
+
+    let mut app = create_app();
+    app.insert_resource(SubstepCount(20));
+
+    // A ball falls on the non-convex ground, and the custom box falls on a regular cuboid
+    app.world
+        .spawn((RigidBody::Static, Collider::custom(Ground)));
+    app.world.spawn((
+        RigidBody::Static,
+        Collider::cuboid(4.0, 1.0, 4.0),
+        Position(Vector::new(20.0, -0.5, 0.0)),
+    ));
+    let ball = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            Collider::ball(0.5),
+            Position(Vector::Y * 2.0),
+        ))
+        .id();
+    let body = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            custom_box,
+            Position(Vector::new(20.0, 2.0, 0.0)),
+        ))
+        .id();
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    assert_relative_eq!(
+        app.world.get::<Position>(ball).unwrap().y,
+        0.5,
+        epsilon = 0.05
+    );
+    assert_relative_eq!(
+        app.world.get::<Position>(body).unwrap().y,
+        0.5,
+        epsilon = 0.05
+    );
+
+    // Rays hit the custom shapes through the spatial query pipeline
+    let pipeline = app.world.resource::<SpatialQueryPipeline>();
+    let hit = pipeline
+        .cast_ray(
+            Vector::new(-5.0, 5.0, 0.0),
+            Vector::NEG_Y,
+            10.0,
+            true,
+            SpatialQueryFilter::default(),
+        )
+        .unwrap();
+    assert_relative_eq!(hit.time_of_impact, 5.0, epsilon = 0.001);
+    assert_relative_eq!(hit.normal, Vector::Y, epsilon = 0.001);
+
+    let hit = pipeline
+        .cast_ray(
+            Vector::new(20.0, 5.0, 0.0),
+            Vector::NEG_Y,
+            10.0,
+            true,
+            SpatialQueryFilter::default(),
+        )
+        .unwrap();
+    assert_eq!(hit.entity, body);
+    assert_relative_eq!(hit.time_of_impact, 4.0, epsilon = 0.05);
+}
+
+#[test]
+fn custom_shapes_rest_on_faces_with_polygonal_manifolds() {
+    use parry::{
+        bounding_volume::Aabb,
+        mass_properties::MassProperties,
+        query::{PointQuery, Ray, RayCast},
+        shape::Cuboid,
+    };
+
+    /// A convex box described only by its support mapping
+    #[derive(Debug)]
+    struct SupportBox(Vector);
+
+    impl CustomShape for SupportBox {
+        fn compute_local_aabb(&self) -> ColliderAabb {
+            ColliderAabb(Aabb::new((-self.0).into(), self.0.into()))
+        }
+
+        fn mass_properties(&self, density: Scalar) -> MassProperties {
+            MassProperties::from_cuboid(density, self.0.into())
+        }
+
+        fn project_point(&self, point: Vector, solid: bool) -> (Vector, bool) {
+            let projection = Cuboid::new(self.0.into()).project_local_point(&point.into(), solid);
+            (projection.point.into(), projection.is_inside)
+        }
+
+        fn cast_ray(
+            &self,
+            origin: Vector,
+            direction: Vector,
+            max_time_of_impact: Scalar,
+            solid: bool,
+        ) -> Option<(Scalar, Vector)> {
+            Cuboid::new(self.0.into())
+                .cast_local_ray_and_get_normal(
+                    &Ray::new(origin.into(), direction.into()),
+                    max_time_of_impact,
+                    solid,
+                )
+                .map(|hit| (hit.toi, hit.normal.into()))
+        }
+
+        fn support_point(&self, direction: Vector) -> Option<Vector> {
+            Some(Vector::select(
+                direction.cmplt(Vector::ZERO),
+                -self.0,
+                self.0,
+            ))
+        }
+    }
+
+    let custom_box = Collider::custom(SupportBox(Vector::splat(0.5)));
+    #[cfg(feature = "2d")]
+    let (floor, corner_count) = (Collider::cuboid(4.0, 1.0), 2);
+    #[cfg(feature = "3d")]
+    let (floor, corner_count) = (Collider::cuboid(4.0, 1.0, 4.0), 4);
+
+    // A contact for each corner of the bottom face, in both orders
+    let manifolds = contact_query::contact_manifolds(
+        &custom_box,
+        Vector::Y * 0.99,
+        Rotation::default(),
+        &floor,
+        Vector::ZERO,
+        Rotation::default(),
+        0.0,
+    );
+    assert_eq!(manifolds.len(), 1);
+    assert_eq!(manifolds[0].contacts.len(), corner_count);
+    for contact in &manifolds[0].contacts {
+        assert_relative_eq!(contact.penetration, 0.01, epsilon = 0.001);
+    }
+    let manifolds = contact_query::contact_manifolds(
+        &floor,
+        Vector::ZERO,
+        Rotation::default(),
+        &custom_box,
+        Vector::Y * 0.99,
+        Rotation::default(),
+        0.0,
+    );
+    assert_eq!(manifolds[0].contacts.len(), corner_count);
+
+    // The box comes to rest without tipping over
+    let mut app = create_app();
+    app.world
+        .spawn((RigidBody::Static, floor, Position(Vector::NEG_Y * 0.5)));
+    let body = app
+        .world
+        .spawn((RigidBody::Dynamic, custom_box, Position(Vector::Y * 2.0)))
+        .id();
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    assert_relative_eq!(
+        app.world.get::<Position>(body).unwrap().y,
+        0.5,
+        epsilon = 0.05
+    );
+    let angular_velocity = app.world.get::<AngularVelocity>(body).unwrap().0;
+    #[cfg(feature = "2d")]
+    assert!(angular_velocity.abs() < 0.01);
+    #[cfg(feature = "3d")]
+    assert!(angular_velocity.length() < 0.01);
+}
+
+#[cfg(feature = "collider-asset")]
+#[test]
+fn collider_assets_are_shared_and_hot_reloaded() {
+    let asset = ColliderAsset::new(Collider::ball(0.5));
+
+    // Both file formats round-trip the shape
+    let from_ron = ColliderAsset::from_ron(&asset.to_ron().unwrap()).unwrap();
+    let from_bytes = ColliderAsset::from_bytes(&asset.to_bytes().unwrap()).unwrap();
+    for collider in [&from_ron, &from_bytes] {
+        assert_eq!(collider.shape().as_ball().unwrap().radius, 0.5);
+    }
+    assert!(ColliderAsset::from_ron("not a collider").is_err());
+
+    let mut app = create_app();
+
+    let handle = app.world.resource_mut::<Assets<ColliderAsset>>().add(asset);
+    let entity1 = app.world.spawn(handle.clone()).id();
+    let entity2 = app.world.spawn(handle.clone()).id();
+
+    tick_60_fps(&mut app);
+
+    // The entities share the shape of the asset
+    let collider1 = app.world.get::<Collider>(entity1).unwrap();
+    let collider2 = app.world.get::<Collider>(entity2).unwrap();
+    assert_eq!(collider1.shape().as_ball().unwrap().radius, 0.5);
+    assert!(std::sync::Arc::ptr_eq(
+        &collider1.shape().0,
+        &collider2.shape().0
+    ));
+
+    // Modifying the asset replaces the colliders
+    app.world
+        .resource_mut::<Assets<ColliderAsset>>()
+        .get_mut(&handle)
+        .unwrap()
+        .0 = Collider::ball(2.0);
+
+    tick_60_fps(&mut app);
+
+    for entity in [entity1, entity2] {
+        let collider = app.world.get::<Collider>(entity).unwrap();
+        assert_eq!(collider.shape().as_ball().unwrap().radius, 2.0);
+    }
+}
+
+#[cfg(all(feature = "3d", feature = "async-collider", feature = "collider-asset"))]
+#[test]
+fn convex_decompositions_are_cached_on_disk() {
+    use std::path::PathBuf;
+
+    let directory = std::env::temp_dir().join(format!(
+        "bevy_xpbd_decomposition_cache_{}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&directory);
+    let cache = ConvexDecompositionCache::new(&directory);
+
+    let mesh = Mesh::from(shape::Cube { size: 1.0 });
+    let parameters = VHACDParameters {
+        resolution: 16,
+        ..default()
+    };
+
+    // The key depends on both the mesh and the parameters
+    let key = ConvexDecompositionCache::key(&mesh, &parameters).unwrap();
+    assert_eq!(ConvexDecompositionCache::key(&mesh, &parameters), Some(key));
+    assert_ne!(
+        ConvexDecompositionCache::key(&Mesh::from(shape::Cube { size: 2.0 }), &parameters),
+        Some(key)
+    );
+    assert_ne!(
+        ConvexDecompositionCache::key(&mesh, &VHACDParameters::default()),
+        Some(key)
+    );
+
+    assert!(cache.load(&mesh, &parameters).is_none());
+    let baked = cache.bake(&mesh, &parameters).unwrap().unwrap();
+    assert!(cache.path(key).exists());
+    let loaded = cache.load(&mesh, &parameters).unwrap();
+    assert_eq!(
+        loaded.shape().as_compound().unwrap().shapes().len(),
+        baked.shape().as_compound().unwrap().shapes().len()
+    );
+
+    // Replace the cached decomposition to check that async colliders load it instead of recomputing it
+    cache
+        .store(&mesh, &parameters, &Collider::ball(0.25))
+        .unwrap();
+
+    // The temporary files are renamed into place, so only the decomposition is left in the directory
+    let files: Vec<PathBuf> = std::fs::read_dir(&directory)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    assert_eq!(files, vec![cache.path(key)]);
+
+    let mut app = create_app();
+    app.insert_resource(cache);
+    let mesh_handle = app.world.resource_mut::<Assets<Mesh>>().add(mesh);
+    let entity = app
+        .world
+        .spawn((
+            mesh_handle,
+            AsyncCollider(ComputedCollider::ConvexDecomposition(parameters)),
+        ))
+        .id();
+
+    tick_until_colliders_computed(&mut app);
+
+    let collider = app.world.get::<Collider>(entity).unwrap();
+    assert_eq!(collider.shape().as_ball().unwrap().radius, 0.25);
+
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[cfg(all(feature = "3d", feature = "async-collider"))]
+#[test]
+fn async_colliders_are_computed_in_background() {
+    use bevy::render::mesh::PrimitiveTopology;
+
+    let mut app = create_app();
+    let mut meshes = app.world.resource_mut::<Assets<Mesh>>();
+    let cube = meshes.add(Mesh::from(shape::Cube { size: 1.0 }));
+    let empty = meshes.add(Mesh::new(PrimitiveTopology::TriangleList));
+    let loading = Handle::<Mesh>::weak_from_u128(0x4a5b_6c7d);
+
+    let cube_entity = app
+        .world
+        .spawn((cube, AsyncCollider(ComputedCollider::ConvexHull)))
+        .id();
+    let empty_entity = app
+        .world
+        .spawn((empty, AsyncCollider(ComputedCollider::ConvexHull)))
+        .id();
+    let loading_entity = app
+        .world
+        .spawn((loading.clone(), AsyncCollider(ComputedCollider::ConvexHull)))
+        .id();
+
+    let mut event_reader = app
+        .world
+        .resource::<Events<ColliderComputed>>()
+        .get_reader();
+    let mut computed = vec![];
+
+    // The colliders are pending until the tasks have finished
+    tick_60_fps(&mut app);
+    for entity in [cube_entity, empty_entity] {
+        assert!(app.world.get::<ColliderPending>(entity).is_some());
+        assert!(app.world.get::<AsyncCollider>(entity).is_none());
+        assert!(app.world.get::<Collider>(entity).is_none());
+    }
+
+    // The collider is also pending while the mesh is loading
+    assert!(app.world.get::<ColliderPending>(loading_entity).is_some());
+    assert!(app.world.get::<AsyncCollider>(loading_entity).is_some());
+    app.world
+        .resource_mut::<Assets<Mesh>>()
+        .insert(loading.id(), Mesh::from(shape::Cube { size: 1.0 }));
+
+    for _ in 0..1000 {
+        tick_60_fps(&mut app);
+        let events = app.world.resource::<Events<ColliderComputed>>();
+        computed.extend(event_reader.read(events).copied());
+        if computed.len() == 3 {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(5));
+    }
+
+    computed.sort_by_key(|event| event.entity);
+    assert_eq!(
+        computed,
+        vec![
+            ColliderComputed {
+                entity: cube_entity,
+                succeeded: true
+            },
+            ColliderComputed {
+                entity: empty_entity,
+                succeeded: false
+            },
+            ColliderComputed {
+                entity: loading_entity,
+                succeeded: true
+            },
+        ]
+    );
+
+    for entity in [cube_entity, empty_entity, loading_entity] {
+        assert!(app.world.get::<ColliderPending>(entity).is_none());
+    }
+    for entity in [cube_entity, loading_entity] {
+        let collider = app.world.get::<Collider>(entity).unwrap();
+        assert_relative_eq!(collider.mass_properties(1.0).mass.0, 1.0, epsilon = 0.001);
+    }
+    assert!(app.world.get::<Collider>(empty_entity).is_none());
+}
+
+#[cfg(all(feature = "3d", feature = "async-collider"))]
+#[test]
+fn scene_colliders_are_configured_by_name_patterns() {
+    use bevy::render::mesh::PrimitiveTopology;
+
+    let scene_collider = AsyncSceneCollider::new(None)
+        .with_shape_for_name("Pillar_col", ComputedCollider::TriMesh)
+        .with_shape_for_pattern("*_col", ComputedCollider::ConvexHull)
+        .with_collision_only_for_pattern("*_col")
+        .with_sensor_for_pattern("Door?_trigger")
+        .with_density_for_pattern("Door?_trigger", 0.5)
+        .without_shape_with_pattern("*_nocol*")
+        .with_collision_mesh_handling(CollisionMeshHandling::Remove);
+
+    // Exact names take priority over patterns, and node names are matched too
+    let pillar = scene_collider.data_for_mesh("Pillar_col", None).unwrap();
+    assert_eq!(pillar.shape, ComputedCollider::TriMesh);
+    assert!(!pillar.collision_only);
+    let wall = scene_collider
+        .data_for_mesh("Cube", Some("Wall_col"))
+        .unwrap();
+    assert_eq!(wall.shape, ComputedCollider::ConvexHull);
+    assert!(wall.collision_only);
+    let door = scene_collider.data_for_mesh("Door1_trigger", None).unwrap();
+    assert!(door.sensor);
+    assert_eq!(door.density, 0.5);
+    assert!(scene_collider
+        .data_for_mesh("Door12_trigger", None)
+        .is_none());
+    assert!(scene_collider.data_for_mesh("Fence_nocol", None).is_none());
+    // Patterns are checked in the order they were added
+    assert!(scene_collider
+        .data_for_mesh("Fence_nocol_col", None)
+        .is_some());
+    assert!(scene_collider.data_for_mesh("Statue", None).is_none());
+
+    let mut app = create_app();
+    app.register_type::<Name>()
+        .register_type::<Parent>()
+        .register_type::<Children>()
+        .register_type::<Handle<Mesh>>();
+
+    let mut meshes = app.world.resource_mut::<Assets<Mesh>>();
+    let mesh = meshes.add(Mesh::from(shape::Cube { size: 1.0 }));
+    let empty_mesh = meshes.add(Mesh::new(PrimitiveTopology::TriangleList));
+
+    // A scene with a collision-only wall, a trigger, a statue without a collider
+    // and a collision-only mesh that no collider can be generated from
+    let mut scene_world = World::new();
+    let mut spawn_node = |node_name: &str, mesh_name: &str, mesh: &Handle<Mesh>| {
+        scene_world
+            .spawn(Name::new(node_name.to_string()))
+            .with_children(|node| {
+                node.spawn((Name::new(mesh_name.to_string()), mesh.clone()));
+            });
+    };
+    spawn_node("Wall_col", "Cube", &mesh);
+    spawn_node("Door1_trigger", "Door", &mesh);
+    spawn_node("Statue", "StatueMesh", &mesh);
+    spawn_node("Broken_col", "BrokenMesh", &empty_mesh);
+    let scene = app
+        .world
+        .resource_mut::<Assets<Scene>>()
+        .add(Scene::new(scene_world));
+
+    app.world
+        .spawn((scene, SpatialBundle::default(), scene_collider));
+
+    tick_60_fps(&mut app);
+    tick_until_colliders_computed(&mut app);
+    tick_60_fps(&mut app);
+
+    // Finds a mesh entity by name. Its parent is a node, unlike the nodes that are children of the scene root.
+    let find_mesh = |world: &mut World, name: &str| {
+        let mut query = world.query::<(Entity, &Name, &Parent)>();
+        query
+            .iter(world)
+            .find(|(_, mesh_name, parent)| {
+                mesh_name.as_str() == name && world.get::<Name>(parent.get()).is_some()
+            })
+            .map(|(entity, ..)| entity)
+            .unwrap()
+    };
+
+    let wall = find_mesh(&mut app.world, "Cube");
+    assert!(app.world.get::<Collider>(wall).is_some());
+    assert!(app.world.get::<Sensor>(wall).is_none());
+    assert!(app.world.get::<Handle<Mesh>>(wall).is_none());
+
+    let door = find_mesh(&mut app.world, "Door");
+    assert!(app.world.get::<Collider>(door).is_some());
+    assert!(app.world.get::<Sensor>(door).is_some());
+    assert_eq!(app.world.get::<ColliderDensity>(door).unwrap().0, 0.5);
+    assert!(app.world.get::<Handle<Mesh>>(door).is_some());
+
+    let statue = find_mesh(&mut app.world, "StatueMesh");
+    assert!(app.world.get::<Collider>(statue).is_none());
+
+    // The mesh is kept if its collider couldn't be generated, and it's no longer marked as collision-only
+    let broken = find_mesh(&mut app.world, "BrokenMesh");
+    assert!(app.world.get::<Collider>(broken).is_none());
+    assert!(app.world.get::<Handle<Mesh>>(broken).is_some());
+    assert!(app
+        .world
+        .inspect_entity(broken)
+        .iter()
+        .all(|component| !component.name().ends_with("CollisionOnlyMesh")));
+}
+
+#[derive(Component, Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Ord)]
+struct Id(usize);
+
+#[cfg(all(feature = "3d", feature = "enhanced-determinism"))]
+#[test]
+fn cubes_simulation_is_deterministic_across_machines() {
+    setup_insta!();
+    let mut app = create_app();
+
+    app.add_systems(Startup, setup_cubes_simulation);
+
+    const SECONDS: usize = 10;
+    const UPDATES: usize = 60 * SECONDS;
+
+    for _ in 0..UPDATES {
+        tick_60_fps(&mut app);
+    }
+
+    let mut app_query = app.world.query::<(&Id, &Transform)>();
+
+    let mut bodies: Vec<(&Id, &Transform)> = app_query.iter(&app.world).collect();
+    bodies.sort_by_key(|b| b.0);
+
+    assert_debug_snapshot!(bodies);
+}
+
+#[cfg(feature = "3d")]
+#[test]
+fn cubes_simulation_is_locally_deterministic() {
+    use itertools::Itertools;
+
+    fn run_cubes() -> Vec<(Id, Transform)> {
+        let mut app = create_app();
+
+        app.add_systems(Startup, setup_cubes_simulation);
+
+        const SECONDS: usize = 5;
+        const UPDATES: usize = 60 * SECONDS;
+
+        for _ in 0..UPDATES {
+            tick_60_fps(&mut app);
+        }
+
+        let mut app_query = app.world.query::<(&Id, &Transform)>();
+
+        let mut bodies: Vec<(Id, Transform)> = app_query
+            .iter(&app.world)
+            .map(|(id, transform)| (*id, *transform))
+            .collect();
+        bodies.sort_by_key(|b| b.0);
+        bodies
+    }
+
+    // run simulation and check that results are equal each time
+    for (a, b) in (0..4).map(|_| run_cubes()).tuple_windows() {
+        assert_eq!(a, b);
+    }
+}
+
+#[test]
+fn no_ambiguity_errors() {
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct DeterministicSchedule;
+
+    let mut app = App::new();
+
+    #[cfg(any(feature = "async-collider", feature = "collider-asset"))]
+    app.add_plugins(bevy::asset::AssetPlugin::default());
+
+    app.add_plugins((MinimalPlugins, PhysicsPlugins::new(DeterministicSchedule)));
+    #[cfg(feature = "deformable")]
+    app.add_plugins(DeformablePlugin::new(DeterministicSchedule));
+    #[cfg(feature = "fracture")]
+    app.add_plugins(FracturePlugin);
+    #[cfg(all(feature = "3d", feature = "ragdoll"))]
+    app.add_plugins(RagdollPlugin::new(DeterministicSchedule));
+
+    #[cfg(feature = "async-collider")]
+    {
+        app.init_resource::<Assets<Mesh>>();
+        #[cfg(feature = "2d")]
+        app.init_resource::<Assets<Image>>();
+        #[cfg(feature = "3d")]
+        app.add_plugins(bevy::scene::ScenePlugin);
     }
 
     app.edit_schedule(DeterministicSchedule, |s| {